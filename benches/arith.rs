@@ -0,0 +1,59 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Standard posit, so that constructors do not need the exponent size spelled out.
+type Posit = unum::Posit;
+
+/// Operands with about `nbits` bits, so that no operation is exact in fewer bits.
+fn operands(nbits: usize) -> (Posit, Posit) {
+    let a = Posit::from(2).sqrt(nbits);
+    let b = Posit::from(3).div_rounded(Posit::from(7), nbits);
+    (a, b)
+}
+
+const WIDTHS: [usize; 4] = [32, 64, 256, 1024];
+
+fn arithmetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("arithmetic");
+    for &nbits in WIDTHS.iter() {
+        let (a, b) = operands(nbits);
+        group.bench_with_input(BenchmarkId::new("add", nbits), &nbits, |bench, _| {
+            bench.iter(|| black_box(&a) + black_box(&b))
+        });
+        group.bench_with_input(BenchmarkId::new("sub", nbits), &nbits, |bench, _| {
+            bench.iter(|| black_box(&a) - black_box(&b))
+        });
+        group.bench_with_input(BenchmarkId::new("mul", nbits), &nbits, |bench, _| {
+            bench.iter(|| black_box(&a) * black_box(&b))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("div_rounded", nbits),
+            &nbits,
+            |bench, &n| bench.iter(|| black_box(a.clone()).div_rounded(black_box(b.clone()), n)),
+        );
+        group.bench_with_input(BenchmarkId::new("eq", nbits), &nbits, |bench, _| {
+            bench.iter(|| black_box(&a) == black_box(&b))
+        });
+    }
+    group.finish();
+}
+
+fn functions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("functions");
+    group.sample_size(20);
+    for &nbits in WIDTHS.iter().take(3) {
+        let (a, _) = operands(nbits);
+        group.bench_with_input(BenchmarkId::new("sqrt", nbits), &nbits, |bench, &n| {
+            bench.iter(|| black_box(a.clone()).sqrt(n))
+        });
+        group.bench_with_input(BenchmarkId::new("exp", nbits), &nbits, |bench, &n| {
+            bench.iter(|| black_box(a.clone()).exp(n))
+        });
+        group.bench_with_input(BenchmarkId::new("parse", nbits), &nbits, |bench, &n| {
+            bench.iter(|| Posit::from_str_rounded(black_box("3.14159265358979e-5"), n))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, arithmetic, functions);
+criterion_main!(benches);
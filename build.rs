@@ -1,4 +1,4 @@
-use cbindgen::{Config, ExportConfig, ItemType, Language};
+use cbindgen::{Config, EnumConfig, ExportConfig, ItemType, Language, RenameRule};
 use std::env;
 use std::path::PathBuf;
 
@@ -14,9 +14,27 @@ fn main() {
     let config = Config {
         language: Language::C,
         export: ExportConfig {
-            item_types: vec![ItemType::Functions, ItemType::Structs],
+            item_types: vec![
+                ItemType::Functions,
+                ItemType::Structs,
+                ItemType::Enums,
+                ItemType::OpaqueItems,
+            ],
+            // Only used by the functions of `softposit_declarations`
+            include: vec![
+                "posit8_t".to_string(),
+                "posit16_t".to_string(),
+                "posit32_t".to_string(),
+            ],
             ..Default::default()
         },
+        enumeration: EnumConfig {
+            rename_variants: Some(RenameRule::ScreamingSnakeCase),
+            prefix_with_name: true,
+            ..Default::default()
+        },
+        documentation: true,
+        trailer: Some(softposit_declarations()),
         ..Default::default()
     };
 
@@ -25,6 +43,81 @@ fn main() {
         .write_to_file(&output_file);
 }
 
+/// Declarations of the SoftPosit functions, which `softposit_api!` generates where cbindgen
+/// cannot see them. `NN` stands for the width of the posit type, `MM` for another width.
+fn softposit_declarations() -> String {
+    const FUNCTIONS: &[(&str, &str)] = &[
+        ("Posit with the given bit pattern.", "pNN castPNN(uintNN_t a)"),
+        ("Sum.", "pNN pNN_add(pNN a, pNN b)"),
+        ("Difference.", "pNN pNN_sub(pNN a, pNN b)"),
+        ("Product.", "pNN pNN_mul(pNN a, pNN b)"),
+        ("Quotient, NaR for a zero divisor.", "pNN pNN_div(pNN a, pNN b)"),
+        ("`a * b + c` rounded once.", "pNN pNN_mulAdd(pNN a, pNN b, pNN c)"),
+        ("Square root, NaR for negative values.", "pNN pNN_sqrt(pNN a)"),
+        ("Nearest integer, ties to even.", "pNN pNN_roundToInt(pNN a)"),
+        ("Whether `a == b`, NaR is only equal to itself.", "bool pNN_eq(pNN a, pNN b)"),
+        ("Whether `a <= b`, NaR is below every other value.", "bool pNN_le(pNN a, pNN b)"),
+        ("Whether `a < b`, NaR is below every other value.", "bool pNN_lt(pNN a, pNN b)"),
+        (
+            "-1, 0 or 1 as `a` is below, equal to or above `b`. NaR is below every other value.",
+            "int32_t pNN_cmp(pNN a, pNN b)",
+        ),
+        (
+            "Nearest integer, ties to even. NaR gives `INT32_MIN`, out of range values saturate.",
+            "int32_t pNN_to_i32(pNN a)",
+        ),
+        (
+            "Nearest integer, ties to even. NaR gives `INT64_MIN`, out of range values saturate.",
+            "int64_t pNN_to_i64(pNN a)",
+        ),
+        (
+            "Nearest integer, ties to even. NaR gives `0x80000000`, negative values give zero.",
+            "uint32_t pNN_to_ui32(pNN a)",
+        ),
+        (
+            "Nearest integer, ties to even. NaR gives `0x8000000000000000`, negative values give\n \
+             * zero.",
+            "uint64_t pNN_to_ui64(pNN a)",
+        ),
+        ("Nearest MM-bit posit, exact if it is wider.", "pMM pNN_to_pMM(pNN a)"),
+        ("Exact value, NaN for NaR.", "double pNN_to_f64(pNN a)"),
+        ("Nearest posit, NaR for NaN and the infinities.", "pNN pNN_from_f64(double a)"),
+        ("Same as `pNN_to_f64`.", "double convertPNNToDouble(pNN a)"),
+        ("Same as `pNN_from_f64`.", "pNN convertDoubleToPNN(double a)"),
+        ("Nearest posit.", "pNN i32_to_pNN(int32_t a)"),
+        ("Nearest posit.", "pNN i64_to_pNN(int64_t a)"),
+        ("Nearest posit.", "pNN ui32_to_pNN(uint32_t a)"),
+        ("Nearest posit.", "pNN ui64_to_pNN(uint64_t a)"),
+    ];
+    let widths = ["8", "16", "32"];
+    let mut declarations = String::new();
+    for &width in &widths {
+        for &(doc, declaration) in FUNCTIONS {
+            // Conversions are declared to each other width
+            let others = widths.iter().filter(|&&other| other != width);
+            let others: Vec<&str> = if declaration.contains("MM") {
+                others.copied().collect()
+            } else {
+                vec![""]
+            };
+            for other in others {
+                let substitute = |s: &str| {
+                    s.replace("pMM ", &format!("posit{}_t ", other))
+                        .replace("pNN ", &format!("posit{}_t ", width))
+                        .replace("MM", other)
+                        .replace("NN", width)
+                };
+                declarations += &format!(
+                    "\n/**\n * {}\n */\n{};\n",
+                    substitute(doc),
+                    substitute(declaration)
+                );
+            }
+        }
+    }
+    declarations
+}
+
 /// Find the location of the `target/` directory. Note that this may be
 /// overridden by `cmake`, so we also need to check the `CARGO_TARGET_DIR`
 /// variable.
@@ -0,0 +1,91 @@
+//! Solves `H x = b` for Hilbert matrices `H`, whose condition number grows exponentially with
+//! their size, and prints the largest error of each solver.
+//!
+//! The right-hand side is chosen so that the exact solution is a vector of ones. `P32 na` is
+//! the LU decomposition of nalgebra on posits, which rounds every operation, the other posit
+//! solvers accumulate their dot products in the quire.
+
+use nalgebra::{DMatrix, DVector};
+use unum::{Cholesky, Lu, Posit, PositField, Qr, Quire, P32, P64};
+
+/// Bits of the elements before they are rounded to the tested type.
+const EXACT_BITS: usize = 256;
+
+/// `1 / (i + j + 1)`
+fn element(i: usize, j: usize) -> Posit {
+    Posit::one().div_rounded(Posit::from((i + j + 1) as u64), EXACT_BITS)
+}
+
+/// Sums of the rows of the Hilbert matrix of size `n`.
+fn row_sums(n: usize) -> Vec<Posit> {
+    (0..n)
+        .map(|i| {
+            let mut quire = Quire::new();
+            for j in 0..n {
+                quire.add_posit(&element(i, j));
+            }
+            quire.to_posit(EXACT_BITS)
+        })
+        .collect()
+}
+
+fn system<T: PositField>(n: usize) -> (DMatrix<T>, DVector<T>) {
+    let b = row_sums(n);
+    (
+        DMatrix::from_fn(n, n, |i, j| T::from(element(i, j))),
+        DVector::from_fn(n, |i, _| T::from(b[i].clone())),
+    )
+}
+
+fn system_f64(n: usize) -> (DMatrix<f64>, DVector<f64>) {
+    let b = row_sums(n);
+    (
+        DMatrix::from_fn(n, n, |i, j| element(i, j).to_f64()),
+        DVector::from_fn(n, |i, _| b[i].to_f64()),
+    )
+}
+
+/// Largest distance from one, NaN if there is no solution.
+fn error(x: Option<DVector<f64>>) -> f64 {
+    x.map_or(f64::NAN, |x| {
+        x.iter().map(|x| (x - 1.0).abs()).fold(0.0, f64::max)
+    })
+}
+
+fn to_f64<T: PositField>(x: DVector<T>) -> DVector<f64> {
+    x.map(|x| Posit::to_f64(&x.into()))
+}
+
+fn main() {
+    let columns = [
+        "f64 LU", "f64 Chol", "P32 na", "P32 LU", "P32 Chol", "P32 QR", "P64 LU", "P64 Chol",
+        "P64 QR",
+    ];
+    print!("{:>3}", "n");
+    for column in &columns {
+        print!("{:>10}", column);
+    }
+    println!();
+
+    for n in 2..=14 {
+        let (a, b) = system_f64(n);
+        let (a32, b32) = system::<P32>(n);
+        let (a64, b64) = system::<P64>(n);
+        let errors = [
+            error(a.clone().lu().solve(&b)),
+            error(a.cholesky().map(|c| c.solve(&b))),
+            error(a32.clone().lu().solve(&b32).map(to_f64)),
+            error(Lu::new(a32.clone()).solve(&b32).map(to_f64)),
+            error(Cholesky::new(a32.clone()).map(|c| to_f64(c.solve(&b32)))),
+            error(Qr::new(a32).solve(&b32).map(to_f64)),
+            error(Lu::new(a64.clone()).solve(&b64).map(to_f64)),
+            error(Cholesky::new(a64.clone()).map(|c| to_f64(c.solve(&b64)))),
+            error(Qr::new(a64).solve(&b64).map(to_f64)),
+        ];
+        print!("{:>3}", n);
+        for e in &errors {
+            print!("{:>10.1e}", e);
+        }
+        println!();
+    }
+}
@@ -0,0 +1,467 @@
+use std::cmp;
+
+use alga::general::*;
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use num_traits::{Float, FromPrimitive, Signed, ToPrimitive};
+
+use crate::math::{ln2, pi, GUARD};
+use crate::{Posit, P16, P32, P64, P8};
+
+/// Constant computed by `f` with guard bits and rounded once to the fixed width.
+fn constant<T: From<Posit>>(f: impl FnOnce(usize) -> Posit, nbits: usize) -> T {
+    T::from(f(nbits + GUARD))
+}
+
+macro_rules! algebra_traits {
+    ($($name: ident($nbits: expr, $signed: ty)),*) => {$(
+        impl AbstractMagma<Additive> for $name {
+            fn operate(&self, right: &Self) -> Self {
+                *self + *right
+            }
+        }
+
+        impl AbstractMagma<Multiplicative> for $name {
+            fn operate(&self, right: &Self) -> Self {
+                *self * *right
+            }
+        }
+
+        impl Identity<Additive> for $name {
+            fn identity() -> Self {
+                $name::ZERO
+            }
+        }
+
+        impl Identity<Multiplicative> for $name {
+            fn identity() -> Self {
+                $name::ONE
+            }
+        }
+
+        impl TwoSidedInverse<Additive> for $name {
+            fn two_sided_inverse(&self) -> Self {
+                -*self
+            }
+        }
+
+        impl TwoSidedInverse<Multiplicative> for $name {
+            fn two_sided_inverse(&self) -> Self {
+                Float::recip(*self)
+            }
+        }
+
+        impl AbstractQuasigroup<Additive> for $name {}
+        impl AbstractLoop<Additive> for $name {}
+        impl AbstractSemigroup<Additive> for $name {}
+        impl AbstractMonoid<Additive> for $name {}
+        impl AbstractGroup<Additive> for $name {}
+        impl AbstractGroupAbelian<Additive> for $name {}
+        impl AbstractQuasigroup<Multiplicative> for $name {}
+        impl AbstractLoop<Multiplicative> for $name {}
+        impl AbstractSemigroup<Multiplicative> for $name {}
+        impl AbstractMonoid<Multiplicative> for $name {}
+        impl AbstractGroup<Multiplicative> for $name {}
+        impl AbstractGroupAbelian<Multiplicative> for $name {}
+        impl AbstractRing for $name {}
+        impl AbstractRingCommutative for $name {}
+        impl AbstractField for $name {}
+
+        impl AbstractModule for $name {
+            type AbstractRing = $name;
+
+            fn multiply_by(&self, r: Self) -> Self {
+                *self * r
+            }
+        }
+
+        impl SubsetOf<$name> for $name {
+            fn to_superset(&self) -> Self {
+                *self
+            }
+
+            unsafe fn from_superset_unchecked(element: &Self) -> Self {
+                *element
+            }
+
+            fn is_in_subset(_: &Self) -> bool {
+                true
+            }
+        }
+
+        /// Like `f32` and `f64`, the posits are taken as representations of the same reals,
+        /// conversions round to the nearest value.
+        impl SubsetOf<$name> for f64 {
+            fn to_superset(&self) -> $name {
+                $name::from_f64(*self).unwrap()
+            }
+
+            unsafe fn from_superset_unchecked(element: &$name) -> Self {
+                ToPrimitive::to_f64(element).unwrap()
+            }
+
+            fn is_in_subset(_: &$name) -> bool {
+                true
+            }
+        }
+
+        /// NaR is below every other value, as in the total order of the posits.
+        impl MeetSemilattice for $name {
+            fn meet(&self, other: &Self) -> Self {
+                cmp::min(*self, *other)
+            }
+        }
+
+        impl JoinSemilattice for $name {
+            fn join(&self, other: &Self) -> Self {
+                cmp::max(*self, *other)
+            }
+        }
+
+        impl Lattice for $name {}
+
+        /// NaR is only equal to itself.
+        impl AbsDiffEq for $name {
+            type Epsilon = Self;
+
+            fn default_epsilon() -> Self {
+                Float::epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self) -> bool {
+                if self == other {
+                    return true;
+                }
+                !self.is_nar() && !other.is_nar() && (*self - *other).abs() <= epsilon
+            }
+        }
+
+        impl RelativeEq for $name {
+            fn default_max_relative() -> Self {
+                Float::epsilon()
+            }
+
+            fn relative_eq(&self, other: &Self, epsilon: Self, max_relative: Self) -> bool {
+                if self.abs_diff_eq(other, epsilon) {
+                    return true;
+                }
+                if self.is_nar() || other.is_nar() {
+                    return false;
+                }
+                let largest = cmp::max(self.abs(), other.abs());
+                (*self - *other).abs() <= largest * max_relative
+            }
+        }
+
+        /// Adjacent posits are adjacent integers, across zero as well.
+        impl UlpsEq for $name {
+            fn default_max_ulps() -> u32 {
+                4
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: Self, max_ulps: u32) -> bool {
+                if self.abs_diff_eq(other, epsilon) {
+                    return true;
+                }
+                if self.is_nar() || other.is_nar() {
+                    return false;
+                }
+                let ulps = (self.to_bits() as $signed).wrapping_sub(other.to_bits() as $signed);
+                ulps.unsigned_abs() <= max_ulps as _
+            }
+        }
+
+        impl ComplexField for $name {
+            type RealField = $name;
+
+            fn from_real(re: Self) -> Self {
+                re
+            }
+
+            fn real(self) -> Self {
+                self
+            }
+
+            fn imaginary(self) -> Self {
+                $name::ZERO
+            }
+
+            fn modulus(self) -> Self {
+                $name::abs(self)
+            }
+
+            fn modulus_squared(self) -> Self {
+                self * self
+            }
+
+            fn argument(self) -> Self {
+                if self.is_negative() {
+                    Self::pi()
+                } else {
+                    $name::ZERO
+                }
+            }
+
+            fn norm1(self) -> Self {
+                $name::abs(self)
+            }
+
+            fn scale(self, factor: Self) -> Self {
+                self * factor
+            }
+
+            fn unscale(self, factor: Self) -> Self {
+                self / factor
+            }
+
+            fn to_exp(self) -> (Self, Self) {
+                if self.is_negative() {
+                    (-self, -$name::ONE)
+                } else {
+                    (self, $name::ONE)
+                }
+            }
+
+            fn signum(self) -> Self {
+                Signed::signum(&self)
+            }
+
+            fn floor(self) -> Self {
+                Float::floor(self)
+            }
+
+            fn ceil(self) -> Self {
+                Float::ceil(self)
+            }
+
+            fn round(self) -> Self {
+                Float::round(self)
+            }
+
+            fn trunc(self) -> Self {
+                Float::trunc(self)
+            }
+
+            fn fract(self) -> Self {
+                Float::fract(self)
+            }
+
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                Float::mul_add(self, a, b)
+            }
+
+            fn abs(self) -> Self {
+                $name::abs(self)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                Float::hypot(self, other)
+            }
+
+            fn recip(self) -> Self {
+                Float::recip(self)
+            }
+
+            fn conjugate(self) -> Self {
+                self
+            }
+
+            fn sin(self) -> Self {
+                Float::sin(self)
+            }
+
+            fn cos(self) -> Self {
+                Float::cos(self)
+            }
+
+            fn sin_cos(self) -> (Self, Self) {
+                Float::sin_cos(self)
+            }
+
+            fn tan(self) -> Self {
+                Float::tan(self)
+            }
+
+            fn asin(self) -> Self {
+                Float::asin(self)
+            }
+
+            fn acos(self) -> Self {
+                Float::acos(self)
+            }
+
+            fn atan(self) -> Self {
+                Float::atan(self)
+            }
+
+            fn sinh(self) -> Self {
+                Float::sinh(self)
+            }
+
+            fn cosh(self) -> Self {
+                Float::cosh(self)
+            }
+
+            fn tanh(self) -> Self {
+                Float::tanh(self)
+            }
+
+            fn asinh(self) -> Self {
+                Float::asinh(self)
+            }
+
+            fn acosh(self) -> Self {
+                Float::acosh(self)
+            }
+
+            fn atanh(self) -> Self {
+                Float::atanh(self)
+            }
+
+            fn is_finite(&self) -> bool {
+                !self.is_nar()
+            }
+
+            fn log(self, base: Self) -> Self {
+                Float::log(self, base)
+            }
+
+            fn log2(self) -> Self {
+                Float::log2(self)
+            }
+
+            fn log10(self) -> Self {
+                Float::log10(self)
+            }
+
+            fn ln(self) -> Self {
+                Float::ln(self)
+            }
+
+            fn ln_1p(self) -> Self {
+                Float::ln_1p(self)
+            }
+
+            fn sqrt(self) -> Self {
+                Float::sqrt(self)
+            }
+
+            /// `None` for negative values and NaR.
+            fn try_sqrt(self) -> Option<Self> {
+                Some(Float::sqrt(self)).filter(|root| !root.is_nar())
+            }
+
+            fn exp(self) -> Self {
+                Float::exp(self)
+            }
+
+            fn exp2(self) -> Self {
+                Float::exp2(self)
+            }
+
+            fn exp_m1(self) -> Self {
+                Float::exp_m1(self)
+            }
+
+            fn powi(self, n: i32) -> Self {
+                Float::powi(self, n)
+            }
+
+            fn powf(self, n: Self) -> Self {
+                Float::powf(self, n)
+            }
+
+            fn powc(self, n: Self) -> Self {
+                Float::powf(self, n)
+            }
+
+            fn cbrt(self) -> Self {
+                Float::cbrt(self)
+            }
+        }
+
+        /// The constants are rounded once to the nearest value.
+        impl RealField for $name {
+            fn is_sign_positive(self) -> bool {
+                Float::is_sign_positive(self)
+            }
+
+            fn is_sign_negative(self) -> bool {
+                Float::is_sign_negative(self)
+            }
+
+            fn max(self, other: Self) -> Self {
+                Float::max(self, other)
+            }
+
+            fn min(self, other: Self) -> Self {
+                Float::min(self, other)
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                Float::atan2(self, other)
+            }
+
+            fn pi() -> Self {
+                constant(pi, $nbits)
+            }
+
+            fn two_pi() -> Self {
+                constant(|w| pi(w).mul_pow2(1), $nbits)
+            }
+
+            fn frac_pi_2() -> Self {
+                constant(|w| pi(w).mul_pow2(-1), $nbits)
+            }
+
+            fn frac_pi_3() -> Self {
+                constant(|w| pi(w).div_rounded(Posit::from(3), w), $nbits)
+            }
+
+            fn frac_pi_4() -> Self {
+                constant(|w| pi(w).mul_pow2(-2), $nbits)
+            }
+
+            fn frac_pi_6() -> Self {
+                constant(|w| pi(w).div_rounded(Posit::from(6), w), $nbits)
+            }
+
+            fn frac_pi_8() -> Self {
+                constant(|w| pi(w).mul_pow2(-3), $nbits)
+            }
+
+            fn frac_1_pi() -> Self {
+                constant(|w| Posit::one().div_rounded(pi(w), w), $nbits)
+            }
+
+            fn frac_2_pi() -> Self {
+                constant(|w| Posit::from(2).div_rounded(pi(w), w), $nbits)
+            }
+
+            fn frac_2_sqrt_pi() -> Self {
+                constant(|w| Posit::from(2).div_rounded(pi(w).sqrt(w), w), $nbits)
+            }
+
+            fn e() -> Self {
+                constant(|w| Posit::one().exp(w), $nbits)
+            }
+
+            fn log2_e() -> Self {
+                constant(|w| Posit::one().div_rounded(ln2(w), w), $nbits)
+            }
+
+            fn log10_e() -> Self {
+                constant(|w| Posit::one().div_rounded(Posit::from(10).ln(w), w), $nbits)
+            }
+
+            fn ln_2() -> Self {
+                constant(ln2, $nbits)
+            }
+
+            fn ln_10() -> Self {
+                constant(|w| Posit::from(10).ln(w), $nbits)
+            }
+        }
+    )*};
+}
+
+algebra_traits!(P8(8, i8), P16(16, i16), P32(32, i32), P64(64, i64));
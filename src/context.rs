@@ -0,0 +1,162 @@
+use std::cell::Cell;
+
+use crate::Posit;
+
+/// Direction in which a [`PositContext`] rounds results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// To the nearest posit, ties to even. Like [`Posit::round_to`], nonzero results saturate to
+    /// minpos and maxpos instead of rounding to zero or NaR.
+    #[default]
+    Nearest,
+    /// Toward -∞, results below -maxpos give NaR.
+    Down,
+    /// Toward +∞, results above maxpos give NaR.
+    Up,
+}
+
+/// Precision of the posit operators on the current thread.
+///
+/// By default posits are exact: sums, differences and products keep all their bits, which grow
+/// with every multiplication, and quotients carry 16 bits more than their operands. A context
+/// with `nbits` rounds the result of every operator and of [`Posit::pow`] to that many bits
+/// instead, so that long computations stay bounded in size.
+///
+/// Methods taking their own length, like [`Posit::sqrt`] or [`Posit::add_rounded`], round to it
+/// and ignore the context, as do valids, unums and intervals, whose bounds rely on exact
+/// intermediate results. Only the quotients of valids, which cannot be exact, take the width of
+/// the context, see [`Valid`](crate::Valid).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PositContext {
+    /// Length results are rounded to, `None` for exact results.
+    pub nbits: Option<usize>,
+    /// Exponent size of the posits whose results are rounded, `None` for all of them. This only
+    /// selects the posits the context applies to, it does not set the exponent size of any
+    /// result, which is that of the posit type.
+    pub only_es: Option<usize>,
+    pub rounding: Rounding,
+}
+
+thread_local! {
+    static CONTEXT: Cell<PositContext> = const {
+        Cell::new(PositContext {
+            nbits: None,
+            only_es: None,
+            rounding: Rounding::Nearest,
+        })
+    };
+}
+
+impl PositContext {
+    /// Context of the current thread.
+    pub fn current() -> Self {
+        CONTEXT.with(Cell::get)
+    }
+
+    /// Makes this the context of the current thread until it is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nbits` is below 2.
+    pub fn set_current(self) {
+        self.check();
+        CONTEXT.with(|context| context.set(self));
+    }
+
+    /// Runs `f` with this context on the current thread, then restores the previous one, also
+    /// when `f` panics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nbits` is below 2.
+    pub fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        self.check();
+        let _scope = Scope::enter(self);
+        f()
+    }
+
+    fn check(&self) {
+        assert!(
+            self.nbits.is_none_or(|nbits| nbits >= 2),
+            "posits have at least 2 bits"
+        );
+    }
+
+    /// Length results of posits with `es` exponent bits are rounded to.
+    pub(crate) fn nbits_for(&self, es: usize) -> Option<usize> {
+        self.nbits.filter(|_| self.only_es.is_none_or(|e| e == es))
+    }
+}
+
+/// Runs `f` with results rounded to `nbits` bits on the current thread, keeping `only_es` and
+/// the rounding of the current context.
+///
+/// # Panics
+///
+/// Panics if `nbits` is below 2.
+pub fn with_precision<R>(nbits: usize, f: impl FnOnce() -> R) -> R {
+    PositContext {
+        nbits: Some(nbits),
+        ..PositContext::current()
+    }
+    .scope(f)
+}
+
+/// Context replaced for a scope, restored on drop.
+pub(crate) struct Scope {
+    previous: PositContext,
+}
+
+impl Scope {
+    fn enter(context: PositContext) -> Self {
+        let previous = CONTEXT.with(|current| current.replace(context));
+        Scope { previous }
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        CONTEXT.with(|current| current.set(self.previous));
+    }
+}
+
+/// Makes the operators exact until the scope is dropped, for code whose own rounding relies on
+/// exact intermediate results.
+pub(crate) fn exact() -> Scope {
+    Scope::enter(PositContext {
+        nbits: None,
+        ..PositContext::current()
+    })
+}
+
+impl<const ES: usize> Posit<ES> {
+    /// Rounds the exact result of an operator as the current context requires.
+    pub(crate) fn in_context(mut self) -> Self {
+        let context = PositContext::current();
+        if let Some(nbits) = context.nbits_for(ES) {
+            match context.rounding {
+                Rounding::Nearest => self.round_to(nbits),
+                Rounding::Down => self = self.round_toward(nbits, false),
+                Rounding::Up => self = self.round_toward(nbits, true),
+            }
+        }
+        self
+    }
+
+    /// Quotient rounded as the current context requires.
+    pub(crate) fn quotient_in_context(&self, rhs: &Self) -> Self {
+        let context = PositContext::current();
+        let nbits = match context.nbits_for(ES) {
+            Some(nbits) => nbits,
+            None => return self.divide(rhs, None).0,
+        };
+        match context.rounding {
+            Rounding::Nearest => {
+                let (result, inexact) = self.divide(rhs, Some(nbits));
+                result.round_inexact(nbits, inexact)
+            }
+            Rounding::Down => self.div_directed(rhs, nbits, false).0,
+            Rounding::Up => self.div_directed(rhs, nbits, true).0,
+        }
+    }
+}
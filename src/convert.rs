@@ -0,0 +1,169 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use bitvec::prelude::*;
+
+use crate::{Posit, Regime};
+
+/// Error returned when a posit is NaR, not an integer or out of range of an integer type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryFromPositError(());
+
+impl fmt::Display for TryFromPositError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("posit is not an integer within range of the target type")
+    }
+}
+
+impl Error for TryFromPositError {}
+
+impl<const ES: usize> Posit<ES> {
+    /// Nearest `f64`, ties to even.
+    ///
+    /// Values beyond the range of `f64` become infinities or zeros, NaR becomes NaN.
+    pub fn to_f64(&self) -> f64 {
+        f64::from_bits(self.to_ieee(52, 11))
+    }
+
+    /// Nearest `f32`, ties to even.
+    ///
+    /// Values beyond the range of `f32` become infinities or zeros, NaR becomes NaN.
+    pub fn to_f32(&self) -> f32 {
+        f32::from_bits(self.to_ieee(23, 8) as u32)
+    }
+
+    /// Bits of the nearest IEEE 754 binary number with the given field widths.
+    fn to_ieee(&self, frac_bits: u32, exp_bits: u32) -> u64 {
+        let max_exp = (1 << exp_bits) - 1;
+        if self.is_nar() {
+            return (max_exp << frac_bits) | (1 << (frac_bits - 1));
+        }
+        if self.is_zero() {
+            return 0;
+        }
+        let sign = (self.is_negative() as u64) << (frac_bits + exp_bits);
+        let (scale, frac) = self.scale_and_fraction();
+        let exp = scale + (max_exp >> 1) as isize;
+        if exp >= max_exp as isize {
+            return sign | (max_exp << frac_bits);
+        }
+
+        // Significand bits which fit, subnormals keep fewer of them
+        let kept = frac_bits as isize + exp.min(1);
+        if kept < 0 {
+            return sign;
+        }
+        let kept = kept as usize;
+        let mut sig = bitvec![1];
+        sig.extend(frac);
+        let mut mant = 0u64;
+        for i in 0..kept {
+            mant = mant * 2 + sig.get(i).unwrap_or(false) as u64;
+        }
+        let guard = sig.get(kept).unwrap_or(false);
+        let sticky = sig.iter().skip(kept + 1).any(|i| i);
+        if guard && (sticky || mant & 1 != 0) {
+            mant += 1;
+        }
+
+        // The hidden bit of normal numbers adds one to the exponent, so does a carry
+        let biased = if exp >= 1 { (exp as u64 - 1) << frac_bits } else { 0 };
+        sign | (biased + mant)
+    }
+
+    /// Sign and magnitude of an integral posit below `2^128` in magnitude.
+    fn to_integer(&self) -> Option<(bool, u128)> {
+        if self.is_nar() {
+            return None;
+        }
+        if self.is_zero() {
+            return Some((false, 0));
+        }
+        let (scale, frac) = self.scale_and_fraction();
+        if !(0..128).contains(&scale) || frac.iter().skip(scale as usize).any(|i| i) {
+            return None;
+        }
+        let mut magnitude = 1u128;
+        for i in 0..scale as usize {
+            magnitude = magnitude << 1 | frac.get(i).unwrap_or(false) as u128;
+        }
+        Some((self.is_negative(), magnitude))
+    }
+
+    fn from_integer(negative: bool, magnitude: u128) -> Self {
+        if magnitude == 0 {
+            return Self::zero();
+        }
+        let top = 127 - magnitude.leading_zeros() as usize;
+        let frac: BitVec = (0..top).rev().map(|i| magnitude >> i & 1 != 0).collect();
+        let (regime, exp) = Regime::from_scale(top as isize, ES);
+        Self::from_parts(negative, regime, exp, &frac)
+    }
+}
+
+/// Exact conversion, infinities and NaN become NaR.
+impl<const ES: usize> From<f64> for Posit<ES> {
+    fn from(f: f64) -> Self {
+        if f.is_nan() || f.is_infinite() {
+            return Self::nar();
+        }
+        let bits = f.to_bits();
+        let sign = bits >> 63 != 0;
+        let exponent = ((bits >> 52) & 0x7ff) as isize;
+        let fraction = bits & 0xf_ffff_ffff_ffff;
+
+        // The significand as an integer, subnormals have no hidden bit
+        let (significand, scale) = if exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), exponent - 1075)
+        };
+        Self::from_integer(sign, significand as u128).mul_pow2(scale)
+    }
+}
+
+macro_rules! integer_conversions {
+    ($($int: ty),*) => {$(
+        /// Exact conversion, fails for NaR, fractions and values out of range.
+        impl<const ES: usize> TryFrom<Posit<ES>> for $int {
+            type Error = TryFromPositError;
+
+            fn try_from(p: Posit<ES>) -> Result<Self, Self::Error> {
+                let (negative, magnitude) = p.to_integer().ok_or(TryFromPositError(()))?;
+                let value = if !negative {
+                    <$int>::try_from(magnitude).ok()
+                } else if magnitude <= i128::MIN.unsigned_abs() {
+                    <$int>::try_from((magnitude as i128).wrapping_neg()).ok()
+                } else {
+                    None
+                };
+                value.ok_or(TryFromPositError(()))
+            }
+        }
+    )*};
+}
+
+macro_rules! from_signed {
+    ($($int: ty),*) => {$(
+        impl<const ES: usize> From<$int> for Posit<ES> {
+            fn from(i: $int) -> Self {
+                Self::from_integer(i < 0, i.unsigned_abs() as u128)
+            }
+        }
+    )*};
+}
+
+macro_rules! from_unsigned {
+    ($($int: ty),*) => {$(
+        impl<const ES: usize> From<$int> for Posit<ES> {
+            fn from(i: $int) -> Self {
+                Self::from_integer(false, i as u128)
+            }
+        }
+    )*};
+}
+
+integer_conversions!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+from_unsigned!(u8, u16, u32, u64, u128, usize);
@@ -0,0 +1,362 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::f64::consts::LOG2_10;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::natural::Natural;
+use crate::{Posit, PositContext};
+
+/// Error returned when a string is not a valid decimal posit literal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParsePositError(pub(crate) ());
+
+impl fmt::Display for ParsePositError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid posit literal")
+    }
+}
+
+impl Error for ParsePositError {}
+
+/// Largest magnitude of the decimal exponent of literals parsed without a width, whose exact
+/// value would be too large to build.
+const MAX_EXACT_EXP: isize = 100_000;
+
+/// Fraction bits that tell apart all decimals with `digits` significant digits.
+fn frac_bits_for(digits: usize) -> usize {
+    (digits as f64 * LOG2_10).ceil() as usize + 1
+}
+
+/// Rounds the decimal `digits * 10^exp` to its `keep` leading digits, ties to even.
+fn round_digits(digits: &[u8], exp: isize, keep: isize) -> (Vec<u8>, isize) {
+    let len = digits.len() as isize;
+    if keep >= len {
+        return (digits.to_vec(), exp);
+    }
+    let exp = exp + len - keep;
+    if keep < 0 {
+        return (vec![], exp);
+    }
+    let (kept, dropped) = digits.split_at(keep as usize);
+    let mut kept = kept.to_vec();
+    let sticky = dropped[1..].iter().any(|&d| d != 0);
+    let half = dropped[0].cmp(&5).then(if sticky { Ordering::Greater } else { Ordering::Equal });
+    let odd = kept.last().is_some_and(|&d| d % 2 != 0);
+    if half == Ordering::Greater || (half == Ordering::Equal && odd) {
+        match kept.iter().rposition(|&d| d != 9) {
+            Some(i) => {
+                kept[i] += 1;
+                kept.truncate(i + 1);
+                let len = kept.len() as isize;
+                return (kept, exp + keep - len);
+            }
+            None => return (vec![1], exp + keep),
+        }
+    }
+    (kept, exp)
+}
+
+/// Removes trailing zeros of `digits * 10^exp`.
+fn strip_zeros(digits: &mut Vec<u8>, exp: &mut isize) {
+    while digits.last() == Some(&0) {
+        digits.pop();
+        *exp += 1;
+    }
+}
+
+impl<const ES: usize> Posit<ES> {
+    /// Parses a decimal literal rounding it to `nbits` bits, ties to even. Fails for `nbits`
+    /// below 2, which no posit has.
+    pub fn from_str_rounded(s: &str, nbits: usize) -> Result<Self, ParsePositError> {
+        if nbits < 2 {
+            return Err(ParsePositError(()));
+        }
+        Self::parse_decimal(s, Some(nbits))
+    }
+
+    fn parse_decimal(s: &str, nbits: Option<usize>) -> Result<Self, ParsePositError> {
+        if s.eq_ignore_ascii_case("nar") {
+            return Ok(Self::nar());
+        }
+        let (negative, s) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (mantissa, exp) = match s.find(['e', 'E']) {
+            Some(i) => {
+                let exp = &s[i + 1..];
+                let digits = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(ParsePositError(()));
+                }
+                (&s[..i], exp.parse::<isize>().map_err(|_| ParsePositError(()))?)
+            }
+            None => (s, 0),
+        };
+        let (int, frac) = match mantissa.find('.') {
+            Some(i) => (&mantissa[..i], &mantissa[i + 1..]),
+            None => (mantissa, ""),
+        };
+        if int.is_empty() && frac.is_empty() {
+            return Err(ParsePositError(()));
+        }
+        let mut digits = vec![];
+        for b in int.bytes().chain(frac.bytes()) {
+            if !b.is_ascii_digit() {
+                return Err(ParsePositError(()));
+            }
+            digits.push(b - b'0');
+        }
+        let exp = exp.checked_sub(frac.len() as isize).ok_or(ParsePositError(()))?;
+
+        // The value is below 10^top and at least 10^(top - 1), bound it before building the
+        // powers of ten of its exponent
+        let leading = digits.iter().take_while(|&&d| d == 0).count();
+        if leading == digits.len() {
+            return Ok(Self::zero());
+        }
+        let top = exp as f64 + (digits.len() - leading) as f64;
+        match nbits {
+            Some(nbits) => {
+                let max_scale = (nbits.saturating_sub(2) << ES) as f64;
+                let saturated = if (top - 1.) * LOG2_10 > max_scale + 1. {
+                    Some(Self::maxpos(nbits))
+                } else if top * LOG2_10 < -max_scale - 1. {
+                    Some(Self::minpos(nbits))
+                } else {
+                    None
+                };
+                if let Some(p) = saturated {
+                    return Ok(if negative { -p } else { p });
+                }
+            }
+            None if top.abs() > MAX_EXACT_EXP as f64 => return Err(ParsePositError(())),
+            None => {}
+        }
+        Ok(Self::from_decimal(negative, &digits, exp, nbits))
+    }
+
+    /// Value of `digits * 10^exp`, rounded to `nbits` bits if given.
+    ///
+    /// Without a width, decimals without an exact posit are rounded to as many fraction bits
+    /// as their significant digits need to be told apart.
+    fn from_decimal(negative: bool, digits: &[u8], exp: isize, nbits: Option<usize>) -> Self {
+        let start = digits.iter().position(|&d| d != 0).unwrap_or(digits.len());
+        let mut digits = digits[start..].to_vec();
+        let mut exp = exp;
+        strip_zeros(&mut digits, &mut exp);
+        if digits.is_empty() {
+            return Self::zero();
+        }
+
+        let mut n = Natural::from_digits(&digits);
+        let result = if exp >= 0 {
+            for _ in 0..exp {
+                n.mul_add(10, 0);
+            }
+            let mut result = Self::from_natural(&n);
+            if let Some(nbits) = nbits {
+                result.round_to(nbits);
+            }
+            result
+        } else {
+            // digits / 10^k = (digits / 5^k) / 2^k
+            let k = -exp;
+            let mut den = Natural::power_of_two(0);
+            for _ in 0..k {
+                den.mul_add(5, 0);
+            }
+            let num = Self::from_natural(&n);
+            let target = nbits.unwrap_or_else(|| frac_bits_for(digits.len()));
+            let frac_bits = (target + 2).max(num.bits.len());
+            let (quot, inexact) = num.divide(&Self::from_natural(&den), Some(frac_bits));
+            let quot = quot.mul_pow2(-k);
+            match nbits {
+                Some(nbits) => quot.round_inexact(nbits, inexact),
+                None if inexact => {
+                    let width = 1 + quot.regime().bits() + ES + target;
+                    quot.round_inexact(width, inexact)
+                }
+                None => quot,
+            }
+        };
+        if negative {
+            -result
+        } else {
+            result
+        }
+    }
+
+    fn from_natural(n: &Natural) -> Self {
+        if n.is_zero() {
+            return Self::zero();
+        }
+        let frac_len = n.bits() - 1;
+        Self::pack(false, frac_len as i64, n, frac_len)
+    }
+
+    /// Exact value of the magnitude as `digits * 10^exp` without trailing zeros.
+    fn exact_decimal(&self) -> (Vec<u8>, isize) {
+        let (scale, mut frac) = self.scale_and_fraction();
+        let len = frac.iter().rposition(|i| i).map_or(0, |i| i + 1);
+        frac.truncate(len);
+        let mut n = Natural::from_bits(std::iter::once(true).chain(frac.iter()));
+        let exp2 = scale - len as isize;
+        let mut exp = 0;
+        if exp2 >= 0 {
+            for _ in 0..exp2 {
+                n.mul_add(2, 0);
+            }
+        } else {
+            // 2^-k = 5^k * 10^-k
+            for _ in 0..-exp2 {
+                n.mul_add(5, 0);
+            }
+            exp = exp2;
+        }
+        let mut digits = n.to_digits();
+        strip_zeros(&mut digits, &mut exp);
+        (digits, exp)
+    }
+
+    /// Shortest decimal `digits * 10^exp` of the magnitude which parses back to the same
+    /// posit, rounded to `nbits` bits if given.
+    fn shortest_decimal(&self, nbits: Option<usize>) -> (Vec<u8>, isize) {
+        let (digits, exp) = self.exact_decimal();
+        let target = self.clone().abs();
+        let frac_len = target.scale_and_fraction().1.iter().rposition(|i| i).map_or(0, |i| i + 1);
+        for keep in 1..digits.len() {
+            // Fewer fraction bits than the posit has cannot give it back
+            if nbits.is_none() && frac_bits_for(keep) < frac_len {
+                continue;
+            }
+            let (mut short, mut short_exp) = round_digits(&digits, exp, keep as isize);
+            strip_zeros(&mut short, &mut short_exp);
+            if Self::from_decimal(false, &short, short_exp, nbits) == target {
+                return (short, short_exp);
+            }
+        }
+        (digits, exp)
+    }
+
+    /// Decimal digits of the magnitude for the given formatting precision.
+    ///
+    /// `exponent` tells whether the precision counts the digits after the first one rather
+    /// than the digits after the decimal point.
+    fn format_digits(
+        &self,
+        precision: Option<usize>,
+        exponent: bool,
+        nbits: Option<usize>,
+    ) -> (Vec<u8>, isize) {
+        let precision = match precision {
+            Some(precision) => precision as isize,
+            None => return self.shortest_decimal(nbits),
+        };
+        let (digits, exp) = self.exact_decimal();
+        let keep = if exponent {
+            precision + 1
+        } else {
+            digits.len() as isize + exp + precision
+        };
+        round_digits(&digits, exp, keep)
+    }
+}
+
+impl<const ES: usize> FromStr for Posit<ES> {
+    type Err = ParsePositError;
+
+    /// Parses a decimal or scientific literal, or `NaR`.
+    ///
+    /// Values with a finite binary expansion are exact, others are rounded to as many
+    /// fraction bits as their significant digits need. Use [`Posit::from_str_rounded`]
+    /// for a specific width, which also saturates literals out of its range. Without a width,
+    /// literals whose magnitude is beyond 10^±100000 are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_decimal(s, None)
+    }
+}
+
+/// Shortest decimal which parses back to the same posit, or the given number of digits
+/// after the decimal point.
+///
+/// Posits are exact values, so they print the shortest decimal [`FromStr`] gives them back
+/// from. In a [`PositContext`] with a width, they print the shortest one which
+/// [`Posit::from_str_rounded`] gives back at that width instead, like the fixed-width posits
+/// do at theirs, for example `0.1` for 0.1 rounded to 32 bits.
+impl<const ES: usize> fmt::Display for Posit<ES> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_decimal(f, PositContext::current().nbits_for(ES))
+    }
+}
+
+/// Scientific notation with the shortest mantissa which parses back to the same posit, or
+/// the given number of digits after its decimal point. The shortest mantissa depends on the
+/// context as for [`Display`](fmt::Display).
+impl<const ES: usize> fmt::LowerExp for Posit<ES> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_exp(f, PositContext::current().nbits_for(ES))
+    }
+}
+
+impl<const ES: usize> Posit<ES> {
+    /// Formats like [`Display`](fmt::Display), shortest decimals parsing back at `nbits` bits
+    /// if given.
+    pub(crate) fn fmt_decimal(&self, f: &mut fmt::Formatter, nbits: Option<usize>) -> fmt::Result {
+        if self.is_nar() {
+            return f.pad("NaR");
+        }
+        let (digits, exp) = if self.is_zero() {
+            (vec![], 0)
+        } else {
+            self.format_digits(f.precision(), false, nbits)
+        };
+
+        let mut s = String::new();
+        let point = digits.len() as isize + exp;
+        if point <= 0 {
+            s.push('0');
+        } else {
+            for i in 0..point {
+                s.push(char::from(b'0' + digits.get(i as usize).copied().unwrap_or(0)));
+            }
+        }
+        let decimals = f.precision().unwrap_or((-exp).max(0) as usize);
+        if decimals > 0 {
+            s.push('.');
+            for i in point..point + decimals as isize {
+                let digit = if i < 0 { 0 } else { digits.get(i as usize).copied().unwrap_or(0) };
+                s.push(char::from(b'0' + digit));
+            }
+        }
+        f.pad_integral(!self.is_negative(), "", &s)
+    }
+
+    /// Formats like [`LowerExp`](fmt::LowerExp), shortest mantissas parsing back at `nbits`
+    /// bits if given.
+    pub(crate) fn fmt_exp(&self, f: &mut fmt::Formatter, nbits: Option<usize>) -> fmt::Result {
+        if self.is_nar() {
+            return f.pad("NaR");
+        }
+        let (digits, exp) = if self.is_zero() {
+            (vec![0], 0)
+        } else {
+            self.format_digits(f.precision(), true, nbits)
+        };
+
+        let mut s = String::new();
+        s.push(char::from(b'0' + digits[0]));
+        let decimals = f.precision().unwrap_or(digits.len() - 1);
+        if decimals > 0 {
+            s.push('.');
+            for i in 1..=decimals {
+                s.push(char::from(b'0' + digits.get(i).copied().unwrap_or(0)));
+            }
+        }
+        s.push('e');
+        s.push_str(&(exp + digits.len() as isize - 1).to_string());
+        f.pad_integral(!self.is_negative(), "", &s)
+    }
+}
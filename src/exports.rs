@@ -1,57 +1,406 @@
+//! C API over heap-allocated posit handles.
+//!
+//! Functions creating a posit return a new handle, which the caller releases with
+//! `posit_free`, or null on failure. Functions writing into an existing handle return a
+//! [`UnumStatus`] and leave it unchanged on failure. Every failure, including a panic caught
+//! at the boundary, is described by `unum_last_error` on the calling thread.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
 use super::*;
 
+/// Outcome of a C API call.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnumStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// An argument was out of range or could not be parsed.
+    InvalidArgument = 2,
+    /// The library panicked, the panic was stopped at the boundary.
+    InternalError = 3,
+}
+
+thread_local! {
+    /// Message of the last failed call on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Reason a call failed.
+pub(crate) struct Failure {
+    status: UnumStatus,
+    message: String,
+}
+
+impl Failure {
+    fn null(argument: &str) -> Self {
+        Failure {
+            status: UnumStatus::NullPointer,
+            message: format!("`{}` is null", argument),
+        }
+    }
+
+    fn invalid(message: String) -> Self {
+        Failure {
+            status: UnumStatus::InvalidArgument,
+            message,
+        }
+    }
+
+    fn panic(payload: Box<dyn Any + Send>) -> Self {
+        let reason = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "unknown panic".to_string(),
+            },
+        };
+        Failure {
+            status: UnumStatus::InternalError,
+            message: format!("internal error: {}", reason),
+        }
+    }
+}
+
+/// Runs the body of an export, recording a failure or a panic as the last error of the
+/// thread.
+pub(crate) fn run<T>(f: impl FnOnce() -> Result<T, Failure>) -> Result<T, UnumStatus> {
+    let failure = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => return Ok(value),
+        Ok(Err(failure)) => failure,
+        Err(payload) => Failure::panic(payload),
+    };
+    // Only a panic message could hold a NUL
+    let message = failure.message.replace('\0', " ");
+    LAST_ERROR.with(|last| *last.borrow_mut() = CString::new(message).ok());
+    Err(failure.status)
+}
+
+/// Runs an export which cannot fail but by panicking, `fallback` is returned then.
+pub(crate) fn guard<T>(fallback: T, f: impl FnOnce() -> T) -> T {
+    run(|| Ok(f())).unwrap_or(fallback)
+}
+
+/// Runs an export returning a status.
+fn status(f: impl FnOnce() -> Result<(), Failure>) -> UnumStatus {
+    match run(f) {
+        Ok(()) => UnumStatus::Ok,
+        Err(status) => status,
+    }
+}
+
+/// Runs an export returning a new handle, null on failure.
+fn handle(f: impl FnOnce() -> Result<Posit, Failure>) -> *mut Posit {
+    run(f).map_or(ptr::null_mut(), |p| Box::into_raw(Box::new(p)))
+}
+
+/// Borrows the value behind a pointer argument.
+unsafe fn arg<'a, T>(p: *const T, name: &str) -> Result<&'a T, Failure> {
+    p.as_ref().ok_or_else(|| Failure::null(name))
+}
+
+/// Checks an output argument, which is only written once the result is known so that it
+/// may alias the inputs.
+fn out_arg<T>(p: *mut T, name: &str) -> Result<*mut T, Failure> {
+    if p.is_null() {
+        Err(Failure::null(name))
+    } else {
+        Ok(p)
+    }
+}
+
+fn width(nbits: usize) -> Result<usize, Failure> {
+    if nbits < 2 {
+        Err(Failure::invalid(format!(
+            "`nbits` is {}, posits have at least 2 bits",
+            nbits
+        )))
+    } else {
+        Ok(nbits)
+    }
+}
+
+/// Parses a NUL-terminated decimal literal or `NaR`.
+unsafe fn parse(s: *const c_char) -> Result<Posit, Failure> {
+    if s.is_null() {
+        return Err(Failure::null("s"));
+    }
+    let s = CStr::from_ptr(s)
+        .to_str()
+        .map_err(|_| Failure::invalid("`s` is not valid UTF-8".to_string()))?;
+    s.parse::<Posit>()
+        .map_err(|e| Failure::invalid(format!("{}: {:?}", e, s)))
+}
+
+/// Message describing the last failed call on the calling thread, null if none failed yet.
+///
+/// The string is owned by the library and stays valid until the next failing call on the
+/// same thread.
+#[no_mangle]
+pub extern "C" fn unum_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Creates a posit holding zero.
+#[no_mangle]
+pub extern "C" fn posit_new() -> *mut Posit {
+    handle(|| Ok(Posit::zero()))
+}
+
+/// Creates the posit nearest to `x`. NaN and the infinities give NaR.
+#[no_mangle]
+pub extern "C" fn posit_from_double(x: f64) -> *mut Posit {
+    handle(|| Ok(Posit::from(x)))
+}
+
+/// Creates a posit holding the integer `x` exactly.
+#[no_mangle]
+pub extern "C" fn posit_from_int64(x: i64) -> *mut Posit {
+    handle(|| Ok(Posit::from(x)))
+}
+
+/// Creates a posit from a NUL-terminated decimal literal or `NaR`.
+#[no_mangle]
+pub unsafe extern "C" fn posit_from_string(s: *const c_char) -> *mut Posit {
+    handle(|| parse(s))
+}
+
+/// Creates a copy of a posit.
+#[no_mangle]
+pub unsafe extern "C" fn posit_clone(p: *const Posit) -> *mut Posit {
+    handle(|| Ok(arg(p, "p")?.clone()))
+}
+
+/// Releases a posit, null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn posit_free(p: *mut Posit) {
+    guard((), || {
+        if !p.is_null() {
+            drop(Box::from_raw(p));
+        }
+    })
+}
+
+/// Sets `out` to the posit nearest to `x`.
+#[no_mangle]
+pub unsafe extern "C" fn posit_set_double(out: *mut Posit, x: f64) -> UnumStatus {
+    status(|| {
+        *out_arg(out, "out")? = Posit::from(x);
+        Ok(())
+    })
+}
+
+/// Sets `out` to the integer `x`.
+#[no_mangle]
+pub unsafe extern "C" fn posit_set_int64(out: *mut Posit, x: i64) -> UnumStatus {
+    status(|| {
+        *out_arg(out, "out")? = Posit::from(x);
+        Ok(())
+    })
+}
+
+/// Sets `out` to a NUL-terminated decimal literal or `NaR`.
+#[no_mangle]
+pub unsafe extern "C" fn posit_set_string(out: *mut Posit, s: *const c_char) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = parse(s)?;
+        Ok(())
+    })
+}
+
+/// Stores the `double` nearest to the posit in `out`, NaN for NaR.
+#[no_mangle]
+pub unsafe extern "C" fn posit_to_double(p: *const Posit, out: *mut f64) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = arg(p, "p")?.to_f64();
+        Ok(())
+    })
+}
+
+/// Formats the posit as its shortest decimal, as a NUL-terminated string released with
+/// `posit_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn posit_to_string(p: *const Posit) -> *mut c_char {
+    let string = run(|| {
+        // The decimal digits contain no NUL
+        Ok(CString::new(arg(p, "p")?.to_string()).unwrap())
+    });
+    string.map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by `posit_to_string`, null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn posit_string_free(s: *mut c_char) {
+    guard((), || {
+        if !s.is_null() {
+            drop(CString::from_raw(s));
+        }
+    })
+}
+
+/// Stores -1, 0 or 1 in `out` as `lhs` is below, equal to or above `rhs`. NaR is below
+/// every other value and equal to itself.
+#[no_mangle]
+pub unsafe extern "C" fn posit_compare(
+    lhs: *const Posit,
+    rhs: *const Posit,
+    out: *mut i32,
+) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = arg(lhs, "lhs")?.cmp(arg(rhs, "rhs")?) as i32;
+        Ok(())
+    })
+}
+
+/// Rounds the posit in place to `nbits` bits, ties to even.
+#[no_mangle]
+pub unsafe extern "C" fn posit_round(p: *mut Posit, nbits: usize) -> UnumStatus {
+    status(|| {
+        let p = out_arg(p, "p")?;
+        (*p).round_to(width(nbits)?);
+        Ok(())
+    })
+}
+
+/// Exact negation.
+#[no_mangle]
+pub unsafe extern "C" fn posit_neg(p: *const Posit) -> *mut Posit {
+    handle(|| Ok(-arg(p, "p")?.clone()))
+}
+
+/// Exact sum.
+#[no_mangle]
+pub unsafe extern "C" fn posit_add(lhs: *const Posit, rhs: *const Posit) -> *mut Posit {
+    handle(|| Ok(arg(lhs, "lhs")? + arg(rhs, "rhs")?))
+}
+
+/// Exact difference.
+#[no_mangle]
+pub unsafe extern "C" fn posit_sub(lhs: *const Posit, rhs: *const Posit) -> *mut Posit {
+    handle(|| Ok(arg(lhs, "lhs")? - arg(rhs, "rhs")?))
+}
+
+/// Exact product.
+#[no_mangle]
+pub unsafe extern "C" fn posit_mul(lhs: *const Posit, rhs: *const Posit) -> *mut Posit {
+    handle(|| Ok(arg(lhs, "lhs")? * arg(rhs, "rhs")?))
+}
+
+/// Quotient, truncated to the combined fraction bits of the operands and 16 more.
+#[no_mangle]
+pub unsafe extern "C" fn posit_div(lhs: *const Posit, rhs: *const Posit) -> *mut Posit {
+    handle(|| Ok(arg(lhs, "lhs")? / arg(rhs, "rhs")?))
+}
+
+/// Exact `n`-th power.
+#[no_mangle]
+pub unsafe extern "C" fn posit_pow(p: *const Posit, n: u32) -> *mut Posit {
+    handle(|| Ok(arg(p, "p")?.clone().pow(n as usize)))
+}
+
+/// Square root rounded to `nbits` bits, NaR for negative values.
+#[no_mangle]
+pub unsafe extern "C" fn posit_sqrt(p: *const Posit, nbits: usize) -> *mut Posit {
+    handle(|| Ok(arg(p, "p")?.clone().sqrt(width(nbits)?)))
+}
+
+/// Stores the negation of `p` in `out`, which may be `p`.
 #[no_mangle]
-#[doc(hidden)]
-pub extern "C" fn posit_new() -> *mut u8 {
-    Box::into_raw(Box::new(Posit { bits: bitvec![] })) as *mut _
+pub unsafe extern "C" fn posit_neg_into(out: *mut Posit, p: *const Posit) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = -arg(p, "p")?.clone();
+        Ok(())
+    })
 }
 
+/// Stores the sum in `out`, which may be one of the operands.
 #[no_mangle]
-#[doc(hidden)]
-pub unsafe extern "C" fn posit_neg(p: *mut u8) -> *mut u8 {
-    let p = (p as *mut Posit).as_ref().unwrap();
-    let r = Box::new(-p.clone());
-    Box::into_raw(r) as *mut _
+pub unsafe extern "C" fn posit_add_into(
+    out: *mut Posit,
+    lhs: *const Posit,
+    rhs: *const Posit,
+) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = arg(lhs, "lhs")? + arg(rhs, "rhs")?;
+        Ok(())
+    })
 }
 
+/// Stores the difference in `out`, which may be one of the operands.
 #[no_mangle]
-#[doc(hidden)]
-pub unsafe extern "C" fn posit_add(lhs: *mut u8, rhs: *mut u8) -> *mut u8 {
-    let lhs = (lhs as *mut Posit).as_ref().unwrap();
-    let rhs = (rhs as *mut Posit).as_ref().unwrap();
-    let r = Box::new(lhs.clone() + rhs.clone());
-    Box::into_raw(r) as *mut _
+pub unsafe extern "C" fn posit_sub_into(
+    out: *mut Posit,
+    lhs: *const Posit,
+    rhs: *const Posit,
+) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = arg(lhs, "lhs")? - arg(rhs, "rhs")?;
+        Ok(())
+    })
 }
 
+/// Stores the product in `out`, which may be one of the operands.
 #[no_mangle]
-#[doc(hidden)]
-pub unsafe extern "C" fn posit_sub(lhs: *mut u8, rhs: *mut u8) -> *mut u8 {
-    let lhs = (lhs as *mut Posit).as_ref().unwrap();
-    let rhs = (rhs as *mut Posit).as_ref().unwrap();
-    let r = Box::new(lhs.clone() - rhs.clone());
-    Box::into_raw(r) as *mut _
+pub unsafe extern "C" fn posit_mul_into(
+    out: *mut Posit,
+    lhs: *const Posit,
+    rhs: *const Posit,
+) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = arg(lhs, "lhs")? * arg(rhs, "rhs")?;
+        Ok(())
+    })
 }
 
+/// Stores the quotient as `posit_div` computes it in `out`, which may be one of the
+/// operands.
 #[no_mangle]
-#[doc(hidden)]
-pub unsafe extern "C" fn posit_mul(lhs: *mut u8, rhs: *mut u8) -> *mut u8 {
-    let lhs = (lhs as *mut Posit).as_ref().unwrap();
-    let rhs = (rhs as *mut Posit).as_ref().unwrap();
-    let r = Box::new(lhs.clone() * rhs.clone());
-    Box::into_raw(r) as *mut _
+pub unsafe extern "C" fn posit_div_into(
+    out: *mut Posit,
+    lhs: *const Posit,
+    rhs: *const Posit,
+) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = arg(lhs, "lhs")? / arg(rhs, "rhs")?;
+        Ok(())
+    })
 }
 
+/// Stores the `n`-th power of `p` in `out`, which may be `p`.
 #[no_mangle]
-#[doc(hidden)]
-pub unsafe extern "C" fn posit_div(lhs: *mut u8, rhs: *mut u8) -> *mut u8 {
-    let lhs = (lhs as *mut Posit).as_ref().unwrap();
-    let rhs = (rhs as *mut Posit).as_ref().unwrap();
-    let r = Box::new(lhs.clone() / rhs.clone());
-    Box::into_raw(r) as *mut _
+pub unsafe extern "C" fn posit_pow_into(out: *mut Posit, p: *const Posit, n: u32) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = arg(p, "p")?.clone().pow(n as usize);
+        Ok(())
+    })
 }
 
+/// Stores the square root of `p` rounded to `nbits` bits in `out`, which may be `p`.
 #[no_mangle]
-#[doc(hidden)]
-pub unsafe extern "C" fn posit_free(p: *mut u8) {
-    Box::from_raw(p as *mut Posit);
+pub unsafe extern "C" fn posit_sqrt_into(
+    out: *mut Posit,
+    p: *const Posit,
+    nbits: usize,
+) -> UnumStatus {
+    status(|| {
+        let out = out_arg(out, "out")?;
+        *out = arg(p, "p")?.clone().sqrt(width(nbits)?);
+        Ok(())
+    })
 }
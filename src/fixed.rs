@@ -0,0 +1,411 @@
+use std::cmp;
+use std::fmt;
+use std::ops::*;
+
+use bitvec::prelude::*;
+
+use crate::{Posit, Regime};
+
+/// Exponent size of the standard fixed-width posits.
+const ES: u32 = 2;
+
+/// Absolute value of a posit split into its binary scale and significand.
+///
+/// The significand keeps the hidden bit in its most significant bit, so the value is
+/// `sig / 2^63 * 2^scale`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Unpacked {
+    pub(crate) sign: bool,
+    pub(crate) scale: i32,
+    pub(crate) sig: u64,
+}
+
+/// Unpacks an `n`-bit posit pattern with `es` exponent bits stored in the low bits of
+/// `bits`. Zero and NaR are not valid inputs.
+pub(crate) fn decode(n: u32, es: u32, bits: u64) -> Unpacked {
+    let mask = u64::MAX >> (64 - n);
+    let sign = (bits >> (n - 1)) & 1 != 0;
+    let bits = if sign { bits.wrapping_neg() & mask } else { bits };
+
+    // Left-align the bits following the sign
+    let x = bits << (65 - n);
+    let (k, run) = if x >> 63 != 0 {
+        let run = (!x).leading_zeros();
+        (run as i32 - 1, run)
+    } else {
+        let run = x.leading_zeros();
+        (-(run as i32), run)
+    };
+    let rest = x.checked_shl(run + 1).unwrap_or(0);
+    let exp = rest.checked_shr(64 - es).unwrap_or(0) as i32;
+    let frac = rest << es;
+
+    Unpacked {
+        sign,
+        scale: (k << es) + exp,
+        sig: (1 << 63) | (frac >> 1),
+    }
+}
+
+/// Rounds `(-1)^sign * sig / 2^127 * 2^scale` to the nearest `n`-bit posit with `es`
+/// exponent bits, ties to even.
+///
+/// `sig` must have its most significant bit set. `sticky` tells whether there are nonzero
+/// bits below `sig` that were lost. Values outside of the dynamic range saturate to maxpos
+/// or minpos, rounding never produces zero or NaR.
+pub(crate) fn encode(n: u32, es: u32, sign: bool, scale: i32, sig: u128, sticky: bool) -> u64 {
+    let mask = u64::MAX >> (64 - n);
+    let max_scale = ((n - 2) << es) as i32;
+
+    let mag = if scale >= max_scale {
+        mask >> 1
+    } else if scale < -max_scale {
+        1
+    } else {
+        let k = scale >> es;
+        let exp = (scale & ((1 << es) - 1)) as u128;
+
+        // Regime bits, right-aligned
+        let (regime, regime_len) = if k >= 0 {
+            (((1u128 << (k + 1)) - 1) << 1, k as u32 + 2)
+        } else {
+            (1, (-k) as u32 + 1)
+        };
+        let mut body = (regime << es) | exp;
+        let len = regime_len + es;
+
+        // Fill `n` bits: the magnitude without the sign and one guard bit
+        let frac = sig << 1;
+        let mut sticky = sticky;
+        if len <= n {
+            let avail = n - len;
+            if avail > 0 {
+                body = (body << avail) | (frac >> (128 - avail));
+            }
+            sticky |= (frac << avail) != 0;
+        } else {
+            let extra = len - n;
+            sticky |= frac != 0 || body & ((1 << extra) - 1) != 0;
+            body >>= extra;
+        }
+
+        let mut mag = (body >> 1) as u64;
+        let guard = body & 1 != 0;
+        if guard && (sticky || mag & 1 != 0) {
+            mag += 1;
+        }
+        mag
+    };
+
+    if sign {
+        mag.wrapping_neg() & mask
+    } else {
+        mag
+    }
+}
+
+pub(crate) fn mul(n: u32, es: u32, lhs: Unpacked, rhs: Unpacked) -> u64 {
+    let sign = lhs.sign != rhs.sign;
+    let sig = lhs.sig as u128 * rhs.sig as u128;
+    if sig >> 127 != 0 {
+        encode(n, es, sign, lhs.scale + rhs.scale + 1, sig, false)
+    } else {
+        encode(n, es, sign, lhs.scale + rhs.scale, sig << 1, false)
+    }
+}
+
+pub(crate) fn div(n: u32, es: u32, lhs: Unpacked, rhs: Unpacked) -> u64 {
+    let sign = lhs.sign != rhs.sign;
+    let dividend = (lhs.sig as u128) << 64;
+    let divisor = rhs.sig as u128;
+    let quot = dividend / divisor;
+    let sticky = !dividend.is_multiple_of(divisor);
+    if quot >> 64 != 0 {
+        encode(n, es, sign, lhs.scale - rhs.scale, quot << 63, sticky)
+    } else {
+        encode(n, es, sign, lhs.scale - rhs.scale - 1, quot << 64, sticky)
+    }
+}
+
+/// Adds two values, `sub` flips the sign of the right operand.
+pub(crate) fn add(n: u32, es: u32, mut lhs: Unpacked, mut rhs: Unpacked, sub: bool) -> u64 {
+    rhs.sign ^= sub;
+    if (lhs.scale, lhs.sig) < (rhs.scale, rhs.sig) {
+        std::mem::swap(&mut lhs, &mut rhs);
+    }
+
+    // Leave one bit above the hidden one for the carry
+    let l_sig = (lhs.sig as u128) << 63;
+    let r_sig = (rhs.sig as u128) << 63;
+    let shift = (lhs.scale - rhs.scale) as u32;
+    let (r_sig, sticky) = if shift >= 128 {
+        (0, true)
+    } else {
+        (r_sig >> shift, r_sig & ((1 << shift) - 1) != 0)
+    };
+
+    let sig = if lhs.sign == rhs.sign {
+        l_sig + r_sig
+    } else if sticky {
+        // The true subtrahend is slightly bigger than `r_sig`
+        l_sig - r_sig - 1
+    } else {
+        l_sig - r_sig
+    };
+    if sig == 0 {
+        return 0;
+    }
+
+    let shift = sig.leading_zeros();
+    encode(n, es, lhs.sign, lhs.scale + 1 - shift as i32, sig << shift, sticky)
+}
+
+/// Rounds an arbitrary-length posit to an `n`-bit pattern.
+fn from_posit(n: u32, p: &Posit) -> u64 {
+    if p.is_zero() {
+        return 0;
+    }
+    if p.is_nar() {
+        return 1 << (n - 1);
+    }
+    let (scale, frac) = p.scale_and_fraction();
+    let mut bits = frac.iter();
+    let mut sig = 1u128 << 127;
+    for i in (0..127).rev() {
+        match bits.next() {
+            Some(bit) => sig |= (bit as u128) << i,
+            None => break,
+        }
+    }
+    let sticky = bits.any(|i| i);
+    encode(n, ES, p.is_negative(), scale as i32, sig, sticky)
+}
+
+/// Converts an `n`-bit pattern into an arbitrary-length posit exactly.
+fn to_posit(n: u32, bits: u64) -> Posit {
+    if bits == 0 {
+        return Posit::zero();
+    }
+    if bits == 1 << (n - 1) {
+        return Posit::nar();
+    }
+    let value = decode(n, ES, bits);
+    let (regime, exp) = Regime::from_scale(value.scale as isize, ES as usize);
+    let mut frac = BitVec::new();
+    frac.extend((value.sig << 1).as_bitslice::<BigEndian>());
+    Posit::from_parts(value.sign, regime, exp, &frac)
+}
+
+macro_rules! fixed_posit {
+    ($(#[$attr: meta])* $name: ident($bits: ty, $signed: ty)) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct $name($bits);
+
+        impl $name {
+            const N: u32 = <$bits>::MAX.count_ones();
+
+            pub const ZERO: Self = $name(0);
+            pub const ONE: Self = $name(1 << (Self::N - 2));
+            pub const NAR: Self = $name(1 << (Self::N - 1));
+            pub const MAXPOS: Self = $name(<$bits>::MAX >> 1);
+            pub const MINPOS: Self = $name(1);
+
+            pub const fn from_bits(bits: $bits) -> Self {
+                $name(bits)
+            }
+
+            pub const fn to_bits(self) -> $bits {
+                self.0
+            }
+
+            pub fn is_zero(self) -> bool {
+                self == Self::ZERO
+            }
+
+            pub fn is_nar(self) -> bool {
+                self == Self::NAR
+            }
+
+            pub fn is_negative(self) -> bool {
+                (self.0 as $signed) < 0
+            }
+
+            pub fn abs(self) -> Self {
+                if self.is_negative() {
+                    -self
+                } else {
+                    self
+                }
+            }
+
+            fn unpack(self) -> Unpacked {
+                decode(Self::N, ES, self.0 as u64)
+            }
+        }
+
+        impl cmp::PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        /// Posits are ordered as two's complement integers, NaR is the smallest value.
+        impl cmp::Ord for $name {
+            fn cmp(&self, other: &Self) -> cmp::Ordering {
+                (self.0 as $signed).cmp(&(other.0 as $signed))
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                $name(self.0.wrapping_neg())
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                if self.is_nar() || rhs.is_nar() {
+                    return Self::NAR;
+                }
+                if self.is_zero() {
+                    return rhs;
+                }
+                if rhs.is_zero() {
+                    return self;
+                }
+                $name(add(Self::N, ES, self.unpack(), rhs.unpack(), false) as $bits)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                if self.is_nar() || rhs.is_nar() {
+                    return Self::NAR;
+                }
+                if self.is_zero() {
+                    return -rhs;
+                }
+                if rhs.is_zero() {
+                    return self;
+                }
+                $name(add(Self::N, ES, self.unpack(), rhs.unpack(), true) as $bits)
+            }
+        }
+
+        impl Mul for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                if self.is_nar() || rhs.is_nar() {
+                    return Self::NAR;
+                }
+                if self.is_zero() || rhs.is_zero() {
+                    return Self::ZERO;
+                }
+                $name(mul(Self::N, ES, self.unpack(), rhs.unpack()) as $bits)
+            }
+        }
+
+        impl Div for $name {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                if self.is_nar() || rhs.is_nar() || rhs.is_zero() {
+                    return Self::NAR;
+                }
+                if self.is_zero() {
+                    return Self::ZERO;
+                }
+                $name(div(Self::N, ES, self.unpack(), rhs.unpack()) as $bits)
+            }
+        }
+
+        /// Remainder of the division rounded towards zero, rounded to the nearest
+        /// representable value.
+        impl Rem for $name {
+            type Output = Self;
+
+            fn rem(self, rhs: Self) -> Self::Output {
+                Self::from(Posit::from(self).remainder(&Posit::from(rhs)))
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign for $name {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign for $name {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl RemAssign for $name {
+            fn rem_assign(&mut self, rhs: Self) {
+                *self = *self % rhs;
+            }
+        }
+
+        /// Shortest decimal which rounds back to the same value at this width, or the given
+        /// number of digits after the decimal point.
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                Posit::from(*self).fmt_decimal(f, Some(Self::N as usize))
+            }
+        }
+
+        /// Rounds to the nearest representable value, ties to even.
+        impl From<Posit> for $name {
+            fn from(p: Posit) -> Self {
+                $name(from_posit(Self::N, &p) as $bits)
+            }
+        }
+
+        impl From<$name> for Posit {
+            fn from(p: $name) -> Self {
+                to_posit($name::N, p.0 as u64)
+            }
+        }
+    };
+}
+
+fixed_posit! {
+    /// Standard 8-bit posit with 2 exponent bits.
+    P8(u8, i8)
+}
+
+fixed_posit! {
+    /// Standard 16-bit posit with 2 exponent bits.
+    P16(u16, i16)
+}
+
+fixed_posit! {
+    /// Standard 32-bit posit with 2 exponent bits.
+    P32(u32, i32)
+}
+
+fixed_posit! {
+    /// Standard 64-bit posit with 2 exponent bits.
+    P64(u64, i64)
+}
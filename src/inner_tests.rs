@@ -1,11 +1,12 @@
 use super::*;
+use crate::TestPosit as Posit;
 
 macro_rules! test {
     ($name: ident: ($left: expr) < ($right: expr)) => {
         #[test]
         fn $name() {
-            let lhs = Posit { bits: $left };
-            let rhs = Posit { bits: $right };
+            let lhs = $left;
+            let rhs = $right;
             assert_eq!(lhs.partial_cmp(&rhs), Some(cmp::Ordering::Less));
         }
     };
@@ -19,233 +20,635 @@ macro_rules! test {
         #[test]
         fn $name() {
             {
-                let lhs = Posit { bits: $left };
-                let rhs = Posit { bits: $right };
+                let lhs = $left;
+                let rhs = $right;
                 let res = lhs * rhs;
-                assert_eq!(res.bits, $expected);
+                assert_eq!(res, $expected);
             }
             {
-                let lhs = Posit { bits: $right };
-                let rhs = Posit { bits: $left };
+                let lhs = $right;
+                let rhs = $left;
                 let res = lhs * rhs;
-                assert_eq!(res.bits, $expected);
+                assert_eq!(res, $expected);
             }
         }
     };
     ($name: ident: ($left: expr) / ($right: expr) = ($expected: expr)) => {
         #[test]
         fn $name() {
-            let lhs = Posit { bits: $left };
-            let rhs = Posit { bits: $right };
+            let lhs = $left;
+            let rhs = $right;
             let res = lhs / rhs;
-            assert_eq!(res.bits[..$expected.len()], $expected);
+            assert_eq!(res.bits[..$expected.bits.len()], $expected.bits);
         }
     };
     ($name: ident: ($left: expr) + ($right: expr) = ($expected: expr)) => {
         #[test]
         fn $name() {
             {
-                let lhs = Posit { bits: $left };
-                let rhs = Posit { bits: $right };
+                let lhs = $left;
+                let rhs = $right;
                 let res = lhs + rhs;
-                assert_eq!(res.bits, $expected);
+                assert_eq!(res, $expected);
             }
             {
-                let lhs = Posit { bits: $right };
-                let rhs = Posit { bits: $left };
+                let lhs = $right;
+                let rhs = $left;
                 let res = lhs + rhs;
-                assert_eq!(res.bits, $expected);
+                assert_eq!(res, $expected);
             }
             {
-                let lhs = Posit { bits: $expected };
-                let rhs = Posit { bits: $left };
+                let lhs = $expected;
+                let rhs = $left;
                 let res = lhs - rhs;
-                assert_eq!(res.bits, $right);
+                assert_eq!(res, $right);
             }
             {
-                let lhs = Posit { bits: $expected };
-                let rhs = Posit { bits: $right };
+                let lhs = $expected;
+                let rhs = $right;
                 let res = lhs - rhs;
-                assert_eq!(res.bits, $left);
+                assert_eq!(res, $left);
+            }
+        }
+    };
+    ($name: ident: ($left: expr) round_to ($nbits: expr) = ($expected: expr)) => {
+        #[test]
+        fn $name() {
+            {
+                let mut res = $left;
+                res.round_to($nbits);
+                assert_eq!(res, $expected);
+            }
+            {
+                let mut res = -$left;
+                res.round_to($nbits);
+                assert_eq!(res, -$expected);
             }
         }
     };
     ($name: ident: ($left: expr) ^ ($right: expr) = ($expected: expr)) => {
         #[test]
         fn $name() {
-            let lhs = Posit { bits: $left };
+            let lhs = $left;
             let rhs: usize = $right;
             let res = lhs.pow(rhs);
-            assert_eq!(res.bits, $expected);
+            assert_eq!(res, $expected);
         }
     };
 }
 
 test! { convert_1:
-    (Posit::from(0.625_f64).bits) =
-    (bitvec![0, 0, 1, 1, 1, 0, 1])
+    (Posit::from(0.625_f64)) =
+    (posit!(0b0_01_11_01))
 }
 
 test! { convert_2:
     (Posit::from(10e-8).bits[..13]) =
-    (bitvec![0,  0, 0, 0, 0, 0, 0, 1,  0, 0,  1, 0, 1])
+    (posit!(0b0_0000001_00_101).bits)
 }
 
 test! { convert_3:
     (Posit::from(10f64.powf(5.0)).bits[..16]) =
-    (bitvec![0,  1, 1, 1, 1, 1, 0,  0, 0,  1, 0, 0, 0, 0, 1, 1])
+    (posit!(0b0_111110_00_1000011).bits)
 }
 
 test! { convert_4:
-    (Posit::from(-0.625_f64).bits) =
-    (bitvec![1, 0, 1, 1, 1, 0, 1])
+    (Posit::from(-0.625_f64)) =
+    (posit!(0b1_01_11_01))
 }
 
 test! { convert_5:
-    (Posit::from(3f64.powf(2.0)).bits) =
-    (bitvec![0, 1, 0, 1, 1, 0, 0, 1])
+    (Posit::from(3f64.powf(2.0))) =
+    (posit!(0b0_10_11_001))
 }
 
 test! { convert_6:
-    (Posit::from(3f64).bits) =
-    (bitvec![0, 1, 0, 0, 1, 1])
+    (Posit::from(3f64)) =
+    (posit!(0b0_10_01_1))
 }
 
 test! { convert_7:
-    (Posit::from(3f64.powf(3.0)).bits) =
-    (bitvec![0, 1, 1, 0, 0, 0, 1, 0, 1, 1])
+    (Posit::from(3f64.powf(3.0))) =
+    (posit!(0b0_110_00_1011))
 }
 
 test! { multiplication_1:
-    (bitvec![0, 1, 0, 0, 1, 0]) * // 2
-    (bitvec![0, 1, 0, 0, 0, 1]) = // 1.5
-    (bitvec![0, 1, 0, 0, 1, 1]) // 3
+    (posit!(0b0_10_01_0)) * // 2
+    (posit!(0b0_10_00_1)) = // 1.5
+    (posit!(0b0_10_01_1)) // 3
 }
 
 test! { multiplication_2:
-    (bitvec![0, 1, 0, 1, 1]) * // 8
-    (bitvec![0, 1, 0, 1, 1, 1]) = // 12
-    (bitvec![0, 1, 1, 0, 1, 0, 1]) // 96
+    (posit!(0b0_10_11)) * // 8
+    (posit!(0b0_10_11_1)) = // 12
+    (posit!(0b0_110_10_1)) // 96
 }
 
 test! { multiplication_3:
-    (bitvec![0, 1, 0, 0, 0, 0, 1]) * // 1.25
-    (bitvec![1, 1, 0, 0, 0, 1]) = // -1.5
-    (bitvec![1, 1, 0, 0, 0, 1, 1, 1]) // -1.875
+    (posit!(0b0_10_00_01)) * // 1.25
+    (posit!(0b1_10_00_1)) = // -1.5
+    (posit!(0b1_10_00_111)) // -1.875
 }
 
 test! { multiplication_4:
-    (Posit::from(10f64.powf(5.0)).bits[..16].to_owned()) *
-    (Posit::from(10f64.powf(5.0)).bits[..16].to_owned()) =
-    (bitvec![
-        0,
-        1, 1, 1, 1, 1, 1, 1, 1, 1, 0, // 16^8 *
-        0, 1, // 2^1 *
-        0, 0, 1, 0, 1, 0, 0, 1, 0, 0, 0, 1, 0, 0, 1 // (1 + 0b001010010001001 / 2**15)
-    ])
+    (posit!(0b0_111110_00_1000011)) * // first 16 bits of 10^5
+    (posit!(0b0_111110_00_1000011)) =
+    (posit!(0b0_1111111110_01_001010010001001)) // 16^8 * 2^1 * (1 + 0b001010010001001 / 2**15)
 }
 
 test! { multiplication_5:
-    (Posit::from(3f64.powf(3.0)).bits) *
-    (Posit::from(3f64).bits) =
-    (bitvec![
-        0,
-        1, 1, 0,
-        1, 0,
-        0, 1, 0, 0, 0, 1
-    ])
+    (Posit::from(3f64.powf(3.0))) *
+    (Posit::from(3f64)) =
+    (posit!(0b0_110_10_010001)) // 81
 }
 test! { multiplication_6:
-    (bitvec![0, 1, 1, 0, 0, 0, 1, 0, 1, 1]) * // 16 * 1 * 1.6875 = 27
-    (bitvec![0, 1, 0, 0, 1, 1]) = // 2 * 1.5 = 3
-    (bitvec![
-        0,
-        1, 1, 0,
-        1, 0,
-        0, 1, 0, 0, 0, 1
-    ])
+    (posit!(0b0_110_00_1011)) * // 16 * 1 * 1.6875 = 27
+    (posit!(0b0_10_01_1)) = // 2 * 1.5 = 3
+    (posit!(0b0_110_10_010001)) // 16 * 4 * 1.265625 = 81
 }
 
+// The exponent of the quotient used to be expected as 11, which encodes 2/3 = 4/3 * 2^-1. That
+// held only while division left a quotient of fractions below one unnormalized, with its scale
+// one too high; the quotient is 1/3.
 test! { division_1:
-    (bitvec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]) /
-    (bitvec![0, 1, 0, 0, 1, 1]) =
-    (bitvec![0, 0, 1, 1, 1, 0, 1, 0, 1, 0, 1, 0, 1])
+    (posit!(0b0_10_00_000000000)) / // 1
+    (posit!(0b0_10_01_1)) = // 3
+    (posit!(0b0_01_10_01010101)) // 0.333...
 }
 
 test! { division_2:
-    (bitvec![0, 1, 0, 1, 1]) / // 8
-    (bitvec![0, 1, 0, 1, 0]) = // 4
-    (bitvec![0, 1, 0, 0, 1]) // 2
+    (posit!(0b0_10_11)) / // 8
+    (posit!(0b0_10_10)) = // 4
+    (posit!(0b0_10_01)) // 2
+}
+
+test! { division_rounded_1:
+    (Posit::one().div_rounded(Posit::from(3f64), 12)) = // 1 / 3
+    (posit!(0b0_01_10_0101011)) // 0.333..., rounded up
+}
+
+test! { division_rounded_2:
+    (Posit::from(3f64).div_rounded(Posit::from(2f64), 5)) = // 1.5
+    (posit!(0b0_1)) // tie between 1 and 2, rounded to the even 1
+}
+
+test! { division_rounded_3:
+    (Posit::from(151f64).div_rounded(Posit::from(100f64), 5)) = // 1.51
+    (posit!(0b0_10_01)) // just above a tie, the remainder must not be lost
+}
+
+test! { round_exact:
+    (posit!(0b0_10_01_1)) round_to (8) = // 3
+    (posit!(0b0_10_01_1))
+}
+
+test! { round_tie_to_even_down:
+    (posit!(0b0_10_00_0001)) round_to (8) = // 1 + 1/16
+    (posit!(0b0_1))
+}
+
+test! { round_tie_to_even_up:
+    (posit!(0b0_10_00_0011)) round_to (8) = // 1 + 3/16
+    (posit!(0b0_10_00_01)) // 1 + 1/4
+}
+
+test! { round_sticky:
+    (posit!(0b0_10_00_00010001)) round_to (8) = // just above 1 + 1/16
+    (posit!(0b0_10_00_001)) // 1 + 1/8
+}
+
+test! { round_carry_into_exponent:
+    (posit!(0b0_10_01_1111)) round_to (8) = // 2 * 1.9375
+    (posit!(0b0_10_1)) // 4
+}
+
+test! { round_carry_into_regime:
+    (posit!(0b0_10_11_1111)) round_to (8) = // 8 * 1.9375
+    (posit!(0b0_11)) // 16, the regime becomes longer
+}
+
+test! { round_regime_tie:
+    (posit!(0b0_1111110_1)) round_to (8) = // 2^22, halfway between 2^20 and 2^24
+    (posit!(0b0_111111))
+}
+
+test! { round_maxpos:
+    (posit!(0b0_1111111110_1)) round_to (8) = // 2^32 * 1.5
+    (posit!(0b0_1111111)) // maxpos = 2^24
+}
+
+test! { round_minpos:
+    (posit!(0b0_00000000001)) round_to (8) = // 2^-40
+    (posit!(0b0_0000001)) // minpos = 2^-24
+}
+
+test! { round_minpos_tie:
+    (posit!(0b0_0000001_1)) round_to (8) = // 1.5 * 2^-24
+    (posit!(0b0_000001)) // 2^-20, the exponent bits are rounded away
 }
 
 test! { add_sub_zero:
-    (bitvec![0, 1, 0, 0, 1]) + // 2
-    (Posit::zero().bits) =
-    (bitvec![0, 1, 0, 0, 1])
+    (posit!(0b0_10_01)) + // 2
+    (Posit::zero()) =
+    (posit!(0b0_10_01))
 }
 
 test! { add_sub_1:
-    (bitvec![0, 1, 0, 0, 1]) + // 2
-    (bitvec![0, 1, 0, 0, 0, 1]) = // 1.5
-    (bitvec![0, 1, 0, 0, 1, 1, 1]) // 3.5
+    (posit!(0b0_10_01)) + // 2
+    (posit!(0b0_10_00_1)) = // 1.5
+    (posit!(0b0_10_01_11)) // 3.5
 }
 
 test! { add_sub_2:
-    (bitvec![0, 1, 0, 0, 1]) + // 2
-    (bitvec![0, 1, 0, 0, 1, 1]) = // 3
-    (bitvec![0, 1, 0, 1, 0, 0, 1]) // 5
+    (posit!(0b0_10_01)) + // 2
+    (posit!(0b0_10_01_1)) = // 3
+    (posit!(0b0_10_10_01)) // 5
 }
 
 test! { add_sub_3:
-    (bitvec![0, 1, 0, 1, 1]) + // 8
-    (bitvec![0, 1, 0, 1, 1, 1]) = // 12
-    (bitvec![0, 1, 1, 0, 0, 0, 0, 1]) // 20
+    (posit!(0b0_10_11)) + // 8
+    (posit!(0b0_10_11_1)) = // 12
+    (posit!(0b0_110_00_01)) // 20
 }
 
 test! { add_sub_4:
-    (bitvec![1, 1, 0, 1, 1]) + // -8
-    (bitvec![0, 1, 0, 1, 1, 1]) = // 12
-    (bitvec![0, 1, 0, 1]) // 4
+    (posit!(0b1_10_11)) + // -8
+    (posit!(0b0_10_11_1)) = // 12
+    (posit!(0b0_10_1)) // 4
 }
 
 test! { add_sub_5:
-    ((Posit::from(10f64.powf(5.0)) * Posit::from(10f64.powf(5.0))).bits) +
-    ((-Posit::from(10f64.powf(5.0)) * Posit::from(10f64.powf(5.0))).bits) =
-    (bitvec![])
+    (Posit::from(10f64.powf(5.0)) * Posit::from(10f64.powf(5.0))) +
+    (-Posit::from(10f64.powf(5.0)) * Posit::from(10f64.powf(5.0))) =
+    (Posit::zero())
 }
 
 test! { add_sub_6:
-    ((Posit::from(3f64.powf(3.0)) * Posit::from(3f64.powf(1.0))).bits) =
-    ((Posit::from(3f64.powf(2.0)) * Posit::from(3f64.powf(2.0))).bits)
+    (Posit::from(3f64.powf(3.0)) * Posit::from(3f64.powf(1.0))) =
+    (Posit::from(3f64.powf(2.0)) * Posit::from(3f64.powf(2.0)))
 }
 
 test! { lt_1:
-    (bitvec![1, 1, 0, 1, 1]) < (bitvec![0, 1, 0, 1, 1, 1])
+    (posit!(0b1_10_11)) < (posit!(0b0_10_11_1))
 }
 
 test! { lt_2:
-    (bitvec![1, 1, 0, 1, 1, 1]) < (bitvec![1, 1, 0, 1, 1])
+    (posit!(0b1_10_11_1)) < (posit!(0b1_10_11))
 }
 
 test! { lt_3:
-    (bitvec![0, 1, 0, 0, 0]) < (bitvec![0, 1, 0, 0, 0, 1])
+    (posit!(0b0_10_00)) < (posit!(0b0_10_00_1))
 }
 
 test! { lt_4:
-    (bitvec![1, 1, 0, 0, 0, 1]) < (bitvec![1, 1, 0, 0, 0])
+    (posit!(0b1_10_00_1)) < (posit!(0b1_10_00))
 }
 
 test! { lt_5:
-    (bitvec![0, 0, 0, 0, 0, 1, 1, 0, 0]) <
-    (bitvec![0, 0, 0, 0, 0, 1, 1, 0, 1])
+    (posit!(0b0_00001_10_0)) <
+    (posit!(0b0_00001_10_1))
 }
 
 test! { pow_1:
-    (bitvec![0, 1, 0, 0, 0, 1]) ^ (2) =
-    (bitvec![0, 1, 0, 0, 1, 0, 0, 1])
+    (posit!(0b0_10_00_1)) ^ (2) =
+    (posit!(0b0_10_01_001))
 }
 
 test! { pow_2:
-    (bitvec![0, 1, 0, 0, 1, 1]) ^ (3) =
-    (bitvec![0, 1, 1, 0, 0, 0, 1, 0, 1, 1])
+    (posit!(0b0_10_01_1)) ^ (3) =
+    (posit!(0b0_110_00_1011))
+}
+
+/// Values that every exponent size represents exactly, as do `f64` sums and products of them.
+const EXACT: [f64; 12] = [
+    1.0,
+    -1.0,
+    1.5,
+    2.0,
+    -3.0,
+    0.25,
+    -0.625,
+    12.0,
+    27.0,
+    96.0,
+    3.0 * 1048576.0, // 3 * 2^20
+    -1.0 / 1048576.0, // -2^-20
+];
+
+macro_rules! test_es {
+    ($name: ident: $es: expr) => {
+        #[test]
+        fn $name() {
+            type P = super::Posit<$es>;
+            for &l in EXACT.iter() {
+                for &r in EXACT.iter() {
+                    let lhs = P::from(l);
+                    let rhs = P::from(r);
+                    if l + r != 0.0 {
+                        assert_eq!((&lhs + &rhs).bits, P::from(l + r).bits, "{} + {}", l, r);
+                    }
+                    if l - r != 0.0 {
+                        assert_eq!((&lhs - &rhs).bits, P::from(l - r).bits, "{} - {}", l, r);
+                    }
+                    assert_eq!((&lhs * &rhs).bits, P::from(l * r).bits, "{} * {}", l, r);
+                    assert_eq!((P::from(l * r) / &rhs).bits, lhs.bits, "{} / {}", l * r, r);
+                    assert_eq!(lhs.partial_cmp(&rhs), l.partial_cmp(&r), "{} <=> {}", l, r);
+                }
+            }
+        }
+    };
+}
+
+test_es! { es_0: 0 }
+test_es! { es_1: 1 }
+test_es! { es_2: 2 }
+test_es! { es_3: 3 }
+test_es! { es_4: 4 }
+
+macro_rules! test_operator_forms {
+    ($name: ident: $op: tt, $op_assign: tt) => {
+        #[test]
+        fn $name() {
+            for &l in EXACT.iter().chain(&[0.0]) {
+                for &r in EXACT.iter().chain(&[0.0]) {
+                    let (lhs, rhs) = (Posit::from(l), Posit::from(r));
+                    let expected = lhs.clone() $op rhs.clone();
+                    assert_eq!((&lhs $op &rhs).bits, expected.bits, "{} {} {}", l, stringify!($op), r);
+                    assert_eq!((&lhs $op rhs.clone()).bits, expected.bits);
+                    assert_eq!((lhs.clone() $op &rhs).bits, expected.bits);
+                    let mut result = lhs.clone();
+                    result $op_assign &rhs;
+                    assert_eq!(result.bits, expected.bits);
+                    let mut result = lhs;
+                    result $op_assign rhs;
+                    assert_eq!(result.bits, expected.bits);
+                }
+            }
+        }
+    };
+}
+
+test_operator_forms! { forms_add: +, += }
+test_operator_forms! { forms_sub: -, -= }
+test_operator_forms! { forms_mul: *, *= }
+test_operator_forms! { forms_div: /, /= }
+test_operator_forms! { forms_rem: %, %= }
+
+#[test]
+fn negate_reference() {
+    let p = Posit::from(-2.5);
+    assert_eq!(-&p, Posit::from(2.5));
+    assert!((-&Posit::nar()).is_nar());
+    assert!((-&Posit::zero()).is_zero());
+}
+
+test! { convert_es_0:
+    (super::Posit::<0>::from(-6.0)) =
+    (posit!(0b1_1110_1, es = 0))
+}
+
+test! { convert_es_1:
+    (super::Posit::<1>::from(0.375)) =
+    (posit!(0b0_01_0_1, es = 1))
+}
+
+test! { convert_es_3:
+    (super::Posit::<3>::from(768.0)) =
+    (posit!(0b0_110_001_1, es = 3))
+}
+
+test! { convert_es_4:
+    (super::Posit::<4>::from(2f64.powi(-40))) =
+    (posit!(0b0_0001_1, es = 4))
+}
+
+#[test]
+fn quire_cancellation() {
+    let big = Posit::from(2f64.powi(100));
+    let mut quire = Quire::new();
+    quire.add_posit(&big);
+    quire.add_posit(&Posit::one());
+    quire.sub_posit(&big);
+    assert_eq!(quire.to_posit(8), Posit::one());
+}
+
+#[test]
+fn quire_negative_shift() {
+    let tiny = Posit::from(2f64.powi(-70));
+    let mut quire = Quire::new();
+    quire.sub_posit(&Posit::one());
+    quire.add_posit(&tiny);
+    assert_eq!(quire.to_posit(128), -Posit::one() + tiny);
+}
+
+#[test]
+fn quire_nar() {
+    let mut quire = Quire::new();
+    quire.add_product(&Posit::one(), &Posit::nar());
+    quire.add_posit(&Posit::one());
+    assert!(quire.is_nar());
+    assert_eq!(quire.to_posit(16), Posit::nar());
+    assert_eq!(Quire::new().to_posit(16), Posit::zero());
+}
+
+#[test]
+fn quire_fma() {
+    let a = Posit::from(1.125); // 1.125^2 = 1.265625 rounds to 1.25 in 8 bits
+    let c = Posit::from(-1.25);
+    let separate = a.clone().mul_rounded(a.clone(), 8).add_rounded(c.clone(), 8);
+    assert_eq!(separate, Posit::zero());
+    assert_eq!(Quire::fma(&a, &a, &c).to_posit(8), Posit::from(2f64.powi(-6)));
+}
+
+#[test]
+fn quire_fdp() {
+    let a: Vec<Posit> = EXACT.iter().map(|&i| Posit::from(i)).collect();
+    let b: Vec<Posit> = EXACT.iter().rev().map(|&i| Posit::from(-i)).collect();
+    let mut exact = Posit::zero();
+    let mut quire = Quire::new();
+    for (l, r) in a.iter().zip(&b) {
+        exact += l * r;
+        quire.sub_product(l, r); // cancelled by one of the additions below
+        quire.add_product(l, r);
+        quire.add_product(l, r);
+    }
+    assert_eq!(quire.to_posit(256), exact);
+    assert_eq!(Quire::fdp(&a, &b).to_posit(256), exact);
+    let mut rounded = exact.clone();
+    rounded.round_to(16);
+    assert_eq!(Quire::fdp(&a, &b).to_posit(16), rounded);
+}
+
+#[test]
+fn decode_fields() {
+    let p: Posit = crate::posit!(0b0_110_01_101);
+    let decoded = p.decode().unwrap();
+    assert_eq!(
+        decoded,
+        Decoded {
+            sign: false,
+            regime: 1,
+            exponent: 1,
+            fraction: bitvec![1, 0, 1],
+        }
+    );
+    assert_eq!(decoded.scale(), 5);
+    assert_eq!(decoded.encode(), p);
+
+    let decoded = Posit::from(-0.015625 * 1.5).decode().unwrap();
+    assert!(decoded.sign);
+    assert_eq!((decoded.regime, decoded.exponent), (-2, 2));
+    assert_eq!(decoded.scale(), -6);
+    assert_eq!(decoded.fraction, bitvec![1]);
+}
+
+#[test]
+fn decode_truncated() {
+    // Missing exponent bits read as zeros
+    let p: Posit = crate::posit!(0b0_10_1);
+    let decoded = p.decode().unwrap();
+    assert_eq!((decoded.regime, decoded.exponent), (0, 2));
+    assert!(decoded.fraction.is_empty());
+    assert_eq!(decoded.encode(), p);
+    assert_eq!(Posit::maxpos(8).decode().unwrap().regime, 6);
+}
+
+#[test]
+fn decode_special() {
+    assert_eq!(Posit::zero().decode(), None);
+    assert_eq!(Posit::nar().decode(), None);
+}
+
+#[test]
+fn encode_carry() {
+    let decoded = Decoded::<2> {
+        sign: false,
+        regime: -1,
+        exponent: 9,
+        fraction: bitvec![1],
+    };
+    assert_eq!(decoded.scale(), 5);
+    assert_eq!(decoded.encode(), Posit::from(48));
+    for &x in &[1.0, -3.75, 1e-30, 7e40, -0.1] {
+        let p: crate::Posit<0> = crate::Posit::from(x);
+        assert_eq!(p.decode().unwrap().encode(), p);
+    }
+}
+
+#[test]
+fn total_order() {
+    let mut values = vec![
+        Posit::from(2),
+        Posit::nar(),
+        Posit::from(-0.5),
+        Posit::zero(),
+        Posit::maxpos(8),
+        -Posit::maxpos(8),
+        Posit::minpos(8),
+    ];
+    values.sort();
+    assert_eq!(
+        values,
+        vec![
+            Posit::nar(),
+            -Posit::maxpos(8),
+            Posit::from(-0.5),
+            Posit::zero(),
+            Posit::minpos(8),
+            Posit::from(2),
+            Posit::maxpos(8),
+        ]
+    );
+    assert!(Posit::nar() < -Posit::maxpos(64));
+    assert_eq!(Posit::nar().cmp(&Posit::nar()), cmp::Ordering::Equal);
+}
+
+#[test]
+fn hash_ignores_trailing_zeros() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let short = Posit::from(1.5);
+    let mut long = short.clone();
+    long.bits.extend(bitvec![0; 13]);
+    // The bit past the end stays in storage
+    let mut truncated = posit!(0b0_10_00_11);
+    truncated.truncate(6);
+
+    let mut map = HashMap::new();
+    map.insert(short.clone(), 1);
+    map.insert(long, 2);
+    map.insert(truncated, 3);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map[&short], 3);
+
+    let tree: BTreeMap<_, _> = (-3..=3).map(|i| (Posit::from(i), i)).collect();
+    assert_eq!(tree.keys().next(), Some(&Posit::from(-3)));
+    assert_eq!(tree[&Posit::zero()], 0);
+}
+
+#[test]
+fn min_max_clamp() {
+    let (one, two) = (Posit::one(), Posit::from(2));
+    assert_eq!(one.clone().max(two.clone()), two);
+    assert_eq!(one.clone().min(two.clone()), one);
+    assert_eq!(Posit::nar().max(one.clone()), one);
+    assert_eq!(Posit::nar().min(one.clone()), Posit::nar());
+    assert_eq!(Posit::from(5).clamp(one.clone(), two.clone()), two);
+    assert_eq!(Posit::from(-5).clamp(one.clone(), two), one);
+}
+
+#[test]
+fn next_up_down() {
+    // Every posit of 8 bits in order, wrapping around from maxpos to NaR
+    let mut p = Posit::nar();
+    for i in 0..256u32 {
+        let bits = (0x80 + i) as u8;
+        assert_eq!(p, Posit::from(P8::from_bits(bits)), "{:#x}", bits);
+        let next = p.next_up(8);
+        assert_eq!(next.next_down(8), p);
+        p = next;
+    }
+    assert_eq!(p, Posit::nar());
+
+    // Values between the posits of the width step to the neighbour on their side
+    let x = Posit::from(1.1);
+    assert_eq!(x.next_up(8), Posit::from(1.125));
+    assert_eq!(x.next_down(8), Posit::one());
+    assert_eq!((-&x).next_up(8), -Posit::one());
+    assert_eq!(Posit::minpos(16).next_down(8), Posit::zero());
+    assert_eq!((-Posit::minpos(16)).next_up(8), Posit::zero());
+    assert_eq!(Posit::maxpos(16).next_up(8), Posit::nar());
+    assert_eq!(Posit::zero().next_down(32), -Posit::minpos(32));
+}
+
+#[test]
+fn fused_operations() {
+    // 1.125^2 rounds to 1.25 in 8 bits, which would cancel with the addend
+    let x = Posit::from(1.125);
+    assert_eq!(
+        x.clone().mul_add_rounded(x.clone(), Posit::from(-1.25), 8),
+        Posit::from(0.015625)
+    );
+    assert_eq!(
+        x.clone().mul_rounded(x.clone(), 8).add_rounded(Posit::from(-1.25), 8),
+        Posit::zero()
+    );
+    assert_eq!(x.clone().mul_add_rounded(Posit::nar(), Posit::one(), 8), Posit::nar());
+    let cancelled = with_precision(8, || x.clone().mul_add(x.clone(), Posit::from(-1.25)));
+    assert_eq!(cancelled, Posit::from(0.015625));
+    let down = PositContext { nbits: Some(8), rounding: Rounding::Down, ..PositContext::default() };
+    let up = PositContext { rounding: Rounding::Up, ..down };
+    assert_eq!(down.scope(|| x.clone().mul_add(x.clone(), Posit::one())), Posit::from(2.25));
+    assert_eq!(up.scope(|| x.clone().mul_add(x.clone(), Posit::one())), Posit::from(2.5));
+    assert_eq!(x.clone().mul_add(x.clone(), Posit::one()), Posit::from(2.265625));
+
+    let big = Posit::from(1e30);
+    let values = [big.clone(), Posit::one(), -big.clone()];
+    assert_eq!(Posit::fsum(&values, 16), Posit::one());
+    assert_eq!(Posit::fsum(&[], 16), Posit::zero());
+    let ones = [Posit::one(), Posit::one(), Posit::one()];
+    assert_eq!(Posit::fdp(&values, &ones, 16), Posit::one());
+    let mut rounded = big + Posit::one();
+    rounded.round_to(16);
+    assert_eq!(Posit::fdp(&values, &ones[..2], 16), rounded);
 }
@@ -0,0 +1,183 @@
+use std::fmt;
+use std::ops::*;
+
+use crate::Posit;
+
+/// Closed interval `[lo, hi]` of posits for verified computation.
+///
+/// Exact arithmetic keeps the bounds exact, while the rounded operations round the lower bound
+/// toward -∞ and the upper one toward +∞, so that the result always encloses the exact one.
+/// A NaR bound makes the entire interval, which holds every posit and NaR: it results from NaR,
+/// from division by an interval containing zero and from bounds rounding past maxpos.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<T = Posit> {
+    lo: T,
+    hi: T,
+}
+
+impl<const ES: usize> Interval<Posit<ES>> {
+    /// Interval from `lo` to `hi`, or the entire interval if either is NaR.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo` is above `hi`.
+    pub fn new(lo: Posit<ES>, hi: Posit<ES>) -> Self {
+        if lo.is_nar() || hi.is_nar() {
+            return Self::entire();
+        }
+        assert!(lo <= hi, "lower bound {} above upper bound {}", lo, hi);
+        Interval { lo, hi }
+    }
+
+    pub fn entire() -> Self {
+        Interval {
+            lo: Posit::nar(),
+            hi: Posit::nar(),
+        }
+    }
+
+    pub fn is_entire(&self) -> bool {
+        self.lo.is_nar()
+    }
+
+    /// Lower bound, NaR for the entire interval.
+    pub fn lo(&self) -> &Posit<ES> {
+        &self.lo
+    }
+
+    /// Upper bound, NaR for the entire interval.
+    pub fn hi(&self) -> &Posit<ES> {
+        &self.hi
+    }
+
+    pub fn contains(&self, x: &Posit<ES>) -> bool {
+        self.is_entire() || (self.lo <= *x && *x <= self.hi)
+    }
+
+    /// Exact distance between the bounds, NaR for the entire interval.
+    pub fn width(&self) -> Posit<ES> {
+        self.hi.add_signed(&self.lo, true)
+    }
+
+    /// Rounds the bounds outward to `nbits` bits.
+    pub fn round_to(&mut self, nbits: usize) {
+        self.lo.round_directed(nbits, false);
+        self.hi.round_directed(nbits, true);
+        if self.lo.is_nar() || self.hi.is_nar() {
+            *self = Self::entire();
+        }
+    }
+
+    pub fn add_rounded(self, rhs: Self, nbits: usize) -> Self {
+        Self::new(
+            self.lo.add_rounded_down(rhs.lo, nbits),
+            self.hi.add_rounded_up(rhs.hi, nbits),
+        )
+    }
+
+    pub fn sub_rounded(self, rhs: Self, nbits: usize) -> Self {
+        Self::new(
+            self.lo.sub_rounded_down(rhs.hi, nbits),
+            self.hi.sub_rounded_up(rhs.lo, nbits),
+        )
+    }
+
+    pub fn mul_rounded(self, rhs: Self, nbits: usize) -> Self {
+        self.hull(&rhs, |l, r, up| {
+            if up {
+                l.clone().mul_rounded_up(r.clone(), nbits)
+            } else {
+                l.clone().mul_rounded_down(r.clone(), nbits)
+            }
+        })
+    }
+
+    /// Quotient rounded outward to `nbits` bits, the entire interval if the divisor contains
+    /// zero.
+    pub fn div_rounded(self, rhs: Self, nbits: usize) -> Self {
+        if rhs.contains(&Posit::zero()) {
+            return Self::entire();
+        }
+        self.hull(&rhs, |l, r, up| {
+            if up {
+                l.clone().div_rounded_up(r.clone(), nbits)
+            } else {
+                l.clone().div_rounded_down(r.clone(), nbits)
+            }
+        })
+    }
+
+    /// Smallest interval holding `f` of the pairs of bounds, which rounds down for the lower
+    /// bound and up for the upper one.
+    fn hull(&self, rhs: &Self, f: impl Fn(&Posit<ES>, &Posit<ES>, bool) -> Posit<ES>) -> Self {
+        if self.is_entire() || rhs.is_entire() {
+            return Self::entire();
+        }
+        let corners = [
+            (&self.lo, &rhs.lo),
+            (&self.lo, &rhs.hi),
+            (&self.hi, &rhs.lo),
+            (&self.hi, &rhs.hi),
+        ];
+        let lower = corners.iter().map(|(l, r)| f(l, r, false));
+        let upper = corners.iter().map(|(l, r)| f(l, r, true));
+        // NaR is the lowest posit, so it has to be looked for among the upper bounds
+        if upper.clone().any(|p| p.is_nar()) {
+            return Self::entire();
+        }
+        Self::new(lower.min().unwrap(), upper.max().unwrap())
+    }
+}
+
+impl<const ES: usize> From<Posit<ES>> for Interval<Posit<ES>> {
+    fn from(p: Posit<ES>) -> Self {
+        Interval::new(p.clone(), p)
+    }
+}
+
+impl<const ES: usize> Neg for Interval<Posit<ES>> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Interval {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+impl<const ES: usize> Add for Interval<Posit<ES>> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Interval::new(
+            self.lo.add_signed(&rhs.lo, false),
+            self.hi.add_signed(&rhs.hi, false),
+        )
+    }
+}
+
+impl<const ES: usize> Sub for Interval<Posit<ES>> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const ES: usize> Mul for Interval<Posit<ES>> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.hull(&rhs, |l, r, _| l.multiply(r))
+    }
+}
+
+impl<const ES: usize> fmt::Display for Interval<Posit<ES>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_entire() {
+            return f.write_str("entire");
+        }
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
@@ -1,13 +1,22 @@
 use std::cmp;
-use std::mem;
+use std::hash::{Hash, Hasher};
 use std::ops::*;
 
 use bitvec::prelude::*;
 
-const ES: usize = 2;
-
+use crate::natural::{read_word, write_word, Natural};
+
+/// A posit of arbitrary length with `ES` exponent bits.
+///
+/// The bits are stored in sign-magnitude form: the first bit is the sign, the rest is the
+/// regime, exponent and fraction of the absolute value. Missing trailing bits are zeros.
+///
+/// Posits are totally ordered like two's complement integers: NaR is below every real value.
+///
+/// The bits make posits `Clone` but not `Copy`, which `num_traits::Float` requires, so generic
+/// floating point code takes [`Real`] to run on them.
 #[derive(Clone, Debug)]
-pub struct Posit {
+pub struct Posit<const ES: usize = 2> {
     pub bits: BitVec,
 }
 
@@ -18,65 +27,225 @@ struct Regime {
 }
 
 impl Regime {
-    fn bits(&self) -> usize {
+    /// Regime with the run-length value `k`.
+    fn new(k: isize) -> Self {
+        Regime {
+            is_negative: k < 0,
+            value: k.unsigned_abs(),
+        }
+    }
+
+    /// Signed run-length value `k` of the regime.
+    fn k(self) -> isize {
+        if self.is_negative {
+            -(self.value as isize)
+        } else {
+            self.value as isize
+        }
+    }
+
+    /// Splits a binary scale into a regime and an exponent of `es` bits.
+    fn from_scale(scale: isize, es: usize) -> (Self, usize) {
+        (Regime::new(scale >> es), (scale & ((1 << es) - 1)) as usize)
+    }
+
+    fn bits(self) -> usize {
         self.value + if self.is_negative { 0 } else { 1 } + 1
     }
 
-    fn to_bitvec(&self) -> BitVec {
-        let mut bits = BitVec::new();
+    /// Appends the regime bits to `bits`.
+    fn push_to(self, bits: &mut BitVec) {
+        for _ in 0..self.value {
+            bits.push(!self.is_negative);
+        }
         if self.is_negative {
-            for _ in 0..self.value {
-                bits.push(false);
-            }
             bits.push(true);
         } else {
-            for _ in 0..self.value {
-                bits.push(true);
-            }
             bits.push(true);
             bits.push(false);
         }
-        bits
     }
 }
 
-impl Neg for Regime {
-    type Output = Self;
-
-    fn neg(self) -> Self::Output {
-        Regime {
-            is_negative: !self.is_negative && self.value != 0,
-            value: self.value,
-        }
-    }
+/// Fields of a posit other than zero and NaR, as returned by [`Posit::decode`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded<const ES: usize = 2> {
+    /// Whether the posit is negative.
+    pub sign: bool,
+    /// Signed run-length value `k` of the regime.
+    pub regime: i64,
+    /// Exponent, missing bits read as zeros.
+    pub exponent: usize,
+    /// Fraction bits after the hidden bit.
+    pub fraction: BitVec,
 }
 
-impl Add for Regime {
-    type Output = Self;
+impl<const ES: usize> Decoded<ES> {
+    /// Binary scale of the value, `regime * 2^ES + exponent`.
+    pub fn scale(&self) -> i64 {
+        (self.regime << ES) + self.exponent as i64
+    }
 
-    fn add(mut self, mut other: Self) -> Self::Output {
-        if self.is_negative == other.is_negative {
-            Regime {
-                is_negative: self.is_negative,
-                value: self.value.saturating_add(other.value),
-            }
-        } else {
-            if other.value > self.value {
-                mem::swap(&mut self, &mut other);
-            }
-            Regime {
-                is_negative: self.is_negative,
-                value: self.value.saturating_sub(other.value),
-            }
-        }
+    /// Posit with these fields. An exponent of `ES` bits or more carries into the regime.
+    pub fn encode(&self) -> Posit<ES> {
+        Posit::from_scale(self.sign, self.scale(), &self.fraction)
     }
 }
 
-impl Posit {
+impl<const ES: usize> Posit<ES> {
     pub fn truncate(&mut self, len: usize) {
         self.bits.truncate(len)
     }
 
+    /// Rounds the posit to `nbits` bits to the nearest value, ties to even.
+    ///
+    /// As the posit standard requires, nonzero values never round to zero and real values never
+    /// round to NaR: they saturate to minpos and maxpos of the target length instead.
+    pub fn round_to(&mut self, nbits: usize) {
+        assert!(nbits >= 2, "posits have at least 2 bits");
+        if self.bits.len() <= nbits || self.is_zero() || self.is_nar() {
+            return;
+        }
+        let guard = self.bits[nbits];
+        let sticky = self.bits[nbits + 1..].any();
+        self.bits.truncate(nbits);
+
+        let mut magnitude: BitVec = self.bits[1..].iter().collect();
+        if magnitude.not_any() {
+            // Below minpos
+            magnitude.set(nbits - 2, true);
+        } else if guard && (sticky || magnitude[nbits - 2]) && !magnitude.all() {
+            // May carry into the exponent and the regime, but never past maxpos
+            magnitude += bitvec![1];
+        }
+        self.bits.truncate(1);
+        self.bits.extend(magnitude);
+        self.prune();
+    }
+
+    pub fn add_rounded(self, rhs: Self, nbits: usize) -> Self {
+        let mut result = self.add_signed(&rhs, false);
+        result.round_to(nbits);
+        result
+    }
+
+    pub fn sub_rounded(self, rhs: Self, nbits: usize) -> Self {
+        let mut result = self.add_signed(&rhs, true);
+        result.round_to(nbits);
+        result
+    }
+
+    pub fn mul_rounded(self, rhs: Self, nbits: usize) -> Self {
+        let mut result = self.multiply(&rhs);
+        result.round_to(nbits);
+        result
+    }
+
+    pub fn div_rounded(self, rhs: Self, nbits: usize) -> Self {
+        let (result, inexact) = self.divide(&rhs, Some(nbits));
+        result.round_inexact(nbits, inexact)
+    }
+
+    /// Sum rounded to `nbits` bits toward -∞.
+    ///
+    /// Directed rounding does not saturate like [`round_to`](Self::round_to): results past
+    /// maxpos in the direction of rounding give NaR, which stands for an unbounded result, and
+    /// results between zero and minpos may round to zero.
+    pub fn add_rounded_down(self, rhs: Self, nbits: usize) -> Self {
+        self.add_signed(&rhs, false).round_toward(nbits, false)
+    }
+
+    /// Sum rounded to `nbits` bits toward +∞.
+    pub fn add_rounded_up(self, rhs: Self, nbits: usize) -> Self {
+        self.add_signed(&rhs, false).round_toward(nbits, true)
+    }
+
+    /// Difference rounded to `nbits` bits toward -∞.
+    pub fn sub_rounded_down(self, rhs: Self, nbits: usize) -> Self {
+        self.add_signed(&rhs, true).round_toward(nbits, false)
+    }
+
+    /// Difference rounded to `nbits` bits toward +∞.
+    pub fn sub_rounded_up(self, rhs: Self, nbits: usize) -> Self {
+        self.add_signed(&rhs, true).round_toward(nbits, true)
+    }
+
+    /// Product rounded to `nbits` bits toward -∞.
+    pub fn mul_rounded_down(self, rhs: Self, nbits: usize) -> Self {
+        self.multiply(&rhs).round_toward(nbits, false)
+    }
+
+    /// Product rounded to `nbits` bits toward +∞.
+    pub fn mul_rounded_up(self, rhs: Self, nbits: usize) -> Self {
+        self.multiply(&rhs).round_toward(nbits, true)
+    }
+
+    /// Quotient rounded to `nbits` bits toward -∞.
+    pub fn div_rounded_down(self, rhs: Self, nbits: usize) -> Self {
+        self.div_directed(&rhs, nbits, false).0
+    }
+
+    /// Quotient rounded to `nbits` bits toward +∞.
+    pub fn div_rounded_up(self, rhs: Self, nbits: usize) -> Self {
+        self.div_directed(&rhs, nbits, true).0
+    }
+
+    /// Rounds an exact result to `nbits` bits toward +∞ if `up`, toward -∞ otherwise.
+    fn round_toward(mut self, nbits: usize, up: bool) -> Self {
+        self.round_directed(nbits, up);
+        self
+    }
+
+    /// Rounds a truncated result to `nbits` bits. `inexact` tells whether nonzero bits were
+    /// lost in the truncation, the posit must hold at least `nbits + 1` correct bits then.
+    fn round_inexact(mut self, nbits: usize, inexact: bool) -> Self {
+        if inexact {
+            self.mark_inexact(nbits);
+        }
+        self.round_to(nbits);
+        self
+    }
+
+    /// Marks the bits lost by truncating a result past the guard bit of `nbits` bits, so
+    /// that they are neither taken for a tie nor for an exact value.
+    fn mark_inexact(&mut self, nbits: usize) {
+        let len = self.bits.len().max(nbits + 1);
+        self.bits.resize(len, false);
+        self.bits.push(true);
+    }
+
+    /// Rounds the posit to `nbits` bits toward +∞ if `up`, toward -∞ otherwise, and tells
+    /// whether its value changed.
+    ///
+    /// Unlike [`round_to`](Self::round_to), this leaves the dynamic range as
+    /// [`next_up`](Self::next_up) and [`next_down`](Self::next_down) do: values beyond maxpos
+    /// round outward to NaR and values between zero and minpos round inward to zero.
+    pub(crate) fn round_directed(&mut self, nbits: usize, up: bool) -> bool {
+        assert!(nbits >= 2, "posits have at least 2 bits");
+        if self.bits.len() <= nbits || self.bits[nbits..].not_any() {
+            self.bits.truncate(nbits);
+            self.prune();
+            return false;
+        }
+        *self = if up {
+            self.next_up(nbits)
+        } else {
+            self.next_down(nbits)
+        };
+        true
+    }
+
+    /// Quotient rounded to `nbits` bits toward +∞ if `up`, toward -∞ otherwise, and whether
+    /// it is inexact.
+    pub(crate) fn div_directed(&self, rhs: &Self, nbits: usize, up: bool) -> (Self, bool) {
+        let (mut result, inexact) = self.divide(rhs, Some(nbits));
+        if inexact {
+            result.mark_inexact(nbits);
+        }
+        let changed = result.round_directed(nbits, up);
+        (result, inexact || changed)
+    }
+
     pub fn nar() -> Self {
         Posit { bits: bitvec![1] }
     }
@@ -91,13 +260,71 @@ impl Posit {
         }
     }
 
+    /// Largest positive posit of `nbits` bits.
+    pub fn maxpos(nbits: usize) -> Self {
+        let mut bits = bitvec![0];
+        bits.extend(bitvec![1; nbits - 1]);
+        Posit { bits }
+    }
+
+    /// Smallest positive posit of `nbits` bits.
+    pub fn minpos(nbits: usize) -> Self {
+        let mut bits = bitvec![0; nbits - 1];
+        bits.push(true);
+        Posit { bits }
+    }
+
+    /// Smallest posit of `nbits` bits above `self`. The successor of maxpos is NaR and the one
+    /// of NaR is -maxpos, as on the projective circle of the posits.
+    pub fn next_up(&self, nbits: usize) -> Self {
+        assert!(nbits >= 2, "posits have at least 2 bits");
+        if self.is_nar() {
+            return -Self::maxpos(nbits);
+        }
+        let len = self.bits.len().min(nbits);
+        let magnitude = &Natural::from_bit_range(self.bits.as_slice(), 1.min(len), len)
+            << (nbits - len);
+        let one = Natural::power_of_two(0);
+        let magnitude = if !self.is_negative() {
+            let next = &magnitude + &one;
+            if next.bits() >= nbits {
+                return Self::nar();
+            }
+            next
+        } else if self.bits.len() <= nbits || self.bits[nbits..].not_any() {
+            &magnitude - &one
+        } else {
+            // Truncating the magnitude rounds up already
+            magnitude
+        };
+
+        let mut bytes = vec![0; nbits.div_ceil(8)];
+        write_word(&mut bytes, 0, self.is_negative() as u64, 1);
+        magnitude.write_bits(nbits - 1, &mut bytes, 1);
+        let result = Self::from_bytes(bytes);
+        // Negative zero
+        if result.is_nar() {
+            Self::zero()
+        } else {
+            result
+        }
+    }
+
+    /// Largest posit of `nbits` bits below `self`. The predecessor of -maxpos is NaR and the
+    /// one of NaR is maxpos, as on the projective circle of the posits.
+    pub fn next_down(&self, nbits: usize) -> Self {
+        -(-self).next_up(nbits)
+    }
+
+    /// Power by repeated squaring, exact unless the current [`PositContext`] rounds the
+    /// products.
     pub fn pow(mut self, mut i: usize) -> Self {
-        let mut res = Posit::one();
+        let mut res = Self::one();
         while i > 0 {
             if i % 2 == 1 {
-                res = res * self.clone();
+                res *= &self;
             }
-            self = self.clone() * self;
+            self = &self * &self;
             i /= 2;
         }
         res
@@ -113,266 +340,249 @@ impl Posit {
         self.bits.get(0).unwrap_or(false)
     }
 
+    pub fn is_zero(&self) -> bool {
+        self.bits.not_any()
+    }
+
+    pub fn is_nar(&self) -> bool {
+        self.is_negative() && self.bits.iter().skip(1).all(|i| !i)
+    }
+
     fn regime(&self) -> Regime {
         let is_negative = !self.bits[1];
-        let length = self
-            .bits
-            .iter()
-            .skip(1)
-            .take_while(|&i| i != is_negative)
-            .count();
+        let (bytes, len) = (self.bits.as_slice(), self.bits.len());
+        // Count the run a word at a time, the missing bits end a run of ones
+        let mut length = 0;
+        loop {
+            let word = read_word(bytes, len, 1 + length);
+            let run = if is_negative { word } else { !word }.leading_zeros() as usize;
+            length += run;
+            if run < 64 || 1 + length >= len {
+                break;
+            }
+        }
+        let length = length.min(len - 1);
         let value = if is_negative { length } else { length - 1 };
         Regime { is_negative, value }
     }
 
-    pub fn abs(self) -> Self {
-        if self.is_negative() {
-            -self
-        } else {
-            self
+    /// Sign, regime, exponent and fraction of the posit, `None` for zero and NaR.
+    pub fn decode(&self) -> Option<Decoded<ES>> {
+        if self.is_zero() || self.is_nar() {
+            return None;
         }
+        let regime = self.regime();
+        let mut bits = self.bits.iter().skip(1 + regime.bits());
+        let mut exponent = 0;
+        for _ in 0..ES {
+            exponent = exponent * 2 + bits.next().unwrap_or(false) as usize;
+        }
+        Some(Decoded {
+            sign: self.is_negative(),
+            regime: regime.k() as i64,
+            exponent,
+            fraction: bits.collect(),
+        })
     }
-}
 
-impl cmp::PartialEq<Posit> for Posit {
-    fn eq(&self, other: &Posit) -> bool {
-        let mut l_iter = self.bits.iter();
-        let mut r_iter = other.bits.iter();
-        loop {
-            let l = l_iter.next();
-            let r = r_iter.next();
-            if l.is_none() && r.is_none() {
-                return true;
-            }
-            let l = l.unwrap_or(false);
-            let r = r.unwrap_or(false);
-            if l != r {
-                return false;
-            }
-        }
+    /// Fields of a posit other than zero and NaR.
+    fn decoded(&self) -> Decoded<ES> {
+        self.decode().expect("zero and NaR have no fields")
     }
-}
 
-impl cmp::PartialOrd<Posit> for Posit {
-    fn partial_cmp(&self, other: &Posit) -> Option<cmp::Ordering> {
-        let l_sign = self.is_negative();
-        let r_sign = other.is_negative();
-        if l_sign && !r_sign {
-            return Some(cmp::Ordering::Less);
-        }
-        if !l_sign && r_sign {
-            return Some(cmp::Ordering::Greater);
-        }
-        let mut l_iter = self.bits.iter().skip(1);
-        let mut r_iter = other.bits.iter().skip(1);
-        loop {
-            let l = l_iter.next();
-            let r = r_iter.next();
-            if l.is_none() && r.is_none() {
-                return Some(cmp::Ordering::Equal);
-            }
-            let l = l.unwrap_or(false) != l_sign;
-            let r = r.unwrap_or(false) != r_sign;
-            if l > r {
-                return Some(cmp::Ordering::Greater);
-            }
-            if r > l {
-                return Some(cmp::Ordering::Less);
+    /// Binary scale and fraction bits of a posit other than zero and NaR.
+    fn scale_and_fraction(&self) -> (isize, BitVec) {
+        let decoded = self.decoded();
+        (decoded.scale() as isize, decoded.fraction)
+    }
+
+    /// Binary scale of a posit other than zero and NaR, and the index of its first fraction
+    /// bit.
+    fn scale_and_start(&self) -> (i64, usize) {
+        let regime = self.regime();
+        let len = self.bits.len();
+        let start = (1 + regime.bits()).min(len);
+        // Missing exponent bits read as zeros
+        let exponent = match ES {
+            0 => 0,
+            _ => read_word(self.bits.as_slice(), len, start) >> (64 - ES),
+        };
+        let scale = ((regime.k() as i64) << ES) + exponent as i64;
+        (scale, (start + ES).min(len))
+    }
+
+    /// Binary scale of a posit other than zero and NaR, its significand `1.frac` as an integer
+    /// and the number of fraction bits after the hidden one.
+    fn unpack(&self) -> (i64, Natural, usize) {
+        let (scale, start) = self.scale_and_start();
+        let len = self.bits.len();
+        let significand = &Natural::from_bit_range(self.bits.as_slice(), start, len)
+            + &Natural::power_of_two(len - start);
+        (scale, significand, len - start)
+    }
+
+    /// Builds a posit from its sign, binary scale and significand, whose hidden bit is its
+    /// most significant one, followed by `frac_len` fraction bits.
+    fn pack(sign: bool, scale: i64, significand: &Natural, frac_len: usize) -> Self {
+        let (regime, exp) = Regime::from_scale(scale as isize, ES);
+        let frac_start = 1 + regime.bits() + ES;
+        let mut bytes = vec![0; (frac_start + frac_len).div_ceil(8)];
+        write_word(&mut bytes, 0, sign as u64, 1);
+        if regime.is_negative {
+            write_word(&mut bytes, 1 + regime.value, 1, 1);
+        } else {
+            let ones = regime.value + 1;
+            for start in (1..=ones).step_by(64) {
+                write_word(&mut bytes, start, u64::MAX, (ones + 1 - start).min(64));
             }
         }
+        write_word(&mut bytes, frac_start - ES, exp as u64, ES);
+        significand.write_bits(frac_len, &mut bytes, frac_start);
+        Self::from_bytes(bytes)
     }
-}
 
-impl Mul<Posit> for Posit {
-    type Output = Self;
+    /// Posit with the bits stored in `bytes`, without their trailing zeros.
+    fn from_bytes(bytes: Vec<u8>) -> Self {
+        let len = bytes
+            .iter()
+            .rposition(|&byte| byte != 0)
+            .map_or(0, |i| (i + 1) * 8 - bytes[i].trailing_zeros() as usize);
+        let mut bits = BitVec::from_vec(bytes);
+        bits.truncate(len);
+        Posit { bits }
+    }
 
-    fn mul(self, rhs: Posit) -> Self::Output {
-        if self == Self::zero() || rhs == Self::zero() {
-            return Self::zero();
+    /// Multiplies the posit by `2^n` exactly.
+    fn mul_pow2(self, n: isize) -> Self {
+        if self.is_zero() || self.is_nar() {
+            return self;
         }
-        if self == Self::nar() || rhs == Self::nar() {
-            return Self::nar();
+        let (scale, significand, frac_len) = self.unpack();
+        Self::pack(self.is_negative(), scale + n as i64, &significand, frac_len)
+    }
+
+    pub fn abs(self) -> Self {
+        if self.is_negative() {
+            -self
+        } else {
+            self
         }
+    }
 
-        let sign = self.bits[0] != rhs.bits[0];
+    /// Compares absolute values of two posits, ignoring the signs.
+    fn cmp_abs(&self, other: &Self) -> cmp::Ordering {
+        cmp_bits(&self.bits, &other.bits, 1)
+    }
 
-        // Regimes
-        let l_regime = self.regime();
-        let r_regime = rhs.regime();
-        let mut o_regime = l_regime + r_regime;
+    /// Divides `self` by `rhs` computing `frac_bits` bits of the fraction, or `l_fs + r_fs + 16`
+    /// bits if not given. Returns the quotient and whether it is inexact.
+    fn divide(&self, rhs: &Self, frac_bits: Option<usize>) -> (Self, bool) {
+        if self.is_nar() || rhs.is_nar() || rhs.is_zero() {
+            return (Self::nar(), false);
+        }
+        if self.is_zero() {
+            return (Self::zero(), false);
+        }
 
-        // Bits
-        let mut l_bits = self.bits.into_iter().skip(1).skip(l_regime.bits());
-        let mut r_bits = rhs.bits.into_iter().skip(1).skip(r_regime.bits());
+        let ((l_scale, l_sig, l_len), (r_scale, r_sig, r_len)) = (self.unpack(), rhs.unpack());
+        let sign = self.is_negative() != rhs.is_negative();
+        let mut scale = l_scale - r_scale;
 
-        // Exponents
-        let mut l_exp = 0;
-        let mut r_exp = 0;
-        for _ in 0..ES {
-            l_exp = l_exp * 2 + l_bits.next().unwrap_or(false) as usize;
-            r_exp = r_exp * 2 + r_bits.next().unwrap_or(false) as usize;
-        }
-        let mut o_exp = l_exp + r_exp;
-
-        // Fractions
-        let l_frac: BitVec = l_bits.collect();
-        let r_frac: BitVec = r_bits.collect();
-        let l_fs = l_frac.len();
-        let r_fs = r_frac.len();
-        let mut o_frac: BitVec = {
-            let mut o_frac = l_frac.clone();
-            o_frac.extend(bitvec![0; r_fs]);
-
-            let mut tmp = r_frac.clone();
-            tmp.extend(bitvec![0; l_fs]);
-            o_frac += tmp; // makes bitvec bigger by adding bits at the start if necessary
-
-            // Add f1*f2
-            let mut tmp = l_frac.clone();
-            for flag in r_frac.iter().rev() {
-                if flag {
-                    o_frac += tmp.clone();
-                }
-                tmp.push(false);
-            }
+        // Significands aligned to the same number of fraction bits
+        let len = l_len.max(r_len);
+        let dividend = &l_sig << (len - l_len);
+        let divisor = &r_sig << (len - r_len);
 
-            // Add leading one
-            let mut kek = bitvec![1];
-            kek.extend(bitvec![0; l_fs + r_fs]);
-            o_frac += kek;
-            o_frac
+        // Normalize the quotient into [1, 2)
+        let normalize = if dividend < divisor {
+            scale -= 1;
+            1
+        } else {
+            0
         };
 
-        // Carry fraction overflow to exponent
-        if o_frac.len() > (l_fs + r_fs + 1) {
-            o_exp += o_frac.len() - l_fs - r_fs - 1;
-        }
-        // Remove leading one
-        o_frac <<= 1;
+        // The quotient has the hidden bit and `frac_bits` fraction bits
+        let frac_bits = frac_bits.unwrap_or(l_len + r_len + 16);
+        let (quotient, rem) = (&dividend << (frac_bits + normalize)).div_rem(&divisor);
+        let inexact = !rem.is_zero();
+        (Self::pack(sign, scale, &quotient, frac_bits), inexact)
+    }
 
-        // Carry exponent to regime if overflowed
-        while o_exp >= (1 << ES) {
-            o_regime = o_regime
-                + Regime {
-                    is_negative: false,
-                    value: 1,
-                };
-            o_exp -= 1 << ES;
+    /// Product of two posits, exact.
+    fn multiply(&self, rhs: &Self) -> Self {
+        if self.is_nar() || rhs.is_nar() {
+            return Self::nar();
         }
-
-        // Combine the result
-        let mut result = BitVec::new();
-        result.push(sign);
-        result.extend(o_regime.to_bitvec());
-        for i in 0..ES {
-            result.push((o_exp & (1 << (ES - i - 1))) != 0);
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
         }
-        result.extend(o_frac);
-
-        let mut result = Posit { bits: result };
-        result.prune();
-        result
-    }
-}
 
-impl Neg for Posit {
-    type Output = Self;
+        let ((l_scale, l_sig, l_len), (r_scale, r_sig, r_len)) = (self.unpack(), rhs.unpack());
+        let sign = self.is_negative() != rhs.is_negative();
+        let mut scale = l_scale + r_scale;
 
-    fn neg(mut self) -> Self::Output {
-        if let Some(a) = self.bits.get(0) {
-            self.bits.set(0, !a);
-        } else {
-            self.bits.push(true);
+        // The product of the significands is in [1, 4)
+        let product = &l_sig * &r_sig;
+        let mut frac_len = l_len + r_len;
+        if product.bits() > frac_len + 1 {
+            scale += 1;
+            frac_len += 1;
         }
-        Posit { bits: self.bits }
+        Self::pack(sign, scale, &product, frac_len)
     }
-}
-
-impl Add<Posit> for Posit {
-    type Output = Self;
 
-    fn add(mut self, mut rhs: Posit) -> Self::Output {
-        if self.is_negative() != rhs.is_negative() {
-            return self - (-rhs);
-        }
-        if self == Posit::zero() {
-            return rhs;
-        }
-        if rhs == Posit::zero() {
-            return self;
+    /// Sum of two posits, or their difference if `subtract`, exact.
+    fn add_signed(&self, rhs: &Self, subtract: bool) -> Self {
+        if self.is_nar() || rhs.is_nar() {
+            return Self::nar();
         }
-        if self == Posit::nar() || rhs == Posit::nar() {
-            return Posit::nar();
+        if rhs.is_zero() {
+            return self.clone();
         }
-        if self < rhs {
-            mem::swap(&mut self, &mut rhs);
+        if self.is_zero() {
+            return if subtract { -rhs.clone() } else { rhs.clone() };
         }
 
-        let sign = self.is_negative();
-
-        // Regime
-        let l_regime = self.regime();
-        let r_regime = rhs.regime();
-        let regime = l_regime + (-r_regime);
+        let rhs_sign = rhs.is_negative() != subtract;
+        let subtract = self.is_negative() != rhs_sign;
+        let (big, small, sign) = match self.cmp_abs(rhs) {
+            cmp::Ordering::Equal if subtract => return Self::zero(),
+            cmp::Ordering::Less => (rhs, self, rhs_sign),
+            _ => (self, rhs, self.is_negative()),
+        };
 
-        // Bits
-        let mut l_bits = self.bits.into_iter().skip(1).skip(l_regime.bits());
-        let mut r_bits = rhs.bits.into_iter().skip(1).skip(r_regime.bits());
+        let ((b_scale, b_sig, b_len), (s_scale, s_sig, s_len)) = (big.unpack(), small.unpack());
+        let shift = (b_scale - s_scale) as usize;
 
-        // Exponents
-        let mut l_exp = 0;
-        let mut r_exp = 0;
-        for _ in 0..ES {
-            l_exp = l_exp * 2 + l_bits.next().unwrap_or(false) as usize;
-            r_exp = r_exp * 2 + r_bits.next().unwrap_or(false) as usize;
-        }
-        //Left: 11100
-        //Было: 1101
-        //Стал: 01101
-        let shift = (regime.value << ES) + l_exp - r_exp;
-
-        // Fractions
-        let mut l_frac: BitVec = bitvec![1];
-        l_frac.extend(l_bits);
-
-        let mut r_frac: BitVec = bitvec![0; shift];
-        r_frac.push(true);
-        r_frac.extend(r_bits);
-
-        let l_fs = l_frac.len();
-        let r_fs = r_frac.len();
-        let fs = l_fs.max(r_fs);
-        if r_fs > l_fs {
-            l_frac.extend(bitvec![0; fs - l_fs]);
+        // Both significands as integers with the fraction bits of the smaller one
+        let len = b_len.max(shift + s_len);
+        let b_sig = &b_sig << (len - b_len);
+        let s_sig = &s_sig << (len - shift - s_len);
+        let sum = if subtract {
+            &b_sig - &s_sig
         } else {
-            r_frac.extend(bitvec![0; fs - r_fs]);
-        }
-        let o_frac = l_frac + r_frac;
-        let mut o_exp = l_exp;
-        let mut o_regime = l_regime;
-        if o_frac.len() > fs {
-            // Carry fraction overflow to exponent
-            o_exp += 1;
-
-            // Carry exponent to regime if overflowed
-            while o_exp >= (1 << ES) {
-                o_regime = o_regime
-                    + Regime {
-                        is_negative: false,
-                        value: 1,
-                    };
-                o_exp -= 1 << ES;
-            }
-        }
+            &b_sig + &s_sig
+        };
+
+        let top = sum.bits() - 1;
+        Self::pack(sign, b_scale + top as i64 - len as i64, &sum, top)
+    }
 
-        // Combine the result
-        let mut result = BitVec::new();
+    /// Builds a posit from its sign, binary scale and fraction without the hidden bit.
+    fn from_scale(sign: bool, scale: i64, frac: &BitSlice) -> Self {
+        let (regime, exp) = Regime::from_scale(scale as isize, ES);
+        Self::from_parts(sign, regime, exp, frac)
+    }
+
+    /// Builds a posit from its fields. `frac` holds the fraction without the hidden bit.
+    fn from_parts(sign: bool, regime: Regime, exp: usize, frac: &BitSlice) -> Self {
+        let mut result = BitVec::with_capacity(1 + regime.bits() + ES + frac.len());
         result.push(sign);
-        result.extend(o_regime.to_bitvec());
+        regime.push_to(&mut result);
         for i in 0..ES {
-            result.push((o_exp & (1 << (ES - i - 1))) != 0);
+            result.push((exp & (1 << (ES - i - 1))) != 0);
         }
-        result.extend(&o_frac[1..]);
+        result.extend(frac);
 
         let mut result = Posit { bits: result };
         result.prune();
@@ -380,253 +590,248 @@ impl Add<Posit> for Posit {
     }
 }
 
-impl Sub<Posit> for Posit {
-    type Output = Self;
+impl<const ES: usize> cmp::PartialEq<Posit<ES>> for Posit<ES> {
+    fn eq(&self, other: &Posit<ES>) -> bool {
+        cmp_bits(&self.bits, &other.bits, 0) == cmp::Ordering::Equal
+    }
+}
+
+impl<const ES: usize> Eq for Posit<ES> {}
+
+impl<const ES: usize> cmp::PartialOrd<Posit<ES>> for Posit<ES> {
+    fn partial_cmp(&self, other: &Posit<ES>) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-    fn sub(mut self, mut rhs: Posit) -> Self::Output {
-        if self.is_negative() != rhs.is_negative() {
-            return self + (-rhs);
+impl<const ES: usize> Ord for Posit<ES> {
+    fn cmp(&self, other: &Posit<ES>) -> cmp::Ordering {
+        match (self.is_nar(), other.is_nar()) {
+            (true, true) => return cmp::Ordering::Equal,
+            (true, false) => return cmp::Ordering::Less,
+            (false, true) => return cmp::Ordering::Greater,
+            (false, false) => {}
         }
-        if self == rhs {
-            return Posit::zero();
+        let l_sign = self.is_negative();
+        let r_sign = other.is_negative();
+        if l_sign != r_sign {
+            return r_sign.cmp(&l_sign);
         }
-        if self == Posit::zero() {
-            return -rhs;
+        let ordering = self.cmp_abs(other);
+        if l_sign {
+            ordering.reverse()
+        } else {
+            ordering
         }
-        if rhs == Posit::zero() {
-            return self;
+    }
+}
+
+/// Hashes the bits up to the last one, as equality ignores trailing zeros.
+impl<const ES: usize> Hash for Posit<ES> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut bytes = self.bits.as_slice().to_vec();
+        let tail = self.bits.len() % 8;
+        if let (Some(last), true) = (bytes.last_mut(), tail != 0) {
+            // Clear the stored bits past the end
+            *last &= !(u8::MAX >> tail);
         }
-        if self == Posit::nar() || rhs == Posit::nar() {
-            return Posit::nar();
+        while bytes.last() == Some(&0) {
+            bytes.pop();
         }
-        let sign = if (self < rhs) != self.is_negative() {
-            mem::swap(&mut self, &mut rhs);
-            !self.is_negative()
-        } else {
-            self.is_negative()
-        };
+        bytes.hash(state);
+    }
+}
 
-        // Regime
-        let l_regime = self.regime();
-        let r_regime = rhs.regime();
-        let regime = l_regime + (-r_regime);
+/// Compares two bit strings from bit `start` of their first byte, missing bits read as zeros.
+fn cmp_bits(l: &BitVec, r: &BitVec, start: usize) -> cmp::Ordering {
+    let (l_bytes, r_bytes) = (l.as_slice(), r.as_slice());
+    // The bytes both strings store in full compare as they are
+    let shared = l.len().min(r.len()) / 8;
+    let ordering = match shared {
+        0 => cmp::Ordering::Equal,
+        _ => (l_bytes[0] & u8::MAX >> start)
+            .cmp(&(r_bytes[0] & u8::MAX >> start))
+            .then_with(|| l_bytes[1..shared].cmp(&r_bytes[1..shared])),
+    };
+    ordering.then_with(|| {
+        ((shared * 8).max(start)..l.len().max(r.len()))
+            .step_by(64)
+            .map(|i| read_word(l_bytes, l.len(), i).cmp(&read_word(r_bytes, r.len(), i)))
+            .find(|&ordering| ordering != cmp::Ordering::Equal)
+            .unwrap_or(cmp::Ordering::Equal)
+    })
+}
 
-        // Bits
-        let mut l_bits = self.bits.into_iter().skip(1).skip(l_regime.bits());
-        let mut r_bits = rhs.bits.into_iter().skip(1).skip(r_regime.bits());
+impl<const ES: usize> Neg for Posit<ES> {
+    type Output = Self;
 
-        // Exponents
-        let mut l_exp = 0;
-        let mut r_exp = 0;
-        for _ in 0..ES {
-            l_exp = l_exp * 2 + l_bits.next().unwrap_or(false) as usize;
-            r_exp = r_exp * 2 + r_bits.next().unwrap_or(false) as usize;
+    fn neg(mut self) -> Self::Output {
+        // Zero and NaR are their own negations
+        if self.bits.iter().skip(1).all(|i| !i) {
+            return self;
         }
+        let a = self.bits[0];
+        self.bits.set(0, !a);
+        self
+    }
+}
 
-        //   11000
-        // -  1001
-        // = 01111
-        //Shift
-        let shift = (regime.value << ES) + l_exp - r_exp;
+impl<const ES: usize> Neg for &Posit<ES> {
+    type Output = Posit<ES>;
 
-        // Fractions
-        let mut l_frac: BitVec = bitvec![1, 1];
-        l_frac.extend(l_bits);
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
 
-        let mut r_frac: BitVec = bitvec![0; shift + 1];
-        r_frac.push(true);
-        r_frac.extend(r_bits);
+/// Implements an operator for all combinations of owned and borrowed operands, and its
+/// assignment form, with a function of two borrowed posits. The functions round their results
+/// as the current [`PositContext`] requires.
+macro_rules! forward_binop {
+    ($op: ident::$method: ident, $op_assign: ident::$method_assign: ident, $f: expr) => {
+        impl<const ES: usize> $op<&Posit<ES>> for &Posit<ES> {
+            type Output = Posit<ES>;
 
-        let l_fs = l_frac.len();
-        let r_fs = r_frac.len();
-        let fs = l_fs.max(r_fs);
-        if r_fs > l_fs {
-            l_frac.extend(bitvec![0; fs - l_fs]);
-        } else {
-            r_frac.extend(bitvec![0; fs - r_fs]);
-        }
-        let mut o_frac = l_frac - r_frac;
-        let mut o_exp = l_exp;
-        let mut o_regime = l_regime;
-        let mut o_exp_shift = 0;
-        if !o_frac[0] {
-            o_exp_shift += 1;
-        }
-        o_frac <<= 1;
-        while !o_frac[0] {
-            o_exp_shift += 1;
-            o_frac <<= 1;
-        }
-        while o_exp_shift > 0 {
-            if o_exp_shift >= o_exp + 1 {
-                o_exp_shift -= o_exp + 1;
-                o_exp = (1 << ES) - 1;
-                o_regime = o_regime
-                    + Regime {
-                        is_negative: true,
-                        value: 1,
-                    };
-            } else {
-                o_exp -= o_exp_shift;
-                o_exp_shift = 0;
+            fn $method(self, rhs: &Posit<ES>) -> Self::Output {
+                $f(self, rhs)
             }
         }
 
-        // Combine the result
-        let mut result = BitVec::new();
-        result.push(sign);
-        result.extend(o_regime.to_bitvec());
-        for i in 0..ES {
-            result.push((o_exp & (1 << (ES - i - 1))) != 0);
-        }
-        result.extend(&o_frac[1..]);
-
-        let mut result = Posit { bits: result };
-        result.prune();
-        result
-    }
-}
-
-impl Div<Posit> for Posit {
-    type Output = Self;
+        impl<const ES: usize> $op<Posit<ES>> for &Posit<ES> {
+            type Output = Posit<ES>;
 
-    fn div(self, rhs: Posit) -> Self::Output {
-        if rhs == Posit::nar() {
-            return Posit::zero();
-        }
-        if rhs == Posit::zero() {
-            return Posit::nar();
+            fn $method(self, rhs: Posit<ES>) -> Self::Output {
+                $f(self, &rhs)
+            }
         }
 
-        let sign = self.bits[0] != rhs.bits[0];
+        impl<const ES: usize> $op<&Posit<ES>> for Posit<ES> {
+            type Output = Posit<ES>;
 
-        // Regimes
-        let l_regime = self.regime();
-        let r_regime = rhs.regime();
-        let mut o_regime = l_regime + (-r_regime);
+            fn $method(self, rhs: &Posit<ES>) -> Self::Output {
+                $f(&self, rhs)
+            }
+        }
 
-        // Bits
-        let mut l_bits = self.bits.into_iter().skip(1).skip(l_regime.bits());
-        let mut r_bits = rhs.bits.into_iter().skip(1).skip(r_regime.bits());
+        impl<const ES: usize> $op<Posit<ES>> for Posit<ES> {
+            type Output = Posit<ES>;
 
-        // Exponents
-        let mut l_exp = 0;
-        let mut r_exp = 0;
-        for _ in 0..ES {
-            l_exp = l_exp * 2 + l_bits.next().unwrap_or(false) as usize;
-            r_exp = r_exp * 2 + r_bits.next().unwrap_or(false) as usize;
+            fn $method(self, rhs: Posit<ES>) -> Self::Output {
+                $f(&self, &rhs)
+            }
         }
-        let o_exp = if l_exp >= r_exp {
-            l_exp - r_exp
-        } else {
-            o_regime = o_regime
-                + Regime {
-                    is_negative: true,
-                    value: 1,
-                };
-            (1 << ES) + l_exp - r_exp
-        };
 
-        // Fractions
-        let mut l_frac: BitVec = l_bits.collect();
-        let r_frac: BitVec = r_bits.collect();
-        let r_fs = r_frac.len();
-        l_frac.extend(bitvec![0; r_fs + 16]);
-        let l_fs = l_frac.len();
-
-        let o_frac = {
-            let mut r_frac_p = r_frac.clone();
-            r_frac_p.extend(bitvec![0; l_fs - r_fs]);
-            let mut dividend = l_frac.clone() - r_frac_p;
-
-            let mut divisor = bitvec![1];
-            divisor.extend(bitvec![0; r_fs]);
-            divisor += r_frac.clone();
-
-            let iters = dividend.len().max(divisor.len()) - divisor.len();
-            divisor.extend(bitvec![0; iters]);
-            let mut o_fs = bitvec![];
-            for _ in 0..iters {
-                if dividend > divisor {
-                    o_fs.push(true);
-                    dividend -= divisor.clone();
-                } else {
-                    o_fs.push(false);
-                }
-                let _ = divisor.pop();
-                divisor >>= 1;
+        impl<const ES: usize> $op_assign<&Posit<ES>> for Posit<ES> {
+            fn $method_assign(&mut self, rhs: &Posit<ES>) {
+                *self = $f(self, rhs);
             }
-            o_fs
-        };
-
-        // Combine the result
-        let mut result = BitVec::new();
-        result.push(sign);
-        result.extend(o_regime.to_bitvec());
-        for i in 0..ES {
-            result.push((o_exp & (1 << (ES - i - 1))) != 0);
         }
-        result.extend(o_frac);
 
-        let mut result = Posit { bits: result };
-        result.prune();
-        result
-    }
+        impl<const ES: usize> $op_assign<Posit<ES>> for Posit<ES> {
+            fn $method_assign(&mut self, rhs: Posit<ES>) {
+                *self = $f(self, &rhs);
+            }
+        }
+    };
 }
 
-impl From<f64> for Posit {
-    fn from(f: f64) -> Self {
-        if f.is_nan() || f.is_infinite() {
-            return Posit::nar();
+forward_binop!(Add::add, AddAssign::add_assign, |l: &Posit<ES>, r| {
+    l.add_signed(r, false).in_context()
+});
+forward_binop!(Sub::sub, SubAssign::sub_assign, |l: &Posit<ES>, r| {
+    l.add_signed(r, true).in_context()
+});
+forward_binop!(Mul::mul, MulAssign::mul_assign, |l: &Posit<ES>, r| {
+    l.multiply(r).in_context()
+});
+forward_binop!(Div::div, DivAssign::div_assign, Posit::quotient_in_context);
+forward_binop!(Rem::rem, RemAssign::rem_assign, |l: &Posit<ES>, r| {
+    l.remainder(r).in_context()
+});
+
+impl<const ES: usize> Posit<ES> {
+    /// Exact remainder of the division rounded towards zero, with the sign of `self`.
+    fn remainder(&self, rhs: &Self) -> Self {
+        if self.is_nar() || rhs.is_nar() || rhs.is_zero() {
+            return Self::nar();
         }
-        if f == 0.0 {
-            return Posit::nar();
+        if self.is_zero() || self.cmp_abs(rhs) == cmp::Ordering::Less {
+            return self.clone();
         }
-        let bits = f.to_bits();
-        let sign = bits >> 63 != 0;
-        let mut exponent: i16 = ((bits >> 52) & 0x7ff) as i16;
-        let mantissa = if exponent == 0 {
-            exponent = 1;
-            (bits & 0xfffffffffffff) << 1
-        } else {
-            (bits & 0xfffffffffffff) | 0x10000000000000
-        };
-        exponent -= 1023;
-
-        let mantissa: BitVec = {
-            let mut m = BitVec::new();
-            m.extend(mantissa.as_bitslice::<BigEndian>());
-            m <<= 11;
-            let shift = m.iter().position(|i| i).unwrap_or(0);
-            m <<= shift + 1;
-            exponent += shift as i16;
-            m
-        };
-
-        let exp = exponent & ((1 << ES as i16) - 1);
-        let regime = exponent >> ES as i16;
-        let regime = Regime {
-            is_negative: regime < 0,
-            value: regime.abs() as usize,
-        };
-        let mut output = bitvec![];
-        output.push(sign);
-        output.extend(regime.to_bitvec());
-        output.push(exp & 0b10 != 0);
-        output.push(exp & 0b01 != 0);
-        output.extend(mantissa);
-
-        let mut result = Posit { bits: output };
-        result.prune();
-        result
+        // The truncated quotient only needs the bits of its integer part
+        let int_bits = self.scale_and_start().0 - rhs.scale_and_start().0 + 1;
+        let (quot, _) = self.divide(rhs, Some(int_bits as usize));
+        self.add_signed(&quot.trunc().multiply(rhs), true)
     }
 }
 
+mod algebra;
+mod context;
+mod convert;
+mod decimal;
 mod exports;
+mod fixed;
+mod interval;
+mod linalg;
+mod literal;
+mod math;
+mod natural;
+mod num;
+mod quire;
+mod softposit;
+mod unum;
+mod unum2;
+mod valid;
+
+pub use context::{with_precision, PositContext, Rounding};
+pub use convert::TryFromPositError;
+pub use decimal::ParsePositError;
+pub use fixed::{P16, P32, P64, P8};
+pub use interval::Interval;
+pub use linalg::{Cholesky, Lu, PositField, Qr};
+pub use num::Real;
+pub use quire::Quire;
+pub use unum::{TryFromUnumError, Ubound, Unum};
+pub use unum2::{LatticeError, Sorn, Unum2};
+pub use valid::Valid;
+
+/// Standard posit of the tests, so that constructors do not need the exponent size spelled out.
+#[cfg(test)]
+pub(crate) type TestPosit = Posit<2>;
 
 #[cfg(test)]
 mod tests_float;
 #[cfg(test)]
 mod tests_posit;
 #[cfg(test)]
+mod tests_fixed;
+#[cfg(test)]
+mod tests_math;
+#[cfg(test)]
+mod tests_convert;
+#[cfg(test)]
+mod tests_decimal;
+#[cfg(test)]
+mod tests_literal;
+#[cfg(test)]
+mod tests_num;
+#[cfg(test)]
+mod tests_linalg;
+#[cfg(test)]
+mod tests_natural;
+#[cfg(test)]
+mod tests_exports;
+#[cfg(test)]
+mod tests_softposit;
+#[cfg(test)]
+mod tests_valid;
+#[cfg(test)]
+mod tests_interval;
+#[cfg(test)]
+mod tests_unum;
+#[cfg(test)]
+mod tests_unum2;
+#[cfg(test)]
+mod tests_context;
+#[cfg(test)]
 mod inner_tests;
@@ -0,0 +1,293 @@
+use nalgebra::{DMatrix, DVector, RealField};
+
+use crate::{Posit, Quire, P16, P32, P64, P8};
+
+/// Fixed-width posit usable with the quire-based solvers [`Lu`], [`Cholesky`] and [`Qr`].
+///
+/// The standard [`Posit`] cannot be a matrix element, as nalgebra requires its scalars to be
+/// `Copy`.
+pub trait PositField: RealField + Ord + From<Posit> + Into<Posit> {
+    /// Width the results of the solvers are rounded to.
+    const NBITS: usize;
+}
+
+impl PositField for P8 {
+    const NBITS: usize = 8;
+}
+
+impl PositField for P16 {
+    const NBITS: usize = 16;
+}
+
+impl PositField for P32 {
+    const NBITS: usize = 32;
+}
+
+impl PositField for P64 {
+    const NBITS: usize = 64;
+}
+
+/// `c + Σ a * b` accumulated in the quire and rounded once.
+fn dot<T: PositField>(c: T, products: impl IntoIterator<Item = (T, T)>) -> T {
+    let mut quire = Quire::new();
+    quire.add_posit(&c.into());
+    for (a, b) in products {
+        quire.add_product(&a.into(), &b.into());
+    }
+    T::from(quire.to_posit(T::NBITS))
+}
+
+/// Solves `U x = y` for the upper triangle `U` of the first `n` rows of `matrix` in place,
+/// `None` if a diagonal element is zero.
+fn back_substitute<T: PositField>(matrix: &DMatrix<T>, x: &mut DVector<T>, n: usize) -> Option<()> {
+    for i in (0..n).rev() {
+        if matrix[(i, i)].is_zero() {
+            return None;
+        }
+        let residual = dot(x[i], (i + 1..n).map(|j| (-matrix[(i, j)], x[j])));
+        x[i] = residual / matrix[(i, i)];
+    }
+    Some(())
+}
+
+/// LU decomposition with partial pivoting, `P A = L U` with a unit lower triangular `L`.
+///
+/// The entries are computed in Doolittle's order, so that each one is a single dot product
+/// accumulated in the quire and rounded once.
+#[derive(Clone, Debug)]
+pub struct Lu<T: PositField> {
+    /// `L` below the diagonal, `U` on and above it
+    lu: DMatrix<T>,
+    /// Row of `A` moved to each row of `P A`
+    permutation: Vec<usize>,
+}
+
+impl<T: PositField> Lu<T> {
+    /// Decomposes a square matrix.
+    pub fn new(matrix: DMatrix<T>) -> Self {
+        assert!(
+            matrix.is_square(),
+            "LU decomposition of a non-square matrix"
+        );
+        let n = matrix.nrows();
+        let mut lu = matrix;
+        let mut permutation: Vec<usize> = (0..n).collect();
+        for k in 0..n {
+            // Column of `U` at the diagonal and the column of `L` before the division
+            for i in k..n {
+                let value = dot(lu[(i, k)], (0..k).map(|j| (-lu[(i, j)], lu[(j, k)])));
+                lu[(i, k)] = value;
+            }
+            // The first of the largest elements, NaR is below all of them
+            let pivot = (k..n).rev().max_by_key(|&i| lu[(i, k)].abs()).unwrap();
+            lu.swap_rows(k, pivot);
+            permutation.swap(k, pivot);
+
+            let diagonal = lu[(k, k)];
+            if !diagonal.is_zero() {
+                for i in k + 1..n {
+                    lu[(i, k)] /= diagonal;
+                }
+            }
+            for j in k + 1..n {
+                let value = dot(lu[(k, j)], (0..k).map(|m| (-lu[(k, m)], lu[(m, j)])));
+                lu[(k, j)] = value;
+            }
+        }
+        Lu { lu, permutation }
+    }
+
+    /// Whether the decomposed matrix has an inverse, i.e. no pivot is zero.
+    pub fn is_invertible(&self) -> bool {
+        (0..self.lu.nrows()).all(|i| !self.lu[(i, i)].is_zero())
+    }
+
+    /// Unit lower triangular factor.
+    pub fn l(&self) -> DMatrix<T> {
+        let mut l = self.lu.lower_triangle();
+        l.fill_diagonal(T::one());
+        l
+    }
+
+    /// Upper triangular factor.
+    pub fn u(&self) -> DMatrix<T> {
+        self.lu.upper_triangle()
+    }
+
+    /// Row of the decomposed matrix moved to each row of `P A`.
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// Solves `A x = b`, `None` if the matrix is singular.
+    pub fn solve(&self, b: &DVector<T>) -> Option<DVector<T>> {
+        let n = self.lu.nrows();
+        assert_eq!(b.len(), n, "right-hand side of the wrong size");
+        let mut x = DVector::from_fn(n, |i, _| b[self.permutation[i]]);
+        for i in 0..n {
+            x[i] = dot(x[i], (0..i).map(|j| (-self.lu[(i, j)], x[j])));
+        }
+        back_substitute(&self.lu, &mut x, n)?;
+        Some(x)
+    }
+}
+
+/// Cholesky decomposition `A = L L^T` of a symmetric positive-definite matrix.
+///
+/// Each entry of `L` is a dot product accumulated in the quire and rounded once, followed by
+/// a square root or a division.
+#[derive(Clone, Debug)]
+pub struct Cholesky<T: PositField> {
+    l: DMatrix<T>,
+}
+
+impl<T: PositField> Cholesky<T> {
+    /// Decomposes a square matrix, only its lower triangle is read. `None` if the matrix is
+    /// not positive-definite.
+    pub fn new(matrix: DMatrix<T>) -> Option<Self> {
+        assert!(
+            matrix.is_square(),
+            "Cholesky decomposition of a non-square matrix"
+        );
+        let n = matrix.nrows();
+        let mut l = DMatrix::<T>::zeros(n, n);
+        for j in 0..n {
+            let square = dot(matrix[(j, j)], (0..j).map(|k| (-l[(j, k)], l[(j, k)])));
+            // NaR is below zero
+            if square <= T::zero() {
+                return None;
+            }
+            let diagonal = square.sqrt();
+            l[(j, j)] = diagonal;
+            for i in j + 1..n {
+                let value = dot(matrix[(i, j)], (0..j).map(|k| (-l[(i, k)], l[(j, k)])));
+                l[(i, j)] = value / diagonal;
+            }
+        }
+        Some(Cholesky { l })
+    }
+
+    /// Lower triangular factor.
+    pub fn l(&self) -> &DMatrix<T> {
+        &self.l
+    }
+
+    /// Solves `A x = b`.
+    pub fn solve(&self, b: &DVector<T>) -> DVector<T> {
+        let n = self.l.nrows();
+        assert_eq!(b.len(), n, "right-hand side of the wrong size");
+        let mut x = b.clone();
+        for i in 0..n {
+            let residual = dot(x[i], (0..i).map(|j| (-self.l[(i, j)], x[j])));
+            x[i] = residual / self.l[(i, i)];
+        }
+        for i in (0..n).rev() {
+            let residual = dot(x[i], (i + 1..n).map(|j| (-self.l[(j, i)], x[j])));
+            x[i] = residual / self.l[(i, i)];
+        }
+        x
+    }
+}
+
+/// QR decomposition `A = Q R` by Householder reflections, for matrices with at least as many
+/// rows as columns.
+///
+/// The norms and the products of the reflections with the columns are accumulated in the
+/// quire.
+#[derive(Clone, Debug)]
+pub struct Qr<T: PositField> {
+    /// `R` on and above the diagonal, zeros below it
+    r: DMatrix<T>,
+    /// Reflection `I - 2 v v^T / (v^T v)` of each column as `v` and `v^T v`, which is zero
+    /// when the column was zero already
+    reflections: Vec<(DVector<T>, T)>,
+}
+
+impl<T: PositField> Qr<T> {
+    pub fn new(matrix: DMatrix<T>) -> Self {
+        let (m, n) = matrix.shape();
+        assert!(
+            m >= n,
+            "QR decomposition of a matrix with more columns than rows"
+        );
+        let mut r = matrix;
+        let mut reflections = Vec::with_capacity(n);
+        for k in 0..n {
+            let mut v = DVector::from_fn(m - k, |i, _| r[(k + i, k)]);
+            let norm = dot(T::zero(), v.iter().map(|&x| (x, x))).sqrt();
+            // Reflect onto the side which avoids cancellation in the first element
+            let alpha = if v[0].is_sign_negative() { norm } else { -norm };
+            v[0] -= alpha;
+            let square = dot(T::zero(), v.iter().map(|&x| (x, x)));
+
+            r[(k, k)] = alpha;
+            for i in k + 1..m {
+                r[(i, k)] = T::zero();
+            }
+            for j in k + 1..n {
+                let mut column = DVector::from_fn(m - k, |i, _| r[(k + i, j)]);
+                reflect(&v, square, &mut column);
+                for i in k..m {
+                    r[(i, j)] = column[i - k];
+                }
+            }
+            reflections.push((v, square));
+        }
+        Qr { r, reflections }
+    }
+
+    /// Upper triangular factor.
+    pub fn r(&self) -> DMatrix<T> {
+        let n = self.r.ncols();
+        self.r.rows(0, n).upper_triangle()
+    }
+
+    /// Orthogonal factor with as many columns as the decomposed matrix.
+    pub fn q(&self) -> DMatrix<T> {
+        let (m, n) = self.r.shape();
+        let mut q = DMatrix::identity(m, n);
+        for j in 0..n {
+            let mut column = q.column(j).into_owned();
+            self.apply(&mut column, true);
+            q.set_column(j, &column);
+        }
+        q
+    }
+
+    /// Solves `A x = b`, in the least squares sense if `A` has more rows than columns.
+    /// `None` if the columns of the matrix are linearly dependent.
+    pub fn solve(&self, b: &DVector<T>) -> Option<DVector<T>> {
+        let (m, n) = self.r.shape();
+        assert_eq!(b.len(), m, "right-hand side of the wrong size");
+        let mut x = b.clone();
+        self.apply(&mut x, false);
+        back_substitute(&self.r, &mut x, n)?;
+        Some(x.rows(0, n).into_owned())
+    }
+
+    /// Multiplies `x` by `Q^T`, or by `Q` if `forward`.
+    fn apply(&self, x: &mut DVector<T>, forward: bool) {
+        let m = x.len();
+        let mut steps: Vec<_> = self.reflections.iter().enumerate().collect();
+        if forward {
+            steps.reverse();
+        }
+        for (k, (v, square)) in steps {
+            let mut tail = x.rows(k, m - k).into_owned();
+            reflect(v, *square, &mut tail);
+            x.rows_mut(k, m - k).copy_from(&tail);
+        }
+    }
+}
+
+/// Applies the reflection `I - 2 v v^T / (v^T v)` to `x`.
+fn reflect<T: PositField>(v: &DVector<T>, square: T, x: &mut DVector<T>) {
+    if square.is_zero() {
+        return;
+    }
+    let product = dot(T::zero(), v.iter().zip(x.iter()).map(|(&a, &b)| (a, b)));
+    let factor = (product + product) / square;
+    for (x, &v) in x.iter_mut().zip(v.iter()) {
+        *x = dot(*x, Some((-factor, v)));
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+
+use bitvec::prelude::*;
+
+use crate::decimal::ParsePositError;
+use crate::Posit;
+
+/// Posit from a bit pattern literal, see [`Posit::from_bit_str`].
+///
+/// The literal is written as a Rust integer literal, leading zeros are kept. The exponent size
+/// is the standard 2 unless given after the literal:
+///
+/// ```
+/// # use unum::{posit, Posit};
+/// assert_eq!(posit!(0b0100_01), Posit::from(1.5));
+/// assert_eq!(posit!(0b0101, es = 0), Posit::<0>::from(1.5));
+/// ```
+#[macro_export]
+macro_rules! posit {
+    ($literal: literal) => {
+        $crate::posit!($literal, es = 2)
+    };
+    ($literal: literal, es = $es: literal) => {
+        $crate::Posit::<$es>::from_bit_str(stringify!($literal))
+            .expect("invalid posit bit pattern")
+    };
+}
+
+impl<const ES: usize> Posit<ES> {
+    /// Parses a bit pattern such as `"0b0110_01"` or `"0x4c"`.
+    ///
+    /// The digits are the bits as stored: the sign followed by the magnitude. Each hex digit
+    /// stands for four bits, underscores are ignored.
+    pub fn from_bit_str(s: &str) -> Result<Self, ParsePositError> {
+        let (radix_bits, digits) = if let Some(digits) = s.strip_prefix("0b") {
+            (1, digits)
+        } else if let Some(digits) = s.strip_prefix("0x") {
+            (4, digits)
+        } else {
+            return Err(ParsePositError(()));
+        };
+        let mut bits = BitVec::new();
+        for c in digits.chars().filter(|&c| c != '_') {
+            let digit = c.to_digit(1 << radix_bits).ok_or(ParsePositError(()))?;
+            for i in (0..radix_bits).rev() {
+                bits.push(digit >> i & 1 != 0);
+            }
+        }
+        if bits.is_empty() {
+            return Err(ParsePositError(()));
+        }
+        Ok(Posit { bits })
+    }
+
+    /// Stored bits split into sign, regime, exponent and fraction, empty fields left out.
+    fn fields(&self) -> Vec<&BitSlice> {
+        let bits = &self.bits[..];
+        if bits.is_empty() {
+            return vec![];
+        }
+        let regime_end = match bits.get(1) {
+            Some(first) => {
+                let run = bits[1..].iter().take_while(|&i| i == first).count();
+                (2 + run).min(bits.len())
+            }
+            None => 1,
+        };
+        let exp_end = (regime_end + ES).min(bits.len());
+        vec![&bits[..1], &bits[1..regime_end], &bits[regime_end..exp_end], &bits[exp_end..]]
+            .into_iter()
+            .filter(|field| !field.is_empty())
+            .collect()
+    }
+
+    fn fmt_hex(&self, f: &mut fmt::Formatter, digits: &[u8; 16]) -> fmt::Result {
+        let mut s = String::from(if f.alternate() { "0x" } else { "" });
+        if self.bits.is_empty() {
+            s.push('0');
+        }
+        // The last digit is filled up with zeros, which do not change the value
+        for chunk in self.bits.chunks(4) {
+            let digit = (0..4).fold(0, |d, i| d * 2 + chunk.get(i).unwrap_or(false) as usize);
+            s.push(char::from(digits[digit]));
+        }
+        f.pad(&s)
+    }
+}
+
+/// Stored bits with the sign, regime, exponent and fraction separated by underscores.
+///
+/// The output parses back with [`Posit::from_bit_str`] when written with the `#` flag.
+impl<const ES: usize> fmt::Binary for Posit<ES> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = String::from(if f.alternate() { "0b" } else { "" });
+        let fields: Vec<String> = self
+            .fields()
+            .into_iter()
+            .map(|field| field.iter().map(|i| if i { '1' } else { '0' }).collect())
+            .collect();
+        if fields.is_empty() {
+            s.push('0');
+        }
+        s.push_str(&fields.join("_"));
+        f.pad(&s)
+    }
+}
+
+/// Stored bits in hex, four to a digit.
+impl<const ES: usize> fmt::LowerHex for Posit<ES> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(f, b"0123456789abcdef")
+    }
+}
+
+/// Stored bits in hex, four to a digit.
+impl<const ES: usize> fmt::UpperHex for Posit<ES> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(f, b"0123456789ABCDEF")
+    }
+}
@@ -0,0 +1,814 @@
+use std::cmp;
+
+use crate::{context, Posit, Regime};
+
+/// Extra bits carried by intermediate results of the elementary functions.
+pub(crate) const GUARD: usize = 24;
+
+impl<const ES: usize> Posit<ES> {
+    /// Square root rounded to `nbits` bits, ties to even. NaR for negative inputs.
+    pub fn sqrt(self, nbits: usize) -> Self {
+        if self.is_nar() || self.is_negative() {
+            return Self::nar();
+        }
+        if self.is_zero() {
+            return self;
+        }
+
+        // Significand as an integer with an even number of fraction bits and an even scale
+        let (mut scale, mut sig, mut frac_bits) = self.unpack();
+        if scale % 2 != 0 {
+            scale -= 1;
+            sig = &sig << 1;
+        }
+        if frac_bits % 2 != 0 {
+            sig = &sig << 1;
+            frac_bits += 1;
+        }
+        // Room for the guard bit of the root
+        let root_bits = (nbits + 1).max(frac_bits / 2);
+        let (root, rem) = (&sig << (2 * root_bits - frac_bits)).sqrt_rem();
+
+        let result = Self::pack(false, scale / 2, &root, root_bits);
+        result.round_inexact(nbits, !rem.is_zero())
+    }
+
+    /// `e^self` rounded to `nbits` bits.
+    pub fn exp(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() {
+            return self;
+        }
+        if self.is_zero() {
+            return Self::one();
+        }
+        let (scale, _) = self.scale_and_fraction();
+        if let Some(saturated) = Self::saturate_exp(scale, self.is_negative(), nbits) {
+            return saturated;
+        }
+
+        // self = n * ln(2) + r, |r| <= ln(2) / 2
+        let w = nbits + GUARD + scale.max(0) as usize;
+        let ln2 = ln2::<ES>(w);
+        let half = Self::one().mul_pow2(-1);
+        let n = (self.clone().div_rounded(ln2.clone(), w) + half).floor_int();
+        let r = (self - ln2 * Self::from(n)).rounded(w);
+
+        exp_series(r, w).mul_pow2(n).rounded(nbits)
+    }
+
+    /// `2^self` rounded to `nbits` bits.
+    pub fn exp2(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() {
+            return self;
+        }
+        if self.is_zero() {
+            return Self::one();
+        }
+        let (scale, _) = self.scale_and_fraction();
+        if let Some(saturated) = Self::saturate_exp(scale - 1, self.is_negative(), nbits) {
+            return saturated;
+        }
+
+        // self = n + f, 0 <= f < 1
+        let w = nbits + GUARD;
+        let n = self.floor_int();
+        let f = self - Self::from(n);
+        if f.is_zero() {
+            return Self::one().mul_pow2(n).rounded(nbits);
+        }
+        let r = f.mul_rounded(ln2(w), w);
+
+        exp_series(r, w).mul_pow2(n).rounded(nbits)
+    }
+
+    /// Natural logarithm rounded to `nbits` bits. NaR for zero and negative inputs.
+    pub fn ln(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        let (scale, ln_m, w) = match self.log_parts(nbits) {
+            Some(parts) => parts,
+            None => return Self::nar(),
+        };
+        (Self::from(scale) * ln2(w) + ln_m).rounded(nbits)
+    }
+
+    /// Binary logarithm rounded to `nbits` bits, exact for powers of two. NaR for zero and
+    /// negative inputs.
+    pub fn log2(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        let (scale, ln_m, w) = match self.log_parts(nbits) {
+            Some(parts) => parts,
+            None => return Self::nar(),
+        };
+        if ln_m.is_zero() {
+            return Self::from(scale).rounded(nbits);
+        }
+        let ln_m_scale = ln_m.scale_and_fraction().0;
+        let w = width::<ES>(w, ln_m_scale);
+        (Self::from(scale) + ln_m.div_rounded(ln2(w), w)).rounded(nbits)
+    }
+
+    /// Decimal logarithm rounded to `nbits` bits. NaR for zero and negative inputs.
+    pub fn log10(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        let w = nbits + GUARD;
+        let ln10 = Self::from(10).ln(w);
+        self.ln(w).div_rounded(ln10, nbits)
+    }
+
+    /// `self^y` rounded to `nbits` bits.
+    ///
+    /// NaR for negative bases and for a zero base with a nonpositive exponent.
+    pub fn powf(self, y: Self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || y.is_nar() || self.is_negative() {
+            return Self::nar();
+        }
+        if self.is_zero() {
+            return if y.is_zero() || y.is_negative() {
+                Self::nar()
+            } else {
+                self
+            };
+        }
+        if y.is_zero() {
+            return Self::one();
+        }
+
+        // The absolute error of y * ln(self) turns into the relative error of the result
+        let estimate = &y * self.clone().ln(GUARD);
+        let extra = if estimate.is_zero() {
+            0
+        } else {
+            estimate.scale_and_fraction().0.max(0) as usize
+        };
+        let w = nbits + GUARD + extra;
+        (y * self.ln(w)).rounded(w).exp(nbits)
+    }
+
+    /// Splits the logarithm of a positive posit into `scale * ln(2) + ln(m)`, returning the
+    /// scale, `ln(m)` and the working precision it was computed with.
+    fn log_parts(self, nbits: usize) -> Option<(isize, Self, usize)> {
+        if self.is_nar() || self.is_zero() || self.is_negative() {
+            return None;
+        }
+        let (mut scale, frac) = self.scale_and_fraction();
+        let mut m = Self::from_parts(false, Regime::new(0), 0, &frac);
+        // Keep m within [0.75, 1.5)
+        if frac.get(0) == Some(true) {
+            m = m.mul_pow2(-1);
+            scale += 1;
+        }
+        let w = nbits + GUARD + (usize::BITS - scale.unsigned_abs().leading_zeros()) as usize;
+        if m == Self::one() {
+            return Some((scale, Self::zero(), w));
+        }
+
+        // ln(m) = 2 atanh((m - 1) / (m + 1))
+        let num = &m - Self::one();
+        let z_scale = num.scale_and_fraction().0 - 1;
+        let z = num.div_rounded(m + Self::one(), width::<ES>(w, z_scale));
+        Some((scale, arc_series(z, w, false).mul_pow2(1), w))
+    }
+
+    /// Saturated result of an exponential function with an argument of the given scale,
+    /// if the result lies beyond maxpos or minpos of `nbits` bits for sure.
+    fn saturate_exp(scale: isize, is_negative: bool, nbits: usize) -> Option<Self> {
+        let max_scale = (nbits - 2) << ES;
+        if scale <= (usize::BITS - max_scale.leading_zeros()) as isize + 1 {
+            return None;
+        }
+        Some(if is_negative {
+            Self::minpos(nbits)
+        } else {
+            Self::maxpos(nbits)
+        })
+    }
+
+    fn rounded(mut self, nbits: usize) -> Self {
+        self.round_to(nbits);
+        self
+    }
+
+    /// Largest integer not greater than a posit of a moderate size.
+    fn floor_int(&self) -> isize {
+        if self.is_zero() {
+            return 0;
+        }
+        let (scale, frac) = self.scale_and_fraction();
+        if scale < 0 {
+            return if self.is_negative() { -1 } else { 0 };
+        }
+        let scale = scale as usize;
+        let mut int: isize = 1;
+        for i in 0..scale {
+            int = int * 2 + frac.get(i).unwrap_or(false) as isize;
+        }
+        if !self.is_negative() {
+            int
+        } else if frac.iter().skip(scale).any(|i| i) {
+            -int - 1
+        } else {
+            -int
+        }
+    }
+}
+
+impl<const ES: usize> Posit<ES> {
+    /// Sine rounded to `nbits` bits.
+    pub fn sin(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let (n, r, w) = self.reduce_half_pi(nbits);
+        quadrant_sin(n, r, w, false).rounded(nbits)
+    }
+
+    /// Cosine rounded to `nbits` bits.
+    pub fn cos(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() {
+            return self;
+        }
+        if self.is_zero() {
+            return Self::one();
+        }
+        let (n, r, w) = self.reduce_half_pi(nbits);
+        quadrant_sin(n, r, w, true).rounded(nbits)
+    }
+
+    /// Tangent rounded to `nbits` bits.
+    pub fn tan(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let (n, r, w) = self.reduce_half_pi(nbits);
+        let sin = quadrant_sin(n, r.clone(), w, false);
+        sin.div_rounded(quadrant_sin(n, r, w, true), nbits)
+    }
+
+    /// `sin(pi * self)` rounded to `nbits` bits, exact at multiples of one half.
+    pub fn sin_pi(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() {
+            return self;
+        }
+        let (n, r) = self.reduce_half();
+        let w = nbits + GUARD;
+        quadrant_sin(n, pi_times(r, w), w, false).rounded(nbits)
+    }
+
+    /// `cos(pi * self)` rounded to `nbits` bits, exact at multiples of one half.
+    pub fn cos_pi(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() {
+            return self;
+        }
+        let (n, r) = self.reduce_half();
+        let w = nbits + GUARD;
+        quadrant_sin(n, pi_times(r, w), w, true).rounded(nbits)
+    }
+
+    /// `tan(pi * self)` rounded to `nbits` bits. NaR at odd multiples of one half.
+    pub fn tan_pi(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() {
+            return self;
+        }
+        let (n, r) = self.reduce_half();
+        let w = nbits + GUARD;
+        let r = pi_times(r, w);
+        let sin = quadrant_sin(n, r.clone(), w, false);
+        sin.div_rounded(quadrant_sin(n, r, w, true), nbits)
+    }
+
+    /// Inverse sine rounded to `nbits` bits. NaR outside of `[-1, 1]`.
+    pub fn asin(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let w = nbits + GUARD;
+        let is_negative = self.is_negative();
+        let x = self.abs();
+        let result = match x.partial_cmp(&Self::one()) {
+            Some(cmp::Ordering::Greater) | None => return Self::nar(),
+            Some(cmp::Ordering::Equal) => pi::<ES>(w).mul_pow2(-1),
+            Some(cmp::Ordering::Less) => {
+                // asin(x) = atan(x / sqrt((1 - x) (1 + x)))
+                let c2 = (Self::one() - x.clone()) * (Self::one() + x.clone());
+                let c = c2.clone().sqrt(width::<ES>(w, c2.scale_and_fraction().0));
+                x.div_rounded(c, w).atan_unrounded(w)
+            }
+        };
+        if is_negative {
+            -result.rounded(nbits)
+        } else {
+            result.rounded(nbits)
+        }
+    }
+
+    /// Inverse cosine rounded to `nbits` bits. NaR outside of `[-1, 1]`.
+    pub fn acos(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() {
+            return self;
+        }
+        if self.clone().abs() > Self::one() {
+            return Self::nar();
+        }
+        if self == Self::one() {
+            return Self::zero();
+        }
+        let w = nbits + GUARD;
+        if self == -Self::one() {
+            return pi::<ES>(w).rounded(nbits);
+        }
+
+        // acos(x) = 2 atan(sqrt((1 - x) / (1 + x)))
+        let num = Self::one() - self.clone();
+        let den = Self::one() + self;
+        let ratio_scale = num.scale_and_fraction().0 - den.scale_and_fraction().0;
+        let ratio = num.div_rounded(den, width::<ES>(w, ratio_scale));
+        let t = ratio.clone().sqrt(width::<ES>(w, ratio.scale_and_fraction().0));
+        t.atan_unrounded(w).mul_pow2(1).rounded(nbits)
+    }
+
+    /// Inverse tangent rounded to `nbits` bits.
+    pub fn atan(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        self.atan_unrounded(nbits + GUARD).rounded(nbits)
+    }
+
+    /// `atan(self) / pi` rounded to `nbits` bits.
+    pub fn atan_pi(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let w = nbits + GUARD;
+        self.atan_unrounded(w).div_rounded(pi(w), nbits)
+    }
+
+    /// Angle of the point `(other, self)` from the positive x axis within `[-pi, pi]`,
+    /// rounded to `nbits` bits. NaR if both coordinates are zero.
+    pub fn atan2(self, other: Self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || other.is_nar() || (self.is_zero() && other.is_zero()) {
+            return Self::nar();
+        }
+        let w = nbits + GUARD;
+        let is_negative = self.is_negative();
+        let result = if other.is_zero() {
+            pi::<ES>(w).mul_pow2(-1)
+        } else if self.is_zero() {
+            if other.is_negative() {
+                pi(w)
+            } else {
+                Self::zero()
+            }
+        } else {
+            let y = self.abs();
+            let x = other.clone().abs();
+            let ratio_scale = y.scale_and_fraction().0 - x.scale_and_fraction().0;
+            let angle = y.div_rounded(x, width::<ES>(w, ratio_scale)).atan_unrounded(w);
+            if other.is_negative() {
+                pi(w) - angle
+            } else {
+                angle
+            }
+        };
+        if is_negative {
+            -result.rounded(nbits)
+        } else {
+            result.rounded(nbits)
+        }
+    }
+
+    /// Hyperbolic sine rounded to `nbits` bits.
+    pub fn sinh(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let w = nbits + GUARD;
+        if self.scale_and_fraction().0 < 0 {
+            return trig_series(self, w, false, true).rounded(nbits);
+        }
+        let w = self.exp_width(w, nbits);
+        (self.clone().exp(w) - (-self).exp(w)).mul_pow2(-1).rounded(nbits)
+    }
+
+    /// Hyperbolic cosine rounded to `nbits` bits.
+    pub fn cosh(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() {
+            return self;
+        }
+        if self.is_zero() {
+            return Self::one();
+        }
+        let w = self.exp_width(nbits + GUARD, nbits);
+        (self.clone().exp(w) + (-self).exp(w)).mul_pow2(-1).rounded(nbits)
+    }
+
+    /// Hyperbolic tangent rounded to `nbits` bits.
+    pub fn tanh(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let w = nbits + GUARD;
+        if self.scale_and_fraction().0 < 0 {
+            let sinh = trig_series(self.clone(), w, false, true);
+            return sinh.div_rounded(trig_series(self, w, true, true), nbits);
+        }
+
+        // tanh(x) = 1 - 2 / (e^(2x) + 1) for x > 0
+        let is_negative = self.is_negative();
+        let e = self.abs().mul_pow2(1).exp(w);
+        let result = Self::one() - Self::from(2).div_rounded(e + Self::one(), w);
+        if is_negative {
+            -result.rounded(nbits)
+        } else {
+            result.rounded(nbits)
+        }
+    }
+
+    /// Number of bits keeping `w` significant bits of `e^|self|`, as long as it does not
+    /// saturate a posit of `nbits` bits.
+    fn exp_width(&self, w: usize, nbits: usize) -> usize {
+        // |self| < 2^(scale + 1), so e^|self| < 2^(2^(scale + 2))
+        let (scale, _) = self.scale_and_fraction();
+        let max_scale = ((nbits - 2) << ES) as isize + 2;
+        let scale = if scale + 2 > max_scale.ilog2() as isize {
+            max_scale
+        } else {
+            (1 << (scale + 2).max(0)).min(max_scale)
+        };
+        width::<ES>(w, scale)
+    }
+
+    /// Inverse tangent with `nbits` significant bits, not rounded to a posit of that size.
+    fn atan_unrounded(self, nbits: usize) -> Self {
+        let is_negative = self.is_negative();
+        let mut x = self.abs();
+        let inverted = x > Self::one();
+        if inverted {
+            let scale = x.scale_and_fraction().0;
+            x = Self::one().div_rounded(x, width::<ES>(nbits, -scale));
+        }
+
+        // atan(x) = 2 atan(x / (1 + sqrt(1 + x^2))) until the series converges quickly
+        let w = width::<ES>(nbits + 4, -8);
+        let mut halvings = 0;
+        while x.scale_and_fraction().0 >= -3 {
+            let root = (Self::one() + &x * &x).sqrt(w);
+            x = x.div_rounded(Self::one() + root, w);
+            halvings += 1;
+        }
+        let mut result = arc_series(x, nbits + 4, true).mul_pow2(halvings);
+        if inverted {
+            result = pi::<ES>(w).mul_pow2(-1) - result;
+        }
+        result.round_to(nbits);
+        if is_negative {
+            -result
+        } else {
+            result
+        }
+    }
+
+    /// Splits a nonzero posit into `n * pi / 2 + r` with `|r|` about `pi / 4` at most,
+    /// returning `n mod 4`, `r` and the number of significant bits `r` is known with.
+    ///
+    /// Posits are exact, so the product `n * pi / 2` only needs as many bits of `pi` as the
+    /// scale of the argument adds, plus whatever cancels when `r` is close to zero.
+    fn reduce_half_pi(&self, nbits: usize) -> (usize, Self, usize) {
+        let w = nbits + GUARD;
+        let (scale, _) = self.scale_and_fraction();
+        if scale < -1 {
+            return (0, self.clone(), w);
+        }
+        let mut extra = GUARD;
+        loop {
+            let wide = nbits + extra + scale.max(0) as usize;
+            let half_pi = pi::<ES>(wide).mul_pow2(-1);
+            let q = self.clone().div_rounded(half_pi.clone(), wide);
+            let n = (q + Self::one().mul_pow2(-1)).floor();
+            let quadrant = n.rem_pow2(2).floor_int() as usize;
+            let r = self - n * half_pi;
+            if r.is_zero() {
+                extra *= 2;
+                continue;
+            }
+            // The absolute error of r is about 2^-(nbits + extra)
+            let r_scale = r.scale_and_fraction().0;
+            if r_scale + (extra as isize) < GUARD as isize {
+                extra = (GUARD as isize - r_scale) as usize;
+                continue;
+            }
+            return (quadrant, r.rounded(width::<ES>(w, r_scale)), w);
+        }
+    }
+
+    /// Splits a posit into `n / 2 + r` exactly with `|r| <= 1 / 4`, returning `n mod 4` and
+    /// `r`.
+    fn reduce_half(&self) -> (usize, Self) {
+        let half = Self::one().mul_pow2(-1);
+        let y = self.rem_pow2(1);
+        let n = (y.clone().mul_pow2(1) + half).floor_int();
+        let r = y - Self::from(n).mul_pow2(-1);
+        (n as usize % 4, r)
+    }
+
+    /// Remainder of the division by `2^k` within `[0, 2^k)`, exactly.
+    fn rem_pow2(&self, k: isize) -> Self {
+        if self.is_nar() || self.is_zero() {
+            return self.clone();
+        }
+        let (scale, frac) = self.scale_and_fraction();
+        let low = if scale < k {
+            self.clone().abs()
+        } else {
+            // Fraction bit `i` weighs `2^(scale - i - 1)`, only those below `2^k` stay
+            let start = (scale - k) as usize;
+            match frac.iter().skip(start).position(|i| i) {
+                None => Self::zero(),
+                Some(pos) => {
+                    let top = start + pos;
+                    let (regime, exp) = Regime::from_scale(scale - top as isize - 1, ES);
+                    Self::from_parts(false, regime, exp, &frac[top + 1..])
+                }
+            }
+        };
+        if self.is_negative() && !low.is_zero() {
+            Self::one().mul_pow2(k) - low
+        } else {
+            low
+        }
+    }
+}
+
+impl<const ES: usize> Posit<ES> {
+    /// Largest integer not greater than the posit, exactly.
+    pub fn floor(self) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let low = self.rem_pow2(0);
+        self - low
+    }
+
+    /// Smallest integer not less than the posit, exactly.
+    pub fn ceil(self) -> Self {
+        let _exact = context::exact();
+        -(-self).floor()
+    }
+
+    /// Integer part of the posit, rounding towards zero.
+    pub fn trunc(self) -> Self {
+        let _exact = context::exact();
+        if self.is_negative() {
+            self.ceil()
+        } else {
+            self.floor()
+        }
+    }
+
+    /// Nearest integer, ties away from zero.
+    pub fn round(self) -> Self {
+        let _exact = context::exact();
+        let half = Self::one().mul_pow2(-1);
+        if self.is_negative() {
+            (self - half).trunc()
+        } else {
+            (self + half).trunc()
+        }
+    }
+
+    /// Fractional part of the posit, with its sign.
+    pub fn fract(self) -> Self {
+        let _exact = context::exact();
+        self.clone() - self.trunc()
+    }
+
+    /// Cube root rounded to `nbits` bits.
+    pub fn cbrt(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let negative = self.is_negative();
+        let w = nbits + GUARD;
+        let root = self.abs().ln(w).div_rounded(Self::from(3), w).exp(nbits);
+        if negative {
+            -root
+        } else {
+            root
+        }
+    }
+
+    /// `sqrt(self^2 + other^2)` rounded to `nbits` bits, without intermediate rounding.
+    pub fn hypot(self, other: Self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        (&self * &self + &other * &other).sqrt(nbits)
+    }
+
+    /// `e^self - 1` rounded to `nbits` bits, accurate for arguments close to zero.
+    pub fn exp_m1(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let w = nbits + GUARD + self.cancelled_bits();
+        (self.exp(w) - Self::one()).rounded(nbits)
+    }
+
+    /// `ln(1 + self)` rounded to `nbits` bits. NaR below `-1`.
+    pub fn ln_1p(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        // The sum is exact
+        (self + Self::one()).ln(nbits)
+    }
+
+    /// Logarithm to the given base rounded to `nbits` bits.
+    pub fn log(self, base: Self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        let w = nbits + GUARD;
+        self.ln(w).div_rounded(base.ln(w), nbits)
+    }
+
+    /// Inverse hyperbolic sine rounded to `nbits` bits.
+    pub fn asinh(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        let negative = self.is_negative();
+        let x = self.abs();
+        let w = nbits + GUARD + x.cancelled_bits();
+        let root = (&x * &x + Self::one()).sqrt(w);
+        let result = (x + root).ln(nbits);
+        if negative {
+            -result
+        } else {
+            result
+        }
+    }
+
+    /// Inverse hyperbolic cosine rounded to `nbits` bits. NaR below 1.
+    pub fn acosh(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self < Self::one() {
+            return Self::nar();
+        }
+        let w = nbits + GUARD;
+        let root = (&self * &self - Self::one()).sqrt(w);
+        (self + root).ln(nbits)
+    }
+
+    /// Inverse hyperbolic tangent rounded to `nbits` bits. NaR outside of `(-1, 1)`.
+    pub fn atanh(self, nbits: usize) -> Self {
+        let _exact = context::exact();
+        if self.is_nar() || self.is_zero() {
+            return self;
+        }
+        if self.cmp_abs(&Self::one()) != cmp::Ordering::Less {
+            return Self::nar();
+        }
+        let w = nbits + GUARD + self.cancelled_bits();
+        let ratio = (Self::one() + self.clone()).div_rounded(Self::one() - self, w);
+        ratio.ln(w).mul_pow2(-1).rounded(nbits)
+    }
+
+    /// Bits lost when a result close to `self` is computed from values close to one.
+    fn cancelled_bits(&self) -> usize {
+        (-self.scale_and_fraction().0).max(0) as usize
+    }
+}
+
+/// `pi` with `nbits` bits.
+pub(crate) fn pi<const ES: usize>(nbits: usize) -> Posit<ES> {
+    let _exact = context::exact();
+    // pi = 16 atan(1 / 5) - 4 atan(1 / 239)
+    let w = nbits + 8;
+    let fifth = Posit::one().div_rounded(Posit::from(5), w);
+    let small = Posit::one().div_rounded(Posit::from(239), w);
+    let pi = arc_series(fifth, w, true).mul_pow2(4) - arc_series(small, w, true).mul_pow2(2);
+    pi.rounded(nbits)
+}
+
+/// `pi * r` with `nbits` significant bits.
+fn pi_times<const ES: usize>(r: Posit<ES>, nbits: usize) -> Posit<ES> {
+    if r.is_zero() {
+        return r;
+    }
+    let w = width::<ES>(nbits, r.scale_and_fraction().0);
+    r.mul_rounded(pi(w), w)
+}
+
+/// Sine of `n * pi / 2 + r`, or its cosine if `cos`, with `nbits` significant bits.
+fn quadrant_sin<const ES: usize>(n: usize, r: Posit<ES>, nbits: usize, cos: bool) -> Posit<ES> {
+    let n = n + cos as usize;
+    let value = trig_series(r, nbits, n % 2 == 1, false);
+    if n % 4 >= 2 {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Taylor series of `sin(r)`, `cos(r)` if `cos`, or their hyperbolic counterparts for a
+/// small `r`, with `nbits` significant bits.
+fn trig_series<const ES: usize>(r: Posit<ES>, nbits: usize, cos: bool, hyperbolic: bool) -> Posit<ES> {
+    if r.is_zero() {
+        return if cos { Posit::one() } else { r };
+    }
+    let (scale, _) = r.scale_and_fraction();
+    let min_scale = if cos { 0 } else { scale } - nbits as isize - 2;
+    let w = width::<ES>(nbits, min_scale);
+    let mut r2 = r.clone().mul_rounded(r.clone(), w);
+    if !hyperbolic {
+        r2 = -r2;
+    }
+    let mut term = if cos { Posit::one() } else { r };
+    let mut sum = term.clone();
+    let mut m = if cos { 1 } else { 2 };
+    loop {
+        term = term
+            .mul_rounded(r2.clone(), w)
+            .div_rounded(Posit::from(m * (m + 1)), w);
+        if term.is_zero() || term.scale_and_fraction().0 < min_scale {
+            break;
+        }
+        sum += &term;
+        sum.round_to(w);
+        m += 2;
+    }
+    sum
+}
+
+/// `ln(2)` with `nbits` bits.
+pub(crate) fn ln2<const ES: usize>(nbits: usize) -> Posit<ES> {
+    let _exact = context::exact();
+    // ln(2) = 2 atanh(1 / 3)
+    let third = Posit::one().div_rounded(Posit::from(3), nbits + 4);
+    arc_series(third, nbits + 4, false).mul_pow2(1).rounded(nbits)
+}
+
+/// Number of bits keeping `nbits` bits after the regime and the exponent of a posit with
+/// the given binary scale.
+fn width<const ES: usize>(nbits: usize, scale: isize) -> usize {
+    nbits + ES + 2 + (scale.unsigned_abs() >> ES)
+}
+
+/// `atanh(z)`, or `atan(z)` if `alternating`, for a small nonzero `z` with `nbits`
+/// significant bits.
+fn arc_series<const ES: usize>(z: Posit<ES>, nbits: usize, alternating: bool) -> Posit<ES> {
+    let (scale, _) = z.scale_and_fraction();
+    let min_scale = scale - nbits as isize - 2;
+    // The smallest terms must not saturate at minpos
+    let w = width::<ES>(nbits, min_scale);
+    let mut z2 = z.clone().mul_rounded(z.clone(), w);
+    if alternating {
+        z2 = -z2;
+    }
+    let mut term = z.clone();
+    let mut sum = z;
+    for k in 1.. {
+        term = term.mul_rounded(z2.clone(), w);
+        if term.is_zero() || term.scale_and_fraction().0 < min_scale {
+            break;
+        }
+        sum += term.clone().div_rounded(Posit::from(2 * k + 1), w);
+        sum.round_to(w);
+    }
+    sum
+}
+
+/// `e^r` for `|r| < 1` with `nbits` significant bits.
+fn exp_series<const ES: usize>(r: Posit<ES>, nbits: usize) -> Posit<ES> {
+    let min_scale = -(nbits as isize) - 2;
+    let w = width::<ES>(nbits, min_scale);
+    let mut term = Posit::one();
+    let mut sum = Posit::one();
+    for k in 1.. {
+        term = term
+            .mul_rounded(r.clone(), w)
+            .div_rounded(Posit::from(k), w);
+        if term.is_zero() || term.scale_and_fraction().0 < min_scale {
+            break;
+        }
+        sum += &term;
+        sum.round_to(w);
+    }
+    sum
+}
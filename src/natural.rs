@@ -0,0 +1,434 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Shl, Shr, Sub};
+
+/// Operand length in limbs from which multiplication switches to Karatsuba's method.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Bits of the quotient estimated by Newton-Raphson division beyond the ones needed, so that
+/// at most a few corrections remain.
+const DIVISION_GUARD: usize = 4;
+
+/// Unsigned integer of arbitrary size, least significant limb first, without leading zero
+/// limbs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Natural(Vec<u64>);
+
+impl Natural {
+    pub(crate) fn zero() -> Self {
+        Natural(vec![])
+    }
+
+    /// `2^n`
+    pub(crate) fn power_of_two(n: usize) -> Self {
+        let mut limbs = vec![0; n / 64 + 1];
+        limbs[n / 64] = 1 << (n % 64);
+        Natural(limbs)
+    }
+
+    /// Integer with the given limbs, least significant first.
+    pub(crate) fn from_limbs(limbs: Vec<u64>) -> Self {
+        Natural::normalized(limbs)
+    }
+
+    pub(crate) fn limbs(&self) -> &[u64] {
+        &self.0
+    }
+
+    fn normalized(mut limbs: Vec<u64>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Natural(limbs)
+    }
+
+    pub(crate) fn from_digits(digits: &[u8]) -> Self {
+        let mut n = Natural::zero();
+        for &d in digits {
+            n.mul_add(10, d as u64);
+        }
+        n
+    }
+
+    pub(crate) fn from_bits(bits: impl IntoIterator<Item = bool>) -> Self {
+        let mut n = Natural::zero();
+        for bit in bits {
+            n.mul_add(2, bit as u64);
+        }
+        n
+    }
+
+    /// Integer read from the bits `start..end` of a bit string stored most significant bit
+    /// first, see [`read_word`].
+    pub(crate) fn from_bit_range(bytes: &[u8], start: usize, end: usize) -> Self {
+        let mut limbs = Vec::with_capacity((end - start).div_ceil(64));
+        let mut high = end;
+        while high > start {
+            let low = high.saturating_sub(64).max(start);
+            limbs.push(read_word(bytes, end, low) >> (64 - (high - low)));
+            high = low;
+        }
+        Natural::normalized(limbs)
+    }
+
+    /// Writes the `len` least significant bits into a bit string stored most significant
+    /// bit first, starting at bit `start`. The bits there must be zeros.
+    pub(crate) fn write_bits(&self, len: usize, bytes: &mut [u8], start: usize) {
+        let mut high = start + len;
+        for &limb in &self.0 {
+            if high <= start {
+                break;
+            }
+            let low = high.saturating_sub(64).max(start);
+            write_word(bytes, low, limb, high - low);
+            high = low;
+        }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of bits without leading zeros.
+    pub(crate) fn bits(&self) -> usize {
+        match self.0.last() {
+            Some(top) => self.0.len() * 64 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// `self * m + a`
+    pub(crate) fn mul_add(&mut self, m: u64, a: u64) {
+        let mut carry = a as u128;
+        for limb in self.0.iter_mut() {
+            let x = *limb as u128 * m as u128 + carry;
+            *limb = x as u64;
+            carry = x >> 64;
+        }
+        if carry != 0 {
+            self.0.push(carry as u64);
+        }
+    }
+
+    /// Divides in place by a single limb, returning the remainder.
+    pub(crate) fn div_rem_limb(&mut self, d: u64) -> u64 {
+        let mut rem = 0u128;
+        for limb in self.0.iter_mut().rev() {
+            let x = (rem << 64) | *limb as u128;
+            *limb = (x / d as u128) as u64;
+            rem = x % d as u128;
+        }
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+        rem as u64
+    }
+
+    /// Quotient and remainder of the division by `d`.
+    ///
+    /// Divisors of more than one limb are multiplied by their reciprocal computed with
+    /// Newton-Raphson iterations, and the estimated quotient is corrected against the
+    /// remainder.
+    pub(crate) fn div_rem(&self, d: &Natural) -> (Natural, Natural) {
+        assert!(!d.is_zero(), "division by zero");
+        if self < d {
+            return (Natural::zero(), self.clone());
+        }
+        if d.0.len() == 1 {
+            let mut quot = self.clone();
+            let rem = quot.div_rem_limb(d.0[0]);
+            return (quot, Natural::normalized(vec![rem]));
+        }
+
+        let n = d.bits();
+        let (reciprocal, precision) = d.reciprocal(self.bits() - n + 1 + DIVISION_GUARD);
+        let mut quot = &(self * &reciprocal) >> (n - 1 + precision);
+        let mut product = &quot * d;
+        let one = Natural::power_of_two(0);
+        while product > *self {
+            quot = &quot - &one;
+            product = &product - d;
+        }
+        let mut rem = self - &product;
+        while rem >= *d {
+            quot = &quot + &one;
+            rem = &rem - d;
+        }
+        (quot, rem)
+    }
+
+    /// Reciprocal `2^(n - 1 + p) / self` of an integer of `n` bits, of at least `precision`
+    /// bits `p` up to a relative error of a few units in the last place. Returns it with `p`.
+    ///
+    /// Each Newton-Raphson step `y + y (1 - d y)` doubles the precision, and only reads as
+    /// many bits of the divisor as the new precision needs.
+    fn reciprocal(&self, precision: usize) -> (Natural, usize) {
+        let n = self.bits();
+        debug_assert!(n > 64);
+        let top = (self >> (n - 64)).0[0];
+        let mut y = Natural::normalized(vec![((u128::MAX >> 1) / top as u128) as u64]);
+        let mut p = 64;
+        while p < precision {
+            let next = (2 * p - DIVISION_GUARD).min(precision);
+            let m = (next + DIVISION_GUARD).min(n);
+            let d = self >> (n - m);
+            // `d y` is close to `2^(p + m - 1)`, the error is scaled to the new precision
+            let product = &d * &y;
+            let one = Natural::power_of_two(p + m - 1);
+            let shift = 2 * p + m - 1 - next;
+            let scaled = &y << (next - p);
+            y = if product <= one {
+                &scaled + &(&(&y * &(&one - &product)) >> shift)
+            } else {
+                &scaled - &(&(&y * &(&product - &one)) >> shift)
+            };
+            p = next;
+        }
+        (y, p)
+    }
+
+    /// Integer square root and remainder, `self - root^2`.
+    ///
+    /// Newton's iteration `(x + self / x) / 2` decreases to the root from an estimate above
+    /// it, computed in floating point from the leading bits.
+    pub(crate) fn sqrt_rem(&self) -> (Natural, Natural) {
+        if self.is_zero() {
+            return (Natural::zero(), Natural::zero());
+        }
+        let shift = self.bits().saturating_sub(63) / 2 * 2;
+        let top = (self >> shift).0[0];
+        let estimate = (top as f64).sqrt().ceil() as u64 + 1;
+        let mut root = &Natural::normalized(vec![estimate]) << (shift / 2);
+        loop {
+            let next = &(&root + &self.div_rem(&root).0) >> 1;
+            if next >= root {
+                break;
+            }
+            root = next;
+        }
+        let rem = self - &(&root * &root);
+        (root, rem)
+    }
+
+    /// Decimal digits without leading zeros, most significant first.
+    pub(crate) fn to_digits(&self) -> Vec<u8> {
+        let mut n = self.clone();
+        let mut digits = vec![];
+        while !n.is_zero() {
+            digits.push(n.div_rem_limb(10) as u8);
+        }
+        digits.reverse();
+        digits
+    }
+}
+
+impl PartialOrd for Natural {
+    fn partial_cmp(&self, other: &Natural) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Natural {
+    fn cmp(&self, other: &Natural) -> Ordering {
+        self.0
+            .len()
+            .cmp(&other.0.len())
+            .then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+    }
+}
+
+impl Add for &Natural {
+    type Output = Natural;
+
+    fn add(self, rhs: &Natural) -> Natural {
+        let mut limbs = self.0.clone();
+        add_limbs(&mut limbs, &rhs.0, 0);
+        Natural(limbs)
+    }
+}
+
+/// Panics if the difference is negative.
+impl Sub for &Natural {
+    type Output = Natural;
+
+    fn sub(self, rhs: &Natural) -> Natural {
+        let mut limbs = self.0.clone();
+        sub_limbs(&mut limbs, &rhs.0);
+        Natural::normalized(limbs)
+    }
+}
+
+impl Mul for &Natural {
+    type Output = Natural;
+
+    fn mul(self, rhs: &Natural) -> Natural {
+        Natural::normalized(mul_limbs(&self.0, &rhs.0))
+    }
+}
+
+impl Shl<usize> for &Natural {
+    type Output = Natural;
+
+    fn shl(self, n: usize) -> Natural {
+        if self.is_zero() {
+            return Natural::zero();
+        }
+        let (limbs, bits) = (n / 64, n % 64);
+        let mut result = vec![0; limbs];
+        result.reserve(self.0.len() + 1);
+        if bits == 0 {
+            result.extend_from_slice(&self.0);
+        } else {
+            let mut carry = 0;
+            for &limb in &self.0 {
+                result.push(limb << bits | carry);
+                carry = limb >> (64 - bits);
+            }
+            result.push(carry);
+        }
+        Natural::normalized(result)
+    }
+}
+
+/// Rounds towards zero.
+impl Shr<usize> for &Natural {
+    type Output = Natural;
+
+    fn shr(self, n: usize) -> Natural {
+        let (limbs, bits) = (n / 64, n % 64);
+        if limbs >= self.0.len() {
+            return Natural::zero();
+        }
+        let source = &self.0[limbs..];
+        let result = if bits == 0 {
+            source.to_vec()
+        } else {
+            (0..source.len())
+                .map(|i| {
+                    source[i] >> bits | source.get(i + 1).map_or(0, |&next| next << (64 - bits))
+                })
+                .collect()
+        };
+        Natural::normalized(result)
+    }
+}
+
+/// Adds `x * 2^(64 offset)` to `acc` in place, growing it as needed.
+fn add_limbs(acc: &mut Vec<u64>, x: &[u64], offset: usize) {
+    if acc.len() < offset + x.len() {
+        acc.resize(offset + x.len(), 0);
+    }
+    let mut carry = false;
+    let mut i = offset;
+    for &limb in x {
+        let (sum, c1) = acc[i].overflowing_add(limb);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        acc[i] = sum;
+        carry = c1 || c2;
+        i += 1;
+    }
+    while carry {
+        if i == acc.len() {
+            acc.push(1);
+            return;
+        }
+        let (sum, c) = acc[i].overflowing_add(1);
+        acc[i] = sum;
+        carry = c;
+        i += 1;
+    }
+}
+
+/// Subtracts `x` from `acc` in place, panics if the difference is negative.
+fn sub_limbs(acc: &mut [u64], x: &[u64]) {
+    let mut borrow = false;
+    let mut i = 0;
+    while i < x.len() || borrow {
+        assert!(i < acc.len(), "negative difference of naturals");
+        let (diff, b1) = acc[i].overflowing_sub(x.get(i).copied().unwrap_or(0));
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        acc[i] = diff;
+        borrow = b1 || b2;
+        i += 1;
+    }
+}
+
+/// Product of two limb strings, possibly with leading zero limbs.
+fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.len() < KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+    let half = a.len() / 2;
+    let (a0, a1) = a.split_at(half);
+    if b.len() <= half {
+        // Too unbalanced to split both, multiply the halves of the longer one separately
+        let mut product = mul_limbs(a0, b);
+        add_limbs(&mut product, &mul_limbs(a1, b), half);
+        return product;
+    }
+    let (b0, b1) = b.split_at(half);
+
+    // (a1 x + a0) (b1 x + b0) = a1 b1 x^2 + ((a0 + a1) (b0 + b1) - a0 b0 - a1 b1) x + a0 b0
+    let low = mul_limbs(a0, b0);
+    let high = mul_limbs(a1, b1);
+    let mut a_sum = a0.to_vec();
+    add_limbs(&mut a_sum, a1, 0);
+    let mut b_sum = b0.to_vec();
+    add_limbs(&mut b_sum, b1, 0);
+    let mut middle = mul_limbs(&a_sum, &b_sum);
+    sub_limbs(&mut middle, &low);
+    sub_limbs(&mut middle, &high);
+
+    let mut product = low;
+    add_limbs(&mut product, &middle, half);
+    add_limbs(&mut product, &high, 2 * half);
+    product
+}
+
+fn schoolbook(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut product = vec![0; a.len() + b.len()];
+    for (i, &x) in b.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        let mut carry = 0u128;
+        for (j, &y) in a.iter().enumerate() {
+            let t = x as u128 * y as u128 + product[i + j] as u128 + carry;
+            product[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        product[i + a.len()] = carry as u64;
+    }
+    product
+}
+
+/// The 64 bits from bit `start` of a bit string of `len` bits stored most significant bit
+/// first, as in a `BitVec`. Bits past `len`, stored or not, read as zeros.
+pub(crate) fn read_word(bytes: &[u8], len: usize, start: usize) -> u64 {
+    if start >= len {
+        return 0;
+    }
+    let first = start / 8;
+    let mut window = [0; 16];
+    let available = bytes.len().saturating_sub(first).min(9);
+    window[..available].copy_from_slice(&bytes[first..first + available]);
+    let word = ((u128::from_be_bytes(window) << (start % 8)) >> 64) as u64;
+    if len - start < 64 {
+        word & !(u64::MAX >> (len - start))
+    } else {
+        word
+    }
+}
+
+/// Sets the `len` least significant bits of `word` from bit `start` of a bit string stored
+/// most significant bit first. The bits there must be zeros.
+pub(crate) fn write_word(bytes: &mut [u8], start: usize, word: u64, len: usize) {
+    if len == 0 {
+        return;
+    }
+    let word = word as u128 & (u128::MAX >> (128 - len));
+    let window = (word << (128 - len) >> (start % 8)).to_be_bytes();
+    for (byte, &bits) in bytes[start / 8..].iter_mut().zip(window.iter()) {
+        *byte |= bits;
+    }
+}
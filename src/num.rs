@@ -0,0 +1,846 @@
+use std::cmp;
+use std::convert::TryFrom;
+use std::num::FpCategory;
+use std::ops::Neg;
+
+use num_traits::{Bounded, Float, FromPrimitive, Num, One, Signed, ToPrimitive, Zero};
+
+use crate::{context, ParsePositError, Posit, PositContext, P16, P32, P64, P8};
+
+impl<const ES: usize> Zero for Posit<ES> {
+    fn zero() -> Self {
+        Posit::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        Posit::is_zero(self)
+    }
+}
+
+impl<const ES: usize> One for Posit<ES> {
+    fn one() -> Self {
+        Posit::one()
+    }
+}
+
+/// Only decimal strings are supported, other radices are an error.
+impl<const ES: usize> Num for Posit<ES> {
+    type FromStrRadixErr = ParsePositError;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if radix != 10 {
+            return Err(ParsePositError(()));
+        }
+        s.parse()
+    }
+}
+
+impl<const ES: usize> Signed for Posit<ES> {
+    fn abs(&self) -> Self {
+        Posit::abs(self.clone())
+    }
+
+    /// NaR if either is NaR.
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self.is_nar() || other.is_nar() {
+            Posit::nar()
+        } else if self <= other {
+            Posit::zero()
+        } else {
+            self - other
+        }
+    }
+
+    /// NaR for NaR, zero for zero.
+    fn signum(&self) -> Self {
+        if self.is_nar() || Posit::is_zero(self) {
+            self.clone()
+        } else if Posit::is_negative(self) {
+            -Posit::one()
+        } else {
+            Posit::one()
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        !Posit::is_negative(self) && !Posit::is_zero(self)
+    }
+
+    fn is_negative(&self) -> bool {
+        Posit::is_negative(self) && !self.is_nar()
+    }
+}
+
+/// Exact conversions, floating point infinities and NaN become NaR.
+impl<const ES: usize> FromPrimitive for Posit<ES> {
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Posit::from(n))
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Posit::from(n))
+    }
+
+    fn from_i128(n: i128) -> Option<Self> {
+        Some(Posit::from(n))
+    }
+
+    fn from_u128(n: u128) -> Option<Self> {
+        Some(Posit::from(n))
+    }
+
+    fn from_f32(n: f32) -> Option<Self> {
+        Some(Posit::from(n as f64))
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        Some(Posit::from(n))
+    }
+}
+
+/// Integers are truncated towards zero, floats rounded to the nearest value. NaR becomes
+/// NaN and has no integer value.
+impl<const ES: usize> ToPrimitive for Posit<ES> {
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self.clone().trunc()).ok()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self.clone().trunc()).ok()
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        i128::try_from(self.clone().trunc()).ok()
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        u128::try_from(self.clone().trunc()).ok()
+    }
+
+    fn to_f32(&self) -> Option<f32> {
+        Some(Posit::to_f32(self))
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(Posit::to_f64(self))
+    }
+}
+
+impl<const ES: usize> num_traits::NumCast for Posit<ES> {
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        // Integers beyond the precision of f64 are taken as they are
+        match n.to_f64() {
+            Some(f) if !f.is_finite() || f.fract() != 0.0 => Some(f.into()),
+            f => n
+                .to_i128()
+                .map(Into::into)
+                .or_else(|| n.to_u128().map(Into::into))
+                .or_else(|| f.map(Into::into)),
+        }
+    }
+}
+
+/// Operations of [`Float`] without its `Copy` bound, for generic code which runs on primitive
+/// floats, on the fixed-width posits and on the standard posit alike.
+///
+/// Posits of arbitrary length keep their bits in a `BitVec`, so they cannot be `Copy` and
+/// cannot implement `Float`. They implement this trait instead, which every `Float` does too.
+/// Constants which depend on a width, like `epsilon` or `max_value`, are left out, as an
+/// arbitrary posit has none. So are `max` and `min`, which would clash with those of [`Ord`]
+/// for posits, ordering NaR below every real value.
+///
+/// The standard posit rounds results which are not exact to the width of the current
+/// [`PositContext`], or without one to 16 bits more than its longer operand, like its
+/// quotients do.
+pub trait Real: Num + num_traits::NumCast + Clone + PartialOrd + Neg<Output = Self> {
+    fn nan() -> Self;
+    fn is_nan(&self) -> bool;
+    fn is_finite(&self) -> bool;
+    fn classify(&self) -> FpCategory;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn fract(self) -> Self;
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn is_sign_positive(&self) -> bool;
+    fn is_sign_negative(&self) -> bool;
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn recip(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn exp2(self) -> Self;
+    fn ln(self) -> Self;
+    fn log(self, base: Self) -> Self;
+    fn log2(self) -> Self;
+    fn log10(self) -> Self;
+    fn abs_sub(self, other: Self) -> Self;
+    fn cbrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn exp_m1(self) -> Self;
+    fn ln_1p(self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn tanh(self) -> Self;
+    fn asinh(self) -> Self;
+    fn acosh(self) -> Self;
+    fn atanh(self) -> Self;
+}
+
+macro_rules! forward_real {
+    ($($method: ident($($arg: ident: $type: ty),*) -> $output: ty;)*) => {$(
+        fn $method(self, $($arg: $type),*) -> $output {
+            Float::$method(self, $($arg),*)
+        }
+    )*};
+}
+
+impl<T: Float> Real for T {
+    fn nan() -> Self {
+        Float::nan()
+    }
+
+    fn is_nan(&self) -> bool {
+        Float::is_nan(*self)
+    }
+
+    fn is_finite(&self) -> bool {
+        Float::is_finite(*self)
+    }
+
+    fn classify(&self) -> FpCategory {
+        Float::classify(*self)
+    }
+
+    fn is_sign_positive(&self) -> bool {
+        Float::is_sign_positive(*self)
+    }
+
+    fn is_sign_negative(&self) -> bool {
+        Float::is_sign_negative(*self)
+    }
+
+    forward_real! {
+        floor() -> Self;
+        ceil() -> Self;
+        round() -> Self;
+        trunc() -> Self;
+        fract() -> Self;
+        abs() -> Self;
+        signum() -> Self;
+        mul_add(a: Self, b: Self) -> Self;
+        recip() -> Self;
+        powi(n: i32) -> Self;
+        powf(n: Self) -> Self;
+        sqrt() -> Self;
+        exp() -> Self;
+        exp2() -> Self;
+        ln() -> Self;
+        log(base: Self) -> Self;
+        log2() -> Self;
+        log10() -> Self;
+        abs_sub(other: Self) -> Self;
+        cbrt() -> Self;
+        hypot(other: Self) -> Self;
+        sin() -> Self;
+        cos() -> Self;
+        tan() -> Self;
+        asin() -> Self;
+        acos() -> Self;
+        atan() -> Self;
+        atan2(other: Self) -> Self;
+        sin_cos() -> (Self, Self);
+        exp_m1() -> Self;
+        ln_1p() -> Self;
+        sinh() -> Self;
+        cosh() -> Self;
+        tanh() -> Self;
+        asinh() -> Self;
+        acosh() -> Self;
+        atanh() -> Self;
+    }
+}
+
+impl<const ES: usize> Posit<ES> {
+    /// Width the inexact results of [`Real`] are rounded to, see there.
+    fn real_nbits(&self, others: &[&Self]) -> usize {
+        let len = others.iter().map(|p| p.bits.len()).fold(self.bits.len(), cmp::max);
+        PositContext::current().nbits_for(ES).unwrap_or(len + 16)
+    }
+}
+
+/// `x^n` rounded to `nbits` bits, which allows negative bases for integral exponents.
+fn powf<const ES: usize>(x: Posit<ES>, n: Posit<ES>, nbits: usize) -> Posit<ES> {
+    let _exact = context::exact();
+    if !x.is_negative() || x.is_nar() {
+        return x.powf(n, nbits);
+    }
+    if n.clone().fract() != Posit::zero() {
+        return Posit::nar();
+    }
+    let odd = n.clone() % Posit::from(2) != Posit::zero();
+    let magnitude = x.abs().powf(n, nbits);
+    if odd {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+macro_rules! posit_real {
+    ($($method: ident($($arg: ident),*);)*) => {$(
+        fn $method(self, $($arg: Self),*) -> Self {
+            let nbits = self.real_nbits(&[$(&$arg),*]);
+            Posit::$method(self, $($arg,)* nbits)
+        }
+    )*};
+}
+
+/// Posits have no infinities, NaR is their NaN. Operators round as the context requires, other
+/// results which are not exact are rounded as described on the trait.
+impl<const ES: usize> Real for Posit<ES> {
+    fn nan() -> Self {
+        Posit::nar()
+    }
+
+    fn is_nan(&self) -> bool {
+        self.is_nar()
+    }
+
+    fn is_finite(&self) -> bool {
+        !self.is_nar()
+    }
+
+    fn classify(&self) -> FpCategory {
+        if self.is_nar() {
+            FpCategory::Nan
+        } else if Posit::is_zero(self) {
+            FpCategory::Zero
+        } else {
+            FpCategory::Normal
+        }
+    }
+
+    fn floor(self) -> Self {
+        Posit::floor(self)
+    }
+
+    fn ceil(self) -> Self {
+        Posit::ceil(self)
+    }
+
+    fn round(self) -> Self {
+        Posit::round(self)
+    }
+
+    fn trunc(self) -> Self {
+        Posit::trunc(self)
+    }
+
+    fn fract(self) -> Self {
+        Posit::fract(self)
+    }
+
+    fn abs(self) -> Self {
+        Posit::abs(self)
+    }
+
+    /// NaR for NaR, zero for zero.
+    fn signum(self) -> Self {
+        Signed::signum(&self)
+    }
+
+    fn is_sign_positive(&self) -> bool {
+        !Posit::is_negative(self)
+    }
+
+    fn is_sign_negative(&self) -> bool {
+        Posit::is_negative(self)
+    }
+
+    /// Rounded once as the context requires, exact without a width.
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        Posit::mul_add(self, a, b)
+    }
+
+    fn recip(self) -> Self {
+        Posit::one() / self
+    }
+
+    fn powi(self, n: i32) -> Self {
+        let power = self.pow(n.unsigned_abs() as usize);
+        if n < 0 {
+            Posit::one() / power
+        } else {
+            power
+        }
+    }
+
+    /// Negative bases are allowed for integral exponents.
+    fn powf(self, n: Self) -> Self {
+        let nbits = self.real_nbits(&[&n]);
+        powf(self, n, nbits)
+    }
+
+    fn abs_sub(self, other: Self) -> Self {
+        Signed::abs_sub(&self, &other)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        (Real::sin(self.clone()), Real::cos(self))
+    }
+
+    posit_real! {
+        sqrt();
+        exp();
+        exp2();
+        ln();
+        log(base);
+        log2();
+        log10();
+        cbrt();
+        hypot(other);
+        sin();
+        cos();
+        tan();
+        asin();
+        acos();
+        atan();
+        atan2(other);
+        exp_m1();
+        ln_1p();
+        sinh();
+        cosh();
+        tanh();
+        asinh();
+        acosh();
+        atanh();
+    }
+}
+
+/// `f(x)` for a fixed-width posit computed on the standard posit, rounded to `nbits` bits.
+///
+/// The operators in `f` are exact whatever the context, fixed-width posits round only to their
+/// own width.
+fn apply<T>(x: T, nbits: usize, f: impl FnOnce(Posit, usize) -> Posit) -> T
+where
+    T: From<Posit> + Into<Posit>,
+{
+    let _exact = context::exact();
+    T::from(f(x.into(), nbits))
+}
+
+macro_rules! fixed_traits {
+    ($($name: ident($nbits: expr)),*) => {$(
+        impl Zero for $name {
+            fn zero() -> Self {
+                $name::ZERO
+            }
+
+            fn is_zero(&self) -> bool {
+                $name::is_zero(*self)
+            }
+        }
+
+        impl One for $name {
+            fn one() -> Self {
+                $name::ONE
+            }
+        }
+
+        /// Only decimal strings are supported, other radices are an error.
+        impl Num for $name {
+            type FromStrRadixErr = ParsePositError;
+
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+                if radix != 10 {
+                    return Err(ParsePositError(()));
+                }
+                Posit::from_str_rounded(s, $nbits).map(Self::from)
+            }
+        }
+
+        impl Signed for $name {
+            fn abs(&self) -> Self {
+                $name::abs(*self)
+            }
+
+            fn abs_sub(&self, other: &Self) -> Self {
+                Float::abs_sub(*self, *other)
+            }
+
+            /// NaR for NaR, zero for zero.
+            fn signum(&self) -> Self {
+                Float::signum(*self)
+            }
+
+            fn is_positive(&self) -> bool {
+                *self > $name::ZERO
+            }
+
+            fn is_negative(&self) -> bool {
+                $name::is_negative(*self) && !self.is_nar()
+            }
+        }
+
+        impl Bounded for $name {
+            fn min_value() -> Self {
+                -$name::MAXPOS
+            }
+
+            fn max_value() -> Self {
+                $name::MAXPOS
+            }
+        }
+
+        /// Rounds to the nearest value, floating point infinities and NaN become NaR.
+        impl FromPrimitive for $name {
+            fn from_i64(n: i64) -> Option<Self> {
+                Some(Self::from(Posit::from(n)))
+            }
+
+            fn from_u64(n: u64) -> Option<Self> {
+                Some(Self::from(Posit::from(n)))
+            }
+
+            fn from_i128(n: i128) -> Option<Self> {
+                Some(Self::from(Posit::from(n)))
+            }
+
+            fn from_u128(n: u128) -> Option<Self> {
+                Some(Self::from(Posit::from(n)))
+            }
+
+            fn from_f32(n: f32) -> Option<Self> {
+                Some(Self::from(Posit::from(n as f64)))
+            }
+
+            fn from_f64(n: f64) -> Option<Self> {
+                Some(Self::from(Posit::from(n)))
+            }
+        }
+
+        /// Integers are truncated towards zero, floats rounded to the nearest value. NaR
+        /// becomes NaN and has no integer value.
+        impl ToPrimitive for $name {
+            fn to_i64(&self) -> Option<i64> {
+                Posit::from(*self).to_i64()
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                Posit::from(*self).to_u64()
+            }
+
+            fn to_i128(&self) -> Option<i128> {
+                Posit::from(*self).to_i128()
+            }
+
+            fn to_u128(&self) -> Option<u128> {
+                Posit::from(*self).to_u128()
+            }
+
+            fn to_f32(&self) -> Option<f32> {
+                Some(Posit::from(*self).to_f32())
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                Some(Posit::from(*self).to_f64())
+            }
+        }
+
+        impl num_traits::NumCast for $name {
+            fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+                <Posit as num_traits::NumCast>::from(n).map(Into::into)
+            }
+        }
+
+        /// Posits have no infinities, those and NaN are NaR. There is a single zero, it is
+        /// positive.
+        impl Float for $name {
+            fn nan() -> Self {
+                $name::NAR
+            }
+
+            fn infinity() -> Self {
+                $name::NAR
+            }
+
+            fn neg_infinity() -> Self {
+                $name::NAR
+            }
+
+            fn neg_zero() -> Self {
+                $name::ZERO
+            }
+
+            fn min_value() -> Self {
+                -$name::MAXPOS
+            }
+
+            fn min_positive_value() -> Self {
+                $name::MINPOS
+            }
+
+            /// Distance from one to the next larger posit.
+            fn epsilon() -> Self {
+                $name::from_bits($name::ONE.to_bits() + 1) - $name::ONE
+            }
+
+            fn max_value() -> Self {
+                $name::MAXPOS
+            }
+
+            fn is_nan(self) -> bool {
+                self.is_nar()
+            }
+
+            fn is_infinite(self) -> bool {
+                false
+            }
+
+            fn is_finite(self) -> bool {
+                !self.is_nar()
+            }
+
+            fn is_normal(self) -> bool {
+                !self.is_nar() && !self.is_zero()
+            }
+
+            fn classify(self) -> FpCategory {
+                if self.is_nar() {
+                    FpCategory::Nan
+                } else if self.is_zero() {
+                    FpCategory::Zero
+                } else {
+                    FpCategory::Normal
+                }
+            }
+
+            fn floor(self) -> Self {
+                apply(self, $nbits, |p, _| p.floor())
+            }
+
+            fn ceil(self) -> Self {
+                apply(self, $nbits, |p, _| p.ceil())
+            }
+
+            fn round(self) -> Self {
+                apply(self, $nbits, |p, _| p.round())
+            }
+
+            fn trunc(self) -> Self {
+                apply(self, $nbits, |p, _| p.trunc())
+            }
+
+            fn fract(self) -> Self {
+                apply(self, $nbits, |p, _| p.fract())
+            }
+
+            fn abs(self) -> Self {
+                $name::abs(self)
+            }
+
+            /// NaR for NaR, zero for zero.
+            fn signum(self) -> Self {
+                apply(self, $nbits, |p, _| Signed::signum(&p))
+            }
+
+            fn is_sign_positive(self) -> bool {
+                !$name::is_negative(self)
+            }
+
+            fn is_sign_negative(self) -> bool {
+                $name::is_negative(self)
+            }
+
+            /// Rounded once, through the quire.
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                let fused =
+                    Posit::from(self).mul_add_rounded(Posit::from(a), Posit::from(b), $nbits);
+                Self::from(fused)
+            }
+
+            fn recip(self) -> Self {
+                apply(self, $nbits, |p, n| Posit::one().div_rounded(p, n))
+            }
+
+            fn powi(self, n: i32) -> Self {
+                // Small powers are exact before the single rounding
+                if n.unsigned_abs() > 64 {
+                    return Float::powf(self, Self::from(Posit::from(n)));
+                }
+                apply(self, $nbits, |p, nbits| {
+                    let power = p.pow(n.unsigned_abs() as usize);
+                    if n < 0 {
+                        Posit::one().div_rounded(power, nbits)
+                    } else {
+                        power
+                    }
+                })
+            }
+
+            /// Negative bases are allowed for integral exponents.
+            fn powf(self, n: Self) -> Self {
+                let n = Posit::from(n);
+                apply(self, $nbits, |p, nbits| powf(p, n, nbits))
+            }
+
+            fn sqrt(self) -> Self {
+                apply(self, $nbits, Posit::sqrt)
+            }
+
+            fn exp(self) -> Self {
+                apply(self, $nbits, Posit::exp)
+            }
+
+            fn exp2(self) -> Self {
+                apply(self, $nbits, Posit::exp2)
+            }
+
+            fn ln(self) -> Self {
+                apply(self, $nbits, Posit::ln)
+            }
+
+            fn log(self, base: Self) -> Self {
+                apply(self, $nbits, |p, n| p.log(base.into(), n))
+            }
+
+            fn log2(self) -> Self {
+                apply(self, $nbits, Posit::log2)
+            }
+
+            fn log10(self) -> Self {
+                apply(self, $nbits, Posit::log10)
+            }
+
+            /// NaR is ignored unless both are NaR.
+            fn max(self, other: Self) -> Self {
+                if self.is_nar() || (!other.is_nar() && other > self) {
+                    other
+                } else {
+                    self
+                }
+            }
+
+            /// NaR is ignored unless both are NaR.
+            fn min(self, other: Self) -> Self {
+                if self.is_nar() || (!other.is_nar() && other < self) {
+                    other
+                } else {
+                    self
+                }
+            }
+
+            fn abs_sub(self, other: Self) -> Self {
+                if self.is_nar() || other.is_nar() {
+                    $name::NAR
+                } else if self <= other {
+                    $name::ZERO
+                } else {
+                    self - other
+                }
+            }
+
+            fn cbrt(self) -> Self {
+                apply(self, $nbits, Posit::cbrt)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                apply(self, $nbits, |p, n| p.hypot(other.into(), n))
+            }
+
+            fn sin(self) -> Self {
+                apply(self, $nbits, Posit::sin)
+            }
+
+            fn cos(self) -> Self {
+                apply(self, $nbits, Posit::cos)
+            }
+
+            fn tan(self) -> Self {
+                apply(self, $nbits, Posit::tan)
+            }
+
+            fn asin(self) -> Self {
+                apply(self, $nbits, Posit::asin)
+            }
+
+            fn acos(self) -> Self {
+                apply(self, $nbits, Posit::acos)
+            }
+
+            fn atan(self) -> Self {
+                apply(self, $nbits, Posit::atan)
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                apply(self, $nbits, |p, n| p.atan2(other.into(), n))
+            }
+
+            fn sin_cos(self) -> (Self, Self) {
+                (Float::sin(self), Float::cos(self))
+            }
+
+            fn exp_m1(self) -> Self {
+                apply(self, $nbits, Posit::exp_m1)
+            }
+
+            fn ln_1p(self) -> Self {
+                apply(self, $nbits, Posit::ln_1p)
+            }
+
+            fn sinh(self) -> Self {
+                apply(self, $nbits, Posit::sinh)
+            }
+
+            fn cosh(self) -> Self {
+                apply(self, $nbits, Posit::cosh)
+            }
+
+            fn tanh(self) -> Self {
+                apply(self, $nbits, Posit::tanh)
+            }
+
+            fn asinh(self) -> Self {
+                apply(self, $nbits, Posit::asinh)
+            }
+
+            fn acosh(self) -> Self {
+                apply(self, $nbits, Posit::acosh)
+            }
+
+            fn atanh(self) -> Self {
+                apply(self, $nbits, Posit::atanh)
+            }
+
+            /// NaR decodes as zero with a negative sign.
+            fn integer_decode(self) -> (u64, i16, i8) {
+                let sign = if self.is_negative() { -1 } else { 1 };
+                match Posit::from(self).decode() {
+                    None => (0, 0, sign),
+                    Some(decoded) => {
+                        let mantissa = decoded
+                            .fraction
+                            .iter()
+                            .fold(1u64, |m, bit| m << 1 | bit as u64);
+                        let exponent = decoded.scale() - decoded.fraction.len() as i64;
+                        (mantissa, exponent as i16, sign)
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+fixed_traits!(P8(8), P16(16), P32(32), P64(64));
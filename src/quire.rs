@@ -0,0 +1,229 @@
+use crate::natural::Natural;
+use crate::{context, Posit};
+
+/// Exact accumulator for sums of posits and their products.
+///
+/// The value is kept as a two's complement fixed-point number which grows as needed, so
+/// nothing is lost until the result is rounded back to a posit with [`Quire::to_posit`].
+#[derive(Clone, Debug)]
+pub struct Quire<const ES: usize = 2> {
+    /// Two's complement value, least significant limb first
+    limbs: Vec<u64>,
+    /// Binary scale of the least significant bit of `limbs`
+    lsb: isize,
+    nar: bool,
+}
+
+impl<const ES: usize> Default for Quire<ES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const ES: usize> Quire<ES> {
+    /// Quire holding zero.
+    pub fn new() -> Self {
+        Quire {
+            limbs: vec![],
+            lsb: 0,
+            nar: false,
+        }
+    }
+
+    /// Exact value of `a * b + c`.
+    pub fn fma(a: &Posit<ES>, b: &Posit<ES>, c: &Posit<ES>) -> Self {
+        let mut quire = Self::new();
+        quire.add_product(a, b);
+        quire.add_posit(c);
+        quire
+    }
+
+    /// Exact dot product of two vectors. Missing elements of the shorter one are zeros.
+    pub fn fdp(a: &[Posit<ES>], b: &[Posit<ES>]) -> Self {
+        let mut quire = Self::new();
+        for (a, b) in a.iter().zip(b) {
+            quire.add_product(a, b);
+        }
+        quire
+    }
+
+    pub fn is_nar(&self) -> bool {
+        self.nar
+    }
+
+    pub fn is_zero(&self) -> bool {
+        !self.nar && self.limbs.iter().all(|&i| i == 0)
+    }
+
+    pub fn add_posit(&mut self, p: &Posit<ES>) {
+        self.accumulate(p, false);
+    }
+
+    pub fn sub_posit(&mut self, p: &Posit<ES>) {
+        self.accumulate(p, true);
+    }
+
+    pub fn add_product(&mut self, a: &Posit<ES>, b: &Posit<ES>) {
+        self.accumulate(&a.multiply(b), false);
+    }
+
+    pub fn sub_product(&mut self, a: &Posit<ES>, b: &Posit<ES>) {
+        self.accumulate(&a.multiply(b), true);
+    }
+
+    /// Rounds the accumulated value to a posit of `nbits` bits, ties to even.
+    pub fn to_posit(&self, nbits: usize) -> Posit<ES> {
+        if self.nar {
+            return Posit::nar();
+        }
+        if self.is_zero() {
+            return Posit::zero();
+        }
+
+        let sign = self.is_negative();
+        let mut magnitude = self.limbs.clone();
+        if sign {
+            negate(&mut magnitude);
+        }
+        let magnitude = Natural::from_limbs(magnitude);
+        let top = magnitude.bits() - 1;
+
+        // Enough fraction bits for the rounding, the rest only matters as a sticky bit
+        let dropped = top.saturating_sub(nbits + 1);
+        let mut kept = &magnitude >> dropped;
+        let mut frac_bits = top - dropped;
+        if &kept << dropped != magnitude {
+            kept = &(&kept << 1) + &Natural::power_of_two(0);
+            frac_bits += 1;
+        }
+
+        let scale = self.lsb as i64 + top as i64;
+        let mut result = Posit::pack(sign, scale, &kept, frac_bits);
+        result.round_to(nbits);
+        result
+    }
+
+    fn is_negative(&self) -> bool {
+        self.limbs.last().is_some_and(|&i| i >> 63 != 0)
+    }
+
+    /// Adds or subtracts a posit exactly.
+    fn accumulate(&mut self, p: &Posit<ES>, subtract: bool) {
+        if p.is_nar() {
+            self.nar = true;
+        }
+        if self.nar || p.is_zero() {
+            return;
+        }
+
+        // Significand with the hidden bit as an integer
+        let (scale, sig, frac_bits) = p.unpack();
+        let lsb = scale as isize - frac_bits as isize;
+
+        if self.limbs.is_empty() {
+            self.lsb = lsb;
+        } else if lsb < self.lsb {
+            self.shift_left((self.lsb - lsb) as usize);
+            self.lsb = lsb;
+        }
+
+        // Align the significand and leave a limb of room for the sign and a carry
+        let offset = (lsb - self.lsb) as usize;
+        let len = (offset + frac_bits + 1).div_ceil(64);
+        self.sign_extend(len.max(self.limbs.len()) + 1);
+        let mut addend = vec![0; self.limbs.len()];
+        let aligned = &sig << offset;
+        addend[..aligned.limbs().len()].copy_from_slice(aligned.limbs());
+        if p.is_negative() != subtract {
+            negate(&mut addend);
+        }
+
+        let mut carry = false;
+        for (limb, addend) in self.limbs.iter_mut().zip(addend) {
+            let (sum, c1) = limb.overflowing_add(addend);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        self.normalize();
+    }
+
+    /// Extends the accumulator to `len` limbs keeping its value.
+    fn sign_extend(&mut self, len: usize) {
+        let fill = if self.is_negative() { u64::MAX } else { 0 };
+        if self.limbs.len() < len {
+            self.limbs.resize(len, fill);
+        }
+    }
+
+    /// Multiplies the accumulator by `2^shift`, keeping its value by moving the scale.
+    fn shift_left(&mut self, shift: usize) {
+        let fill = if self.is_negative() { u64::MAX } else { 0 };
+        let (limbs, bits) = (shift / 64, shift % 64);
+        if bits > 0 {
+            let mut carry = 0;
+            for limb in self.limbs.iter_mut() {
+                let next = *limb >> (64 - bits);
+                *limb = (*limb << bits) | carry;
+                carry = next;
+            }
+            self.limbs.push((fill << bits) | carry);
+        }
+        self.limbs.splice(0..0, vec![0; limbs]);
+    }
+
+    /// Removes redundant sign extension limbs.
+    fn normalize(&mut self) {
+        while self.limbs.len() > 1 {
+            let last = self.limbs[self.limbs.len() - 1];
+            let prev = self.limbs[self.limbs.len() - 2];
+            if (last == 0 && prev >> 63 == 0) || (last == u64::MAX && prev >> 63 != 0) {
+                self.limbs.pop();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// Negates a two's complement number in place.
+fn negate(limbs: &mut [u64]) {
+    let mut carry = true;
+    for limb in limbs.iter_mut() {
+        let (sum, c) = (!*limb).overflowing_add(carry as u64);
+        *limb = sum;
+        carry = c;
+    }
+}
+
+impl<const ES: usize> Posit<ES> {
+    /// `self * a + b` rounded once as the current [`PositContext`](crate::PositContext)
+    /// requires, exact without a width.
+    pub fn mul_add(self, a: Self, b: Self) -> Self {
+        let fused = {
+            let _exact = context::exact();
+            self * a + b
+        };
+        fused.in_context()
+    }
+
+    /// `self * a + b` rounded once to `nbits` bits, ties to even.
+    pub fn mul_add_rounded(self, a: Self, b: Self, nbits: usize) -> Self {
+        Quire::fma(&self, &a, &b).to_posit(nbits)
+    }
+
+    /// Dot product of two vectors rounded once to `nbits` bits, ties to even. Missing
+    /// elements of the shorter one are zeros.
+    pub fn fdp(a: &[Self], b: &[Self], nbits: usize) -> Self {
+        Quire::fdp(a, b).to_posit(nbits)
+    }
+
+    /// Sum rounded once to `nbits` bits, ties to even.
+    pub fn fsum(values: &[Self], nbits: usize) -> Self {
+        let mut quire = Quire::new();
+        for p in values {
+            quire.add_posit(p);
+        }
+        quire.to_posit(nbits)
+    }
+}
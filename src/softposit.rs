@@ -0,0 +1,520 @@
+//! C API compatible with SoftPosit, over posits passed by value.
+//!
+//! The types and function names are those of SoftPosit, so that C code written against it
+//! links against this library unchanged. As there, `posit8_t` has no exponent bits,
+//! `posit16_t` one and `posit32_t` two. Every operation rounds to the nearest value, ties to
+//! even, and none of them allocates.
+//!
+//! A panic is stopped at the boundary and described by `unum_last_error`. The function
+//! then returns NaR, NaN, false, zero for `pX_cmp` or the value it gives for NaR.
+
+#![allow(non_camel_case_types, non_snake_case)]
+
+use std::cmp::Ordering;
+
+use crate::exports::guard;
+use crate::fixed::{self, Unpacked};
+
+/// 8-bit posit with no exponent bits, as its two's complement bit pattern.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct posit8_t {
+    pub v: u8,
+}
+
+/// 16-bit posit with 1 exponent bit, as its two's complement bit pattern.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct posit16_t {
+    pub v: u16,
+}
+
+/// 32-bit posit with 2 exponent bits, as its two's complement bit pattern.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct posit32_t {
+    pub v: u32,
+}
+
+/// Width and exponent size of a SoftPosit type, with its operations on bit patterns stored
+/// in the low bits of a `u64`.
+#[derive(Clone, Copy, Debug)]
+struct Format {
+    n: u32,
+    es: u32,
+}
+
+const SP8_FORMAT: Format = Format { n: 8, es: 0 };
+const SP16_FORMAT: Format = Format { n: 16, es: 1 };
+const SP32_FORMAT: Format = Format { n: 32, es: 2 };
+
+/// Results of the functions stopping a panic.
+const NAR8: posit8_t = posit8_t { v: 1 << 7 };
+const NAR16: posit16_t = posit16_t { v: 1 << 15 };
+const NAR32: posit32_t = posit32_t { v: 1 << 31 };
+
+impl Format {
+    fn nar(self) -> u64 {
+        1 << (self.n - 1)
+    }
+
+    /// Sign-extends the pattern, so that posits compare as integers.
+    fn signed(self, bits: u64) -> i64 {
+        ((bits << (64 - self.n)) as i64) >> (64 - self.n)
+    }
+
+    fn unpack(self, bits: u64) -> Unpacked {
+        fixed::decode(self.n, self.es, bits)
+    }
+
+    fn pack(self, sign: bool, scale: i32, sig: u64, sticky: bool) -> u64 {
+        fixed::encode(self.n, self.es, sign, scale, (sig as u128) << 64, sticky)
+    }
+
+    fn add(self, lhs: u64, rhs: u64, sub: bool) -> u64 {
+        if lhs == self.nar() || rhs == self.nar() {
+            return self.nar();
+        }
+        if rhs == 0 {
+            return lhs;
+        }
+        if lhs == 0 {
+            return if sub { self.neg(rhs) } else { rhs };
+        }
+        fixed::add(self.n, self.es, self.unpack(lhs), self.unpack(rhs), sub)
+    }
+
+    fn neg(self, bits: u64) -> u64 {
+        bits.wrapping_neg() & (u64::MAX >> (64 - self.n))
+    }
+
+    fn mul(self, lhs: u64, rhs: u64) -> u64 {
+        if lhs == self.nar() || rhs == self.nar() {
+            return self.nar();
+        }
+        if lhs == 0 || rhs == 0 {
+            return 0;
+        }
+        fixed::mul(self.n, self.es, self.unpack(lhs), self.unpack(rhs))
+    }
+
+    fn div(self, lhs: u64, rhs: u64) -> u64 {
+        if lhs == self.nar() || rhs == self.nar() || rhs == 0 {
+            return self.nar();
+        }
+        if lhs == 0 {
+            return 0;
+        }
+        fixed::div(self.n, self.es, self.unpack(lhs), self.unpack(rhs))
+    }
+
+    /// `a * b + c` rounded once. The product of two posits of at most 32 bits fits the 64
+    /// bits of an unpacked significand exactly.
+    fn mul_add(self, a: u64, b: u64, c: u64) -> u64 {
+        if a == self.nar() || b == self.nar() || c == self.nar() {
+            return self.nar();
+        }
+        if a == 0 || b == 0 {
+            return c;
+        }
+        let (a, b) = (self.unpack(a), self.unpack(b));
+        let sig = a.sig as u128 * b.sig as u128;
+        let shift = (sig >> 127) as u32;
+        let product = Unpacked {
+            sign: a.sign != b.sign,
+            scale: a.scale + b.scale + shift as i32,
+            sig: ((sig << (1 - shift)) >> 64) as u64,
+        };
+        if c == 0 {
+            return self.pack(product.sign, product.scale, product.sig, false);
+        }
+        fixed::add(self.n, self.es, product, self.unpack(c), false)
+    }
+
+    fn sqrt(self, bits: u64) -> u64 {
+        if bits == 0 {
+            return 0;
+        }
+        if bits & self.nar() != 0 {
+            return self.nar();
+        }
+        // An even scale, so that the root of the significand is in [2^63, 2^64)
+        let x = self.unpack(bits);
+        let odd = x.scale.rem_euclid(2);
+        let square = (x.sig as u128) << (63 + odd);
+        let root = square.isqrt();
+        self.pack(
+            false,
+            (x.scale - odd) / 2,
+            root as u64,
+            root * root != square,
+        )
+    }
+
+    /// Magnitude rounded to the nearest integer, ties to even, `None` if it is too big.
+    fn round_magnitude(self, bits: u64) -> Option<u128> {
+        let x = self.unpack(bits);
+        match x.scale {
+            scale if scale < -1 => Some(0),
+            // Only exactly one half rounds down to zero
+            -1 => Some((x.sig << 1 != 0) as u128),
+            scale if scale < 63 => {
+                let int = x.sig >> (63 - scale);
+                let frac = x.sig << (scale + 1);
+                let up = frac >> 63 != 0 && (frac << 1 != 0 || int & 1 != 0);
+                Some(int as u128 + up as u128)
+            }
+            scale if scale < 128 => Some((x.sig as u128) << (scale - 63)),
+            _ => None,
+        }
+    }
+
+    fn round_to_int(self, bits: u64) -> u64 {
+        if bits == 0 || bits == self.nar() {
+            return bits;
+        }
+        // The significand has at most 30 bits, so such a posit is an integer already
+        if self.unpack(bits).scale >= 63 {
+            return bits;
+        }
+        let magnitude = self.round_magnitude(bits).unwrap() as u64;
+        self.encode_int(bits & self.nar() != 0, magnitude)
+    }
+
+    /// Integer nearest to the posit, saturating to `[min, max]`. NaR gives `nar`.
+    fn to_int(self, bits: u64, min: i128, max: i128, nar: i128) -> i128 {
+        if bits == 0 {
+            return 0;
+        }
+        if bits == self.nar() {
+            return nar;
+        }
+        let magnitude = self
+            .round_magnitude(bits)
+            .map_or(i128::MAX, |m| m.min(i128::MAX as u128) as i128);
+        if bits & self.nar() != 0 {
+            (-magnitude).max(min)
+        } else {
+            magnitude.min(max)
+        }
+    }
+
+    fn encode_int(self, negative: bool, magnitude: u64) -> u64 {
+        if magnitude == 0 {
+            return 0;
+        }
+        let shift = magnitude.leading_zeros();
+        self.pack(negative, 63 - shift as i32, magnitude << shift, false)
+    }
+
+    fn to_f64(self, bits: u64) -> f64 {
+        if bits == 0 {
+            return 0.0;
+        }
+        if bits == self.nar() {
+            return f64::NAN;
+        }
+        // Exact, the significand has at most 30 bits and the scale at most 120
+        let x = self.unpack(bits);
+        let magnitude = x.sig as f64 * 2f64.powi(x.scale - 63);
+        if x.sign {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    fn encode_f64(self, x: f64) -> u64 {
+        if !x.is_finite() {
+            return self.nar();
+        }
+        if x == 0.0 {
+            return 0;
+        }
+        let bits = x.to_bits();
+        let exp = ((bits >> 52) & 0x7ff) as i32;
+        let mantissa = bits & ((1 << 52) - 1);
+        let (scale, sig) = if exp == 0 {
+            let shift = mantissa.leading_zeros();
+            (-1011 - shift as i32, mantissa << shift)
+        } else {
+            (exp - 1023, (1 << 63) | (mantissa << 11))
+        };
+        self.pack(x < 0.0, scale, sig, false)
+    }
+
+    /// Rounds a pattern of this type to the nearest value of `to`.
+    fn convert(self, bits: u64, to: Format) -> u64 {
+        if bits == 0 {
+            return 0;
+        }
+        if bits == self.nar() {
+            return to.nar();
+        }
+        let x = self.unpack(bits);
+        to.pack(x.sign, x.scale, x.sig, false)
+    }
+
+    fn cmp(self, lhs: u64, rhs: u64) -> Ordering {
+        self.signed(lhs).cmp(&self.signed(rhs))
+    }
+}
+
+/// The SoftPosit functions of one type, `$type` of format `$format`, under their C names.
+///
+/// cbindgen cannot expand macros, so `build.rs` declares these functions in the header from
+/// the same list of names.
+macro_rules! softposit_api {
+    (
+        $type: ident($bits: ty), $format: ident, $nar: ident, $names: ident {
+            $cast: ident, $add: ident, $sub: ident, $mul: ident, $div: ident, $mul_add: ident,
+            $sqrt: ident, $round_to_int: ident, $eq: ident, $le: ident, $lt: ident, $cmp: ident,
+            $to_i32: ident, $to_i64: ident, $to_ui32: ident, $to_ui64: ident,
+            $to_f64: ident, $from_f64: ident, $to_double: ident, $from_double: ident,
+            $from_i32: ident, $from_i64: ident, $from_ui32: ident, $from_ui64: ident,
+            $($doc: literal $convert: ident -> $to: ident($to_bits: ty), $to_format: ident, $to_nar: ident),*
+        }
+    ) => {
+        /// Posit with the given bit pattern.
+        #[no_mangle]
+        pub extern "C" fn $cast(a: $bits) -> $type {
+            $type { v: a }
+        }
+
+        /// Sum.
+        #[no_mangle]
+        pub extern "C" fn $add(a: $type, b: $type) -> $type {
+            guard($nar, || $type {
+                v: $format.add(a.v.into(), b.v.into(), false) as $bits,
+            })
+        }
+
+        /// Difference.
+        #[no_mangle]
+        pub extern "C" fn $sub(a: $type, b: $type) -> $type {
+            guard($nar, || $type {
+                v: $format.add(a.v.into(), b.v.into(), true) as $bits,
+            })
+        }
+
+        /// Product.
+        #[no_mangle]
+        pub extern "C" fn $mul(a: $type, b: $type) -> $type {
+            guard($nar, || $type {
+                v: $format.mul(a.v.into(), b.v.into()) as $bits,
+            })
+        }
+
+        /// Quotient, NaR for a zero divisor.
+        #[no_mangle]
+        pub extern "C" fn $div(a: $type, b: $type) -> $type {
+            guard($nar, || $type {
+                v: $format.div(a.v.into(), b.v.into()) as $bits,
+            })
+        }
+
+        /// `a * b + c` rounded once.
+        #[no_mangle]
+        pub extern "C" fn $mul_add(a: $type, b: $type, c: $type) -> $type {
+            guard($nar, || $type {
+                v: $format.mul_add(a.v.into(), b.v.into(), c.v.into()) as $bits,
+            })
+        }
+
+        /// Square root, NaR for negative values.
+        #[no_mangle]
+        pub extern "C" fn $sqrt(a: $type) -> $type {
+            guard($nar, || $type {
+                v: $format.sqrt(a.v.into()) as $bits,
+            })
+        }
+
+        /// Nearest integer, ties to even.
+        #[no_mangle]
+        pub extern "C" fn $round_to_int(a: $type) -> $type {
+            guard($nar, || $type {
+                v: $format.round_to_int(a.v.into()) as $bits,
+            })
+        }
+
+        /// Whether `a == b`, NaR is only equal to itself.
+        #[no_mangle]
+        pub extern "C" fn $eq(a: $type, b: $type) -> bool {
+            a.v == b.v
+        }
+
+        /// Whether `a <= b`, NaR is below every other value.
+        #[no_mangle]
+        pub extern "C" fn $le(a: $type, b: $type) -> bool {
+            guard(false, || {
+                $format.cmp(a.v.into(), b.v.into()) != Ordering::Greater
+            })
+        }
+
+        /// Whether `a < b`, NaR is below every other value.
+        #[no_mangle]
+        pub extern "C" fn $lt(a: $type, b: $type) -> bool {
+            guard(false, || {
+                $format.cmp(a.v.into(), b.v.into()) == Ordering::Less
+            })
+        }
+
+        /// -1, 0 or 1 as `a` is below, equal to or above `b`. NaR is below every other value.
+        #[no_mangle]
+        pub extern "C" fn $cmp(a: $type, b: $type) -> i32 {
+            guard(0, || $format.cmp(a.v.into(), b.v.into()) as i32)
+        }
+
+        /// Nearest integer, ties to even. NaR gives `INT32_MIN`, out of range values saturate.
+        #[no_mangle]
+        pub extern "C" fn $to_i32(a: $type) -> i32 {
+            guard(i32::MIN, || {
+                let (min, max) = (i32::MIN.into(), i32::MAX.into());
+                $format.to_int(a.v.into(), min, max, min) as i32
+            })
+        }
+
+        /// Nearest integer, ties to even. NaR gives `INT64_MIN`, out of range values saturate.
+        #[no_mangle]
+        pub extern "C" fn $to_i64(a: $type) -> i64 {
+            guard(i64::MIN, || {
+                let (min, max) = (i64::MIN.into(), i64::MAX.into());
+                $format.to_int(a.v.into(), min, max, min) as i64
+            })
+        }
+
+        /// Nearest integer, ties to even. NaR gives `0x80000000`, negative values give zero.
+        #[no_mangle]
+        pub extern "C" fn $to_ui32(a: $type) -> u32 {
+            guard(1 << 31, || {
+                $format.to_int(a.v.into(), 0, u32::MAX.into(), 1 << 31) as u32
+            })
+        }
+
+        /// Nearest integer, ties to even. NaR gives `0x8000000000000000`, negative values give
+        /// zero.
+        #[no_mangle]
+        pub extern "C" fn $to_ui64(a: $type) -> u64 {
+            guard(1 << 63, || {
+                $format.to_int(a.v.into(), 0, u64::MAX.into(), 1 << 63) as u64
+            })
+        }
+
+        $(
+            #[doc = $doc]
+            #[no_mangle]
+            pub extern "C" fn $convert(a: $type) -> $to {
+                guard($to_nar, || $to {
+                    v: $format.convert(a.v.into(), $to_format) as $to_bits,
+                })
+            }
+        )*
+
+        /// Exact value, NaN for NaR.
+        #[no_mangle]
+        pub extern "C" fn $to_f64(a: $type) -> f64 {
+            guard(f64::NAN, || $format.to_f64(a.v.into()))
+        }
+
+        /// Nearest posit, NaR for NaN and the infinities.
+        #[no_mangle]
+        pub extern "C" fn $from_f64(a: f64) -> $type {
+            guard($nar, || $type {
+                v: $format.encode_f64(a) as $bits,
+            })
+        }
+
+        #[doc = concat!("Same as `", stringify!($to_f64), "`.")]
+        #[no_mangle]
+        pub extern "C" fn $to_double(a: $type) -> f64 {
+            $to_f64(a)
+        }
+
+        #[doc = concat!("Same as `", stringify!($from_f64), "`.")]
+        #[no_mangle]
+        pub extern "C" fn $from_double(a: f64) -> $type {
+            $from_f64(a)
+        }
+
+        /// Nearest posit.
+        #[no_mangle]
+        pub extern "C" fn $from_i32(a: i32) -> $type {
+            guard($nar, || $type {
+                v: $format.encode_int(a < 0, a.unsigned_abs().into()) as $bits,
+            })
+        }
+
+        /// Nearest posit.
+        #[no_mangle]
+        pub extern "C" fn $from_i64(a: i64) -> $type {
+            guard($nar, || $type {
+                v: $format.encode_int(a < 0, a.unsigned_abs()) as $bits,
+            })
+        }
+
+        /// Nearest posit.
+        #[no_mangle]
+        pub extern "C" fn $from_ui32(a: u32) -> $type {
+            guard($nar, || $type {
+                v: $format.encode_int(false, a.into()) as $bits,
+            })
+        }
+
+        /// Nearest posit.
+        #[no_mangle]
+        pub extern "C" fn $from_ui64(a: u64) -> $type {
+            guard($nar, || $type {
+                v: $format.encode_int(false, a) as $bits,
+            })
+        }
+
+        /// C names of the functions, which the header has to declare.
+        #[cfg(test)]
+        pub(crate) const $names: &[&str] = &[
+            stringify!($cast), stringify!($add), stringify!($sub), stringify!($mul),
+            stringify!($div), stringify!($mul_add), stringify!($sqrt),
+            stringify!($round_to_int), stringify!($eq), stringify!($le), stringify!($lt),
+            stringify!($cmp), stringify!($to_i32), stringify!($to_i64), stringify!($to_ui32),
+            stringify!($to_ui64), $(stringify!($convert),)* stringify!($to_f64),
+            stringify!($from_f64), stringify!($to_double), stringify!($from_double),
+            stringify!($from_i32), stringify!($from_i64), stringify!($from_ui32),
+            stringify!($from_ui64),
+        ];
+    };
+}
+
+softposit_api! {
+    posit8_t(u8), SP8_FORMAT, NAR8, SP8_FUNCTIONS {
+        castP8, p8_add, p8_sub, p8_mul, p8_div, p8_mulAdd,
+        p8_sqrt, p8_roundToInt, p8_eq, p8_le, p8_lt, p8_cmp,
+        p8_to_i32, p8_to_i64, p8_to_ui32, p8_to_ui64,
+        p8_to_f64, p8_from_f64, convertP8ToDouble, convertDoubleToP8,
+        i32_to_p8, i64_to_p8, ui32_to_p8, ui64_to_p8,
+        "Exact 16-bit posit." p8_to_p16 -> posit16_t(u16), SP16_FORMAT, NAR16,
+        "Exact 32-bit posit." p8_to_p32 -> posit32_t(u32), SP32_FORMAT, NAR32
+    }
+}
+
+softposit_api! {
+    posit16_t(u16), SP16_FORMAT, NAR16, SP16_FUNCTIONS {
+        castP16, p16_add, p16_sub, p16_mul, p16_div, p16_mulAdd,
+        p16_sqrt, p16_roundToInt, p16_eq, p16_le, p16_lt, p16_cmp,
+        p16_to_i32, p16_to_i64, p16_to_ui32, p16_to_ui64,
+        p16_to_f64, p16_from_f64, convertP16ToDouble, convertDoubleToP16,
+        i32_to_p16, i64_to_p16, ui32_to_p16, ui64_to_p16,
+        "Nearest 8-bit posit." p16_to_p8 -> posit8_t(u8), SP8_FORMAT, NAR8,
+        "Exact 32-bit posit." p16_to_p32 -> posit32_t(u32), SP32_FORMAT, NAR32
+    }
+}
+
+softposit_api! {
+    posit32_t(u32), SP32_FORMAT, NAR32, SP32_FUNCTIONS {
+        castP32, p32_add, p32_sub, p32_mul, p32_div, p32_mulAdd,
+        p32_sqrt, p32_roundToInt, p32_eq, p32_le, p32_lt, p32_cmp,
+        p32_to_i32, p32_to_i64, p32_to_ui32, p32_to_ui64,
+        p32_to_f64, p32_from_f64, convertP32ToDouble, convertDoubleToP32,
+        i32_to_p32, i64_to_p32, ui32_to_p32, ui64_to_p32,
+        "Nearest 8-bit posit." p32_to_p8 -> posit8_t(u8), SP8_FORMAT, NAR8,
+        "Nearest 16-bit posit." p32_to_p16 -> posit16_t(u16), SP16_FORMAT, NAR16
+    }
+}
@@ -0,0 +1,171 @@
+use std::panic;
+use std::thread;
+
+use num_traits::Float;
+
+use crate::{
+    with_precision, Interval, PositContext, Rounding, TestPosit as Posit, Valid, P16, P64, P8,
+};
+
+fn posit(x: f64) -> Posit {
+    Posit::from(x)
+}
+
+fn rounded(x: &Posit, nbits: usize) -> Posit {
+    let mut p = x.clone();
+    p.round_to(nbits);
+    p
+}
+
+#[test]
+fn exact_by_default() {
+    assert_eq!(PositContext::current(), PositContext::default());
+    let x = posit(1.) + posit(2f64.powi(-40));
+    assert_eq!(x.to_f64(), 1. + 2f64.powi(-40));
+    assert!(posit(1.1).pow(8).bits.len() > 64);
+}
+
+#[test]
+fn precision() {
+    let tiny = posit(2f64.powi(-40));
+    let sum = with_precision(16, || posit(1.) + &tiny);
+    assert_eq!(sum, posit(1.));
+    let product = with_precision(16, || posit(1.1) * posit(1.1));
+    assert_eq!(product, rounded(&(posit(1.1) * posit(1.1)), 16));
+    let third = with_precision(16, || Posit::one() / posit(3.));
+    assert_eq!(third, Posit::one().div_rounded(posit(3.), 16));
+    let rem = with_precision(8, || posit(100.25) % posit(3.));
+    assert_eq!(rem, posit(1.25));
+
+    // Repeated squaring stays bounded
+    let power = with_precision(32, || posit(1.1).pow(1000));
+    assert!(power.bits.len() <= 32);
+    let mut x = posit(1.1);
+    with_precision(32, || {
+        for _ in 0..100 {
+            x *= posit(1.1);
+            x -= posit(0.01);
+        }
+    });
+    assert!(x.bits.len() <= 32);
+}
+
+#[test]
+fn rounding() {
+    let context = |rounding| PositContext {
+        nbits: Some(8),
+        only_es: None,
+        rounding,
+    };
+    let third = |rounding| context(rounding).scope(|| Posit::one() / posit(3.));
+    assert_eq!(
+        third(Rounding::Down),
+        Posit::one().div_rounded_down(posit(3.), 8)
+    );
+    assert_eq!(
+        third(Rounding::Up),
+        Posit::one().div_rounded_up(posit(3.), 8)
+    );
+    assert_eq!(third(Rounding::Down).next_up(8), third(Rounding::Up));
+
+    let maxpos = Posit::maxpos(8);
+    let double = |rounding| context(rounding).scope(|| &maxpos + &maxpos);
+    assert_eq!(double(Rounding::Nearest), maxpos);
+    assert_eq!(double(Rounding::Down), maxpos);
+    assert!(double(Rounding::Up).is_nar());
+}
+
+#[test]
+fn scopes() {
+    with_precision(16, || {
+        assert_eq!(PositContext::current().nbits, Some(16));
+        with_precision(8, || assert_eq!(PositContext::current().nbits, Some(8)));
+        assert_eq!(PositContext::current().nbits, Some(16));
+    });
+    assert_eq!(PositContext::current().nbits, None);
+
+    // Restored when unwinding
+    let result = panic::catch_unwind(|| with_precision(8, || panic!("inside")));
+    assert!(result.is_err());
+    assert_eq!(PositContext::current(), PositContext::default());
+
+    // Other threads keep their own context
+    PositContext {
+        rounding: Rounding::Up,
+        ..PositContext::default()
+    }
+    .set_current();
+    with_precision(8, || {
+        let context = thread::spawn(PositContext::current).join().unwrap();
+        assert_eq!(context, PositContext::default());
+        assert_eq!(PositContext::current().rounding, Rounding::Up);
+    });
+    PositContext::default().set_current();
+}
+
+#[test]
+fn exponent_size() {
+    let context = PositContext {
+        nbits: Some(8),
+        only_es: Some(0),
+        rounding: Rounding::Nearest,
+    };
+    let tiny = 2f64.powi(-20);
+    let (two, zero) = context.scope(|| {
+        (
+            posit(1.) + posit(tiny),
+            crate::Posit::<0>::from(1.) + crate::Posit::<0>::from(tiny),
+        )
+    });
+    assert_eq!(two.to_f64(), 1. + tiny);
+    assert_eq!(zero, crate::Posit::<0>::one());
+}
+
+#[test]
+#[should_panic(expected = "at least 2 bits")]
+fn invalid_precision() {
+    with_precision(1, || ());
+}
+
+#[test]
+fn explicit_lengths() {
+    // Methods with their own length and interval types ignore the context
+    let x = posit(2.);
+    let root = x.clone().sqrt(64);
+    assert_eq!(with_precision(8, || x.clone().sqrt(64)), root);
+    let exp = x.clone().exp(64);
+    assert_eq!(with_precision(8, || x.clone().exp(64)), exp);
+    let sum = x.clone().add_rounded(posit(2f64.powi(-30)), 64);
+    assert_eq!(
+        with_precision(8, || x.clone().add_rounded(posit(2f64.powi(-30)), 64)),
+        sum
+    );
+    assert_eq!(with_precision(8, || posit(2.5).floor()), posit(2.));
+
+    let hi = posit(1.) + posit(2f64.powi(-30));
+    let valid = || {
+        let v = Valid::closed(posit(1.), hi.clone());
+        (v.clone() * v).width()
+    };
+    assert_eq!(with_precision(8, valid), valid());
+    let interval = || {
+        let i = Interval::new(posit(1.), hi.clone());
+        i.clone().div_rounded(i, 32)
+    };
+    assert_eq!(with_precision(8, interval), interval());
+}
+
+#[test]
+fn fixed_width() {
+    // Fixed-width posits round to their own width only
+    let three = P64::from(posit(3.));
+    assert_eq!(with_precision(8, || three.powi(3)), P64::from(posit(27.)));
+    assert_eq!(with_precision(8, || three.powi(-3)), three.powi(-3));
+    assert_eq!(with_precision(8, || (-three).powf(three)), P64::from(posit(-27.)));
+    assert_eq!(with_precision(8, || three.recip()), three.recip());
+    assert_eq!(with_precision(8, || three.sqrt()), three.sqrt());
+    assert_eq!(with_precision(8, || three * three + P64::ONE), P64::from(posit(10.)));
+    let x = P16::from(posit(1.1));
+    assert_eq!(with_precision(2, || x.exp().to_string()), x.exp().to_string());
+    assert_eq!(with_precision(4, || P8::MAXPOS.ln()), P8::MAXPOS.ln());
+}
@@ -0,0 +1,158 @@
+use std::convert::TryFrom;
+
+use proptest::prelude::*;
+
+use crate::TestPosit as Posit;
+
+/// Exact sum of powers of two.
+fn pow2_sum(exponents: &[i32]) -> Posit {
+    exponents
+        .iter()
+        .fold(Posit::zero(), |sum, &e| sum + Posit::one().mul_pow2(e as isize))
+}
+
+macro_rules! test_float {
+    ($name: ident: $posit: expr => $f64: expr, $f32: expr) => {
+        #[test]
+        fn $name() {
+            let p: Posit = $posit;
+            assert_eq!(p.to_f64().to_bits(), ($f64 as f64).to_bits());
+            assert_eq!(p.to_f32().to_bits(), ($f32 as f32).to_bits());
+            assert_eq!((-p.clone()).to_f64().to_bits(), (-($f64 as f64)).to_bits());
+            assert_eq!((-p).to_f32().to_bits(), (-($f32 as f32)).to_bits());
+        }
+    };
+}
+
+test_float! { float_exact: Posit::from(1.5) => 1.5, 1.5 }
+test_float! { float_tie_even: pow2_sum(&[0, -53]) => 1.0, 1.0 }
+test_float! { float_tie_odd: pow2_sum(&[0, -52, -53]) => 1.0 + 2f64.powi(-51), 1.0 }
+test_float! { float_sticky: pow2_sum(&[0, -53, -80]) => 1.0 + 2f64.powi(-52), 1.0 }
+// Rounding through f64 first would give 1.0
+test_float! { float_single_rounding: pow2_sum(&[0, -24, -60]) => 1.0 + 2f64.powi(-24), 1.0 + 2f32.powi(-23) }
+test_float! { float_overflow: Posit::from(2.0).pow(1024) => f64::INFINITY, f32::INFINITY }
+test_float! { float_max_tie: pow2_sum(&[1024]) - pow2_sum(&[970]) => f64::INFINITY, f32::INFINITY }
+test_float! { float_f32_max: Posit::from(f32::MAX as f64) => f32::MAX, f32::MAX }
+test_float! { float_min_subnormal: pow2_sum(&[-1074]) => f64::from_bits(1), 0.0 }
+test_float! { float_underflow_tie: pow2_sum(&[-1075]) => 0.0, 0.0 }
+test_float! { float_above_underflow_tie: pow2_sum(&[-1075, -1100]) => f64::from_bits(1), 0.0 }
+test_float! { float_subnormal_tie: pow2_sum(&[-1074, -1075]) => f64::from_bits(2), 0.0 }
+test_float! { float_subnormal_carry: pow2_sum(&[-1022]) - pow2_sum(&[-1076]) => f64::MIN_POSITIVE, 0.0 }
+test_float! { float_f32_subnormal: pow2_sum(&[-149, -150, -200]) => 2f64.powi(-149) + 2f64.powi(-150) + 2f64.powi(-200), f32::from_bits(2) }
+#[test]
+fn float_special() {
+    assert_eq!(Posit::zero().to_f64().to_bits(), 0);
+    assert_eq!(Posit::zero().to_f32().to_bits(), 0);
+    assert!(Posit::nar().to_f64().is_nan());
+    assert!(Posit::nar().to_f32().is_nan());
+}
+
+#[test]
+fn float_es() {
+    let p = super::Posit::<0>::from(0.1);
+    assert_eq!(p.to_f64(), 0.1);
+    assert_eq!(p.to_f32(), 0.1);
+    let p = super::Posit::<4>::from(1e300);
+    assert_eq!(p.to_f64(), 1e300);
+}
+
+macro_rules! test_int {
+    ($name: ident: $int: ty) => {
+        #[test]
+        fn $name() {
+            for &i in &[<$int>::MIN, <$int>::MAX, 0, 1, <$int>::MAX / 3, <$int>::MIN / 7] {
+                let p: Posit = Posit::from(i);
+                assert_eq!(<$int>::try_from(p.clone()), Ok(i));
+                let es0: super::Posit<0> = super::Posit::from(i);
+                assert_eq!(<$int>::try_from(es0), Ok(i));
+                assert!(<$int>::try_from(p + Posit::from(0.5)).is_err());
+            }
+            assert!(<$int>::try_from(Posit::from(<$int>::MAX) + Posit::one()).is_err());
+            assert!(<$int>::try_from(Posit::from(<$int>::MIN) - Posit::one()).is_err());
+            assert!(<$int>::try_from(Posit::nar()).is_err());
+        }
+    };
+}
+
+test_int! { int_i8: i8 }
+test_int! { int_i16: i16 }
+test_int! { int_i32: i32 }
+test_int! { int_i64: i64 }
+test_int! { int_i128: i128 }
+test_int! { int_isize: isize }
+test_int! { int_u8: u8 }
+test_int! { int_u16: u16 }
+test_int! { int_u32: u32 }
+test_int! { int_u64: u64 }
+test_int! { int_u128: u128 }
+test_int! { int_usize: usize }
+
+#[test]
+fn int_exact() {
+    assert_eq!(Posit::from(u64::MAX), Posit::from(2f64.powi(64)) - Posit::one());
+    assert_eq!(Posit::from(-1000i16), Posit::from(-1000.0));
+    assert_eq!(Posit::from(i128::MIN), -Posit::from(2.0).pow(127));
+    assert_eq!(u8::try_from(Posit::zero()), Ok(0));
+    assert!(u8::try_from(Posit::from(-1)).is_err());
+    assert!(i64::try_from(Posit::from(2.0).pow(200)).is_err());
+}
+
+test_float! { float_max: Posit::from(f64::MAX) => f64::MAX, f32::INFINITY }
+
+#[test]
+fn from_f64_special() {
+    let one = Posit::one();
+    assert_eq!(Posit::from(0.0).bits, Posit::zero().bits);
+    assert_eq!(Posit::from(-0.0).bits, Posit::zero().bits);
+    assert!(Posit::from(f64::NAN).is_nar());
+    assert!(Posit::from(f64::NEG_INFINITY).is_nar());
+    assert_eq!(Posit::from(f64::MIN_POSITIVE), one.clone().mul_pow2(-1022));
+    assert_eq!(Posit::from(f64::from_bits(1)), one.clone().mul_pow2(-1074));
+    assert_eq!(Posit::from(-f64::from_bits(3)), -pow2_sum(&[-1073, -1074]));
+    let max_subnormal = one.clone().mul_pow2(-1022) - one.clone().mul_pow2(-1074);
+    assert_eq!(Posit::from(f64::from_bits(0xf_ffff_ffff_ffff)), max_subnormal);
+    let max = one.clone().mul_pow2(1024) - one.mul_pow2(971);
+    assert_eq!(Posit::from(f64::MAX), max.clone());
+    assert_eq!(Posit::from(f64::MIN), -max);
+}
+
+#[test]
+fn from_f64_regime() {
+    // The regime alone takes more than a thousand bits
+    let p = super::Posit::<0>::from(f64::from_bits(1));
+    assert_eq!(p.bits.len(), 1076);
+    assert_eq!(p.to_f64(), f64::from_bits(1));
+    let p = super::Posit::<0>::from(f64::MAX);
+    assert_eq!(p.to_f64(), f64::MAX);
+}
+
+fn finite() -> impl Strategy<Value = f64> {
+    use proptest::num::f64::*;
+    prop_oneof![
+        any::<u64>().prop_map(f64::from_bits).prop_filter("finite", |x| x.is_finite()),
+        POSITIVE | NEGATIVE | SUBNORMAL | ZERO,
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn f64_round_trip(x in finite()) {
+        prop_assert_eq!(Posit::from(x).to_f64(), x);
+        prop_assert_eq!(super::Posit::<0>::from(x).to_f64(), x);
+        prop_assert_eq!(super::Posit::<5>::from(x).to_f64(), x);
+    }
+
+    #[test]
+    fn f32_round_trip(bits in any::<u32>()) {
+        let x = f32::from_bits(bits);
+        prop_assume!(x.is_finite());
+        prop_assert_eq!(Posit::from(x as f64).to_f32(), x);
+    }
+
+    #[test]
+    fn f64_order(x in finite(), y in finite()) {
+        prop_assert_eq!(Posit::from(x).partial_cmp(&Posit::from(y)), x.partial_cmp(&y));
+    }
+}
@@ -0,0 +1,167 @@
+use proptest::prelude::*;
+
+use crate::{with_precision, TestPosit as Posit, P16, P32, P64, P8};
+
+fn parse(s: &str) -> Posit {
+    s.parse().unwrap()
+}
+
+fn rounded(mut p: Posit, nbits: usize) -> Posit {
+    p.round_to(nbits);
+    p
+}
+
+macro_rules! test_parse {
+    ($name: ident: $s: expr => $expected: expr) => {
+        #[test]
+        fn $name() {
+            let expected: Posit = $expected;
+            assert_eq!(parse($s), expected);
+        }
+    };
+}
+
+test_parse! { parse_integer: "1500" => Posit::from(1500) }
+test_parse! { parse_fraction: "0.375" => Posit::from(0.375) }
+test_parse! { parse_negative: "-2.5" => Posit::from(-2.5) }
+test_parse! { parse_plus: "+7" => Posit::from(7) }
+test_parse! { parse_leading_point: ".5" => Posit::from(0.5) }
+test_parse! { parse_trailing_point: "5." => Posit::from(5) }
+test_parse! { parse_exponent: "-1.5e3" => Posit::from(-1500) }
+test_parse! { parse_negative_exponent: "25E-2" => Posit::from(0.25) }
+test_parse! { parse_zero: "-0.000e10" => Posit::zero() }
+test_parse! { parse_nar: "NaR" => Posit::nar() }
+test_parse! { parse_huge: "1e400" => Posit::from(10).pow(400) }
+
+#[test]
+fn parse_errors() {
+    for s in &["", "-", ".", "e5", "1e", "1e+", "1.2.3", "1,5", " 1", "1 ", "0x1", "inf", "--1", "1e1.5"] {
+        assert!(s.parse::<Posit>().is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn parse_rounded() {
+    let tenth = Posit::from_str_rounded("0.1", 32).unwrap();
+    assert_eq!(tenth, rounded(Posit::from(0.1), 32));
+    assert_eq!(P32::from(tenth).to_bits(), 0x24cc_cccd);
+    // 1 + 1/16 is a tie between 1 and 1 + 1/8 in 8 bits
+    assert_eq!(Posit::from_str_rounded("1.0625", 8).unwrap(), Posit::one());
+    assert_eq!(Posit::from_str_rounded("1.0626", 8).unwrap(), Posit::from(1.125));
+    assert_eq!(Posit::from_str_rounded("1e30", 8).unwrap(), Posit::maxpos(8));
+    let tiny = Posit::one().div_rounded(Posit::from(10).pow(400), 64);
+    assert_eq!(Posit::from_str_rounded("1e-400", 64).unwrap(), tiny);
+}
+
+#[test]
+fn parse_extreme_exponents() {
+    let (min, max) = ("1e-9223372036854775808", "1e9223372036854775807");
+    assert_eq!(Posit::from_str_rounded(min, 32).unwrap(), Posit::minpos(32));
+    assert_eq!(Posit::from_str_rounded(max, 32).unwrap(), Posit::maxpos(32));
+    assert_eq!(Posit::from_str_rounded("-1e-100000", 32).unwrap(), -Posit::minpos(32));
+    assert_eq!(Posit::from_str_rounded("0e9223372036854775807", 32).unwrap(), Posit::zero());
+    // Too large to be exact, or past the range of the exponent
+    assert!(min.parse::<Posit>().is_err());
+    assert!(max.parse::<Posit>().is_err());
+    assert!("1e9223372036854775808".parse::<Posit>().is_err());
+    assert!(Posit::from_str_rounded("0.1e-9223372036854775808", 32).is_err());
+    // No posit has fewer than 2 bits
+    assert!(Posit::from_str_rounded("1", 1).is_err());
+    assert!(Posit::from_str_rounded("0", 0).is_err());
+    assert_eq!(Posit::from_str_rounded("-3", 2).unwrap(), -Posit::one());
+    let tiny = parse("1e-100000");
+    assert!(Posit::zero() < tiny && tiny < Posit::minpos(1000));
+}
+
+#[test]
+fn parse_inexact() {
+    let pi = "3.14159265358979323846264338327950288";
+    assert_eq!(parse(pi).to_string(), pi);
+    assert_eq!(parse("0.1").to_string(), "0.1");
+    assert_eq!(parse("-7.3e-20").to_string(), "-0.000000000000000000073");
+}
+
+macro_rules! test_format {
+    ($name: ident: $value: expr, $format: expr => $expected: expr) => {
+        #[test]
+        fn $name() {
+            let value: Posit = $value;
+            assert_eq!(format!($format, value), $expected);
+        }
+    };
+}
+
+test_format! { display_integer: Posit::from(1500), "{}" => "1500" }
+test_format! { display_fraction: Posit::from(0.375), "{}" => "0.375" }
+test_format! { display_negative: Posit::from(-2.5), "{}" => "-2.5" }
+test_format! { display_small: Posit::from(1) / Posit::from(1024), "{}" => "0.0009765625" }
+test_format! { display_zero: Posit::zero(), "{}" => "0" }
+test_format! { display_nar: Posit::nar(), "{:>5}" => "  NaR" }
+test_format! { display_third: Posit::one().div_rounded(Posit::from(3), 16), "{}" => "0.333374" }
+test_format! { display_precision: Posit::one().div_rounded(Posit::from(3), 64), "{:.3}" => "0.333" }
+test_format! { display_precision_tie: Posit::from(2.5), "{:.0}" => "2" }
+test_format! { display_precision_pad: Posit::from(0.125), "{:.5}" => "0.12500" }
+test_format! { display_precision_carry: Posit::from(9.96), "{:.1}" => "10.0" }
+test_format! { display_sign: Posit::from(1.5), "{:+}" => "+1.5" }
+test_format! { display_width: Posit::from(-1.5), "{:08.2}" => "-0001.50" }
+test_format! { exp_integer: Posit::from(1500), "{:e}" => "1.5e3" }
+test_format! { exp_fraction: Posit::from(0.375), "{:e}" => "3.75e-1" }
+test_format! { exp_zero: Posit::zero(), "{:e}" => "0e0" }
+test_format! { exp_min_subnormal: Posit::from(f64::from_bits(1)), "{:e}" => "5e-324" }
+test_format! { exp_precision: Posit::from(1500), "{:.2e}" => "1.50e3" }
+test_format! { exp_precision_carry: Posit::from(9.96), "{:.1e}" => "1.0e1" }
+
+#[test]
+fn display_at_width() {
+    let third = |nbits| Posit::one().div_rounded(Posit::from(3), nbits);
+    let tenth = |nbits| Posit::from_str_rounded("0.1", nbits).unwrap();
+    assert_eq!(P8::from(third(8)).to_string(), "0.34");
+    assert_eq!(P16::from(third(16)).to_string(), "0.3334");
+    assert_eq!(P32::from(third(32)).to_string(), "0.333333334");
+    assert_eq!(P64::from(third(64)).to_string(), "0.3333333333333333335");
+    for nbits in &[8, 16, 32, 64] {
+        assert_eq!(with_precision(*nbits, || tenth(*nbits).to_string()), "0.1");
+    }
+    assert_eq!(P32::from(tenth(32)).to_string(), "0.1");
+    assert_eq!(P64::from(tenth(64)).to_string(), "0.1");
+    assert_eq!(with_precision(32, || format!("{:e}", -tenth(32))), "-1e-1");
+    // Outside a context the exact value is printed
+    assert_eq!(tenth(32).to_string(), "0.1000000000931322574615478515625");
+}
+
+proptest! {
+    #[test]
+    fn display_round_trip(x in any::<f64>(), nbits in 2usize..80) {
+        let p = rounded(Posit::from(x), nbits);
+        prop_assert_eq!(parse(&p.to_string()), p.clone());
+        prop_assert_eq!(parse(&format!("{:e}", p)), p);
+    }
+
+    #[test]
+    fn display_round_trip_es(x in any::<f64>(), nbits in 2usize..40) {
+        let mut p = super::Posit::<0>::from(x);
+        p.round_to(nbits);
+        prop_assert_eq!(p.to_string().parse::<super::Posit<0>>().unwrap(), p);
+    }
+
+    #[test]
+    fn display_round_trip_at_width(x in any::<f64>(), nbits in 2usize..80, bits in any::<u32>()) {
+        let p = rounded(Posit::from(x), nbits);
+        let s = with_precision(nbits, || p.to_string());
+        prop_assert_eq!(Posit::from_str_rounded(&s, nbits).unwrap(), p);
+        let p = P32::from_bits(bits);
+        prop_assert_eq!(P32::from(Posit::from_str_rounded(&p.to_string(), 32).unwrap()), p);
+    }
+
+    #[test]
+    fn parse_correctly_rounded(m in any::<i64>(), exp in -40i32..40, nbits in 2usize..80) {
+        let p = Posit::from_str_rounded(&format!("{}e{}", m, exp), nbits).unwrap();
+        let power = Posit::from(10).pow(exp.unsigned_abs() as usize);
+        let expected = if exp >= 0 {
+            rounded(Posit::from(m) * power, nbits)
+        } else {
+            Posit::from(m).div_rounded(power, nbits)
+        };
+        prop_assert_eq!(p, expected);
+    }
+}
@@ -0,0 +1,121 @@
+use std::ffi::CStr;
+use std::ptr;
+
+use crate::exports::*;
+use crate::TestPosit as Posit;
+
+fn last_error() -> String {
+    unsafe { CStr::from_ptr(unum_last_error()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn handles() {
+    unsafe {
+        let x = posit_from_double(1.5);
+        let y = posit_from_int64(-3);
+        let z = posit_from_string(b"0.25\0".as_ptr().cast());
+        assert_eq!(*x, Posit::from(1.5));
+        assert_eq!(*y, Posit::from(-3));
+        assert_eq!(*z, Posit::from(0.25));
+
+        let sum = posit_add(x, y);
+        let mut value = 0.0;
+        assert_eq!(posit_to_double(sum, &mut value), UnumStatus::Ok);
+        assert_eq!(value, -1.5);
+        let s = posit_to_string(sum);
+        assert_eq!(CStr::from_ptr(s).to_str(), Ok("-1.5"));
+        posit_string_free(s);
+
+        let mut order = 0;
+        assert_eq!(posit_compare(y, x, &mut order), UnumStatus::Ok);
+        assert_eq!(order, -1);
+        let nar = posit_from_string(b"NaR\0".as_ptr().cast());
+        assert_eq!(posit_compare(nar, y, &mut order), UnumStatus::Ok);
+        assert_eq!(order, -1);
+
+        let cube = posit_pow(x, 3);
+        assert_eq!(*cube, Posit::from(3.375));
+        let root = posit_sqrt(z, 32);
+        assert_eq!(*root, Posit::from(0.5));
+
+        for p in [x, y, z, sum, nar, cube, root] {
+            posit_free(p);
+        }
+    }
+}
+
+#[test]
+fn in_place() {
+    unsafe {
+        let x = posit_from_int64(6);
+        let y = posit_from_int64(4);
+        assert_eq!(posit_mul_into(x, x, y), UnumStatus::Ok);
+        assert_eq!(*x, Posit::from(24));
+        assert_eq!(posit_div_into(x, x, y), UnumStatus::Ok);
+        assert_eq!(*x, Posit::from(6));
+        assert_eq!(posit_sub_into(y, x, y), UnumStatus::Ok);
+        assert_eq!(*y, Posit::from(2));
+        assert_eq!(posit_neg_into(y, y), UnumStatus::Ok);
+        assert_eq!(*y, Posit::from(-2));
+        assert_eq!(posit_pow_into(y, y, 2), UnumStatus::Ok);
+        assert_eq!(*y, Posit::from(4));
+        assert_eq!(posit_sqrt_into(x, y, 16), UnumStatus::Ok);
+        assert_eq!(*x, Posit::from(2));
+        assert_eq!(posit_set_double(x, 0.1), UnumStatus::Ok);
+        assert_eq!(posit_round(x, 8), UnumStatus::Ok);
+        let mut rounded = Posit::from(0.1);
+        rounded.round_to(8);
+        assert_eq!(*x, rounded);
+        posit_free(x);
+        posit_free(y);
+    }
+}
+
+#[test]
+fn invalid_arguments() {
+    unsafe {
+        let x = posit_new();
+        let mut value = 0.0;
+        assert!(posit_add(x, ptr::null()).is_null());
+        assert_eq!(last_error(), "`rhs` is null");
+        assert!(posit_clone(ptr::null()).is_null());
+        assert!(posit_to_string(ptr::null()).is_null());
+        assert!(posit_sqrt(x, 1).is_null());
+        assert!(posit_from_string(ptr::null()).is_null());
+        assert!(posit_from_string(b"1.2.3\0".as_ptr().cast()).is_null());
+        assert_eq!(last_error(), "invalid posit literal: \"1.2.3\"");
+        assert_eq!(
+            posit_to_double(ptr::null(), &mut value),
+            UnumStatus::NullPointer
+        );
+        assert_eq!(posit_to_double(x, ptr::null_mut()), UnumStatus::NullPointer);
+        assert_eq!(
+            posit_add_into(ptr::null_mut(), x, x),
+            UnumStatus::NullPointer
+        );
+        assert_eq!(
+            posit_set_string(x, b"one\0".as_ptr().cast()),
+            UnumStatus::InvalidArgument
+        );
+        assert_eq!(*x, Posit::zero());
+        assert_eq!(posit_round(x, 0), UnumStatus::InvalidArgument);
+        assert_eq!(last_error(), "`nbits` is 0, posits have at least 2 bits");
+        posit_free(x);
+        posit_free(ptr::null_mut());
+        posit_string_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn panics_are_caught() {
+    assert_eq!(run::<()>(|| panic!("boom")), Err(UnumStatus::InternalError));
+    assert_eq!(last_error(), "internal error: boom");
+    assert_eq!(guard(7, || panic!("{} bits", 3)), 7);
+    assert_eq!(last_error(), "internal error: 3 bits");
+    // Errors are kept per thread
+    std::thread::spawn(|| assert!(unum_last_error().is_null()))
+        .join()
+        .unwrap();
+}
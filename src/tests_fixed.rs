@@ -0,0 +1,120 @@
+use crate::{Posit, P16, P32, P64, P8};
+
+macro_rules! test_ops {
+    ($(#[$attr: meta])* $name: ident: $type: ident($nbits: expr), $lhs: expr, $rhs: expr) => {
+        #[test]
+        $(#[$attr])*
+        fn $name() {
+            for l in $lhs {
+                for r in $rhs {
+                    let lhs = $type::from_bits(l);
+                    let rhs = $type::from_bits(r);
+                    let l_posit = Posit::from(lhs);
+                    let r_posit = Posit::from(rhs);
+                    let add = l_posit.clone().add_rounded(r_posit.clone(), $nbits);
+                    let sub = l_posit.clone().sub_rounded(r_posit.clone(), $nbits);
+                    let mul = l_posit.clone().mul_rounded(r_posit.clone(), $nbits);
+                    let div = l_posit.div_rounded(r_posit, $nbits);
+                    assert_eq!(Posit::from(lhs + rhs), add, "{:?} + {:?}", lhs, rhs);
+                    assert_eq!(Posit::from(lhs - rhs), sub, "{:?} - {:?}", lhs, rhs);
+                    assert_eq!(Posit::from(lhs * rhs), mul, "{:?} * {:?}", lhs, rhs);
+                    assert_eq!(Posit::from(lhs / rhs), div, "{:?} / {:?}", lhs, rhs);
+                }
+            }
+        }
+    };
+}
+
+macro_rules! test_conversions {
+    ($name: ident: $type: ident, $bits: ty) => {
+        #[test]
+        fn $name() {
+            let mut prev = $type::NAR;
+            for bits in <$bits>::MIN..=<$bits>::MAX {
+                let p = $type::from_bits(bits);
+                let posit = Posit::from(p);
+                assert_eq!($type::from(posit.clone()), p);
+                assert_eq!(Posit::from(-p), -posit.clone());
+                if !p.is_nar() && !prev.is_nar() {
+                    assert!(prev < p);
+                    assert!(Posit::from(prev) < posit);
+                }
+                prev = p;
+            }
+        }
+    };
+}
+
+macro_rules! test_round {
+    ($name: ident: $type: ident, $value: expr, $expected: expr) => {
+        #[test]
+        fn $name() {
+            let p = $type::from(Posit::from($value));
+            assert_eq!(p.to_bits(), $expected);
+            let p = $type::from(Posit::from(-$value));
+            assert_eq!(p.to_bits(), $expected.wrapping_neg());
+        }
+    };
+}
+
+/// Zero, ±minpos, ±maxpos, NaR and ±1 with their neighbours, where rounding saturates, carries
+/// into the regime or cancels.
+fn p16_boundaries() -> Vec<u16> {
+    let mut bits: Vec<u16> = [0x0000, 0x0001, 0x7fff, 0x8000, 0x4000]
+        .iter()
+        .flat_map(|&b: &u16| vec![b, b.wrapping_neg()])
+        .flat_map(|b| vec![b.wrapping_sub(1), b, b.wrapping_add(1)])
+        .collect();
+    bits.sort_unstable();
+    bits.dedup();
+    bits
+}
+
+test_ops! { p8_ops: P8(8), 0..=u8::MAX, 0..=u8::MAX }
+// P16 has too many pairs to test exhaustively on every run, so every operand is paired with the
+// boundaries and a sample of the rest, spread by steps coprime to each other.
+test_ops! { p16_ops: P16(16), (0..=u16::MAX).step_by(509), (0..=u16::MAX).step_by(521) }
+test_ops! { p16_boundary_lhs_ops: P16(16), p16_boundaries(), 0..=u16::MAX }
+test_ops! { p16_boundary_rhs_ops: P16(16), 0..=u16::MAX, p16_boundaries() }
+test_ops! {
+    #[ignore = "exhaustive, run with --ignored in release mode"]
+    p16_exhaustive_ops: P16(16), 0..=u16::MAX, 0..=u16::MAX
+}
+
+test_conversions! { p8_conversions: P8, u8 }
+test_conversions! { p16_conversions: P16, u16 }
+
+test_round! { round_exact: P8, 1.0, 0x40_u8 }
+test_round! { round_tie_down: P8, 1.0625, 0x40_u8 } // 1 + 1/16 -> 1
+test_round! { round_tie_up: P8, 1.1875, 0x42_u8 } // 1 + 3/16 -> 1.25
+test_round! { round_above_tie: P8, 1.0626, 0x41_u8 }
+test_round! { round_carry_regime: P8, 15.9, 0x60_u8 } // 16 has a longer regime than 15
+test_round! { round_exponent_tie: P8, 4194304.0, 0x7e_u8 } // 2^22 is halfway from 2^20 to 2^24
+test_round! { round_maxpos: P8, 1e30, 0x7f_u8 }
+test_round! { round_minpos: P8, 1e-30, 0x01_u8 }
+test_round! { round_p16: P16, 3.0, 0x4c00_u16 }
+test_round! { round_p32_maxpos: P32, 1e300, 0x7fff_ffff_u32 }
+test_round! { round_p64_minpos: P64, 1e-300, 0x0000_0000_0000_0001_u64 }
+
+#[test]
+fn special_values() {
+    assert_eq!(P32::ONE + P32::ONE, P32::from(Posit::from(2.0)));
+    assert_eq!(P32::ONE - P32::ONE, P32::ZERO);
+    assert_eq!(P32::ONE / P32::ZERO, P32::NAR);
+    assert_eq!(P32::NAR * P32::ZERO, P32::NAR);
+    assert_eq!(P32::MAXPOS * P32::MAXPOS, P32::MAXPOS);
+    assert_eq!(P32::MINPOS / P32::MAXPOS, P32::MINPOS);
+    assert_eq!(-P64::NAR, P64::NAR);
+    assert!(P64::NAR < -P64::MAXPOS);
+}
+
+#[test]
+fn p64_arithmetic() {
+    let third = P64::ONE / P64::from(Posit::from(3.0));
+    let expected: Posit = Posit::from(1.0 / 3.0);
+    assert_eq!(Posit::from(third).bits[..20], expected.bits[..20]);
+    assert_eq!(third + third + third, P64::ONE);
+    let big = P64::from(Posit::from(1e20));
+    assert_eq!(big - big, P64::ZERO);
+    assert_eq!(big * P64::ONE, big);
+}
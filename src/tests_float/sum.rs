@@ -1,4 +1,5 @@
 const EPS: f64 = 10e-18;
+#[allow(clippy::excessive_precision)]
 const RES: f64 = 1.644725755214774951;
 const LIMIT: u32 = 4801;
 
@@ -0,0 +1,144 @@
+use proptest::prelude::*;
+
+use crate::{Interval, TestPosit as Posit};
+
+fn posit(x: f64) -> Posit {
+    Posit::from(x)
+}
+
+fn interval(lo: f64, hi: f64) -> Interval {
+    Interval::new(posit(lo), posit(hi))
+}
+
+/// Posit of 8 bits, not NaR.
+fn small() -> impl Strategy<Value = Posit> {
+    (-1e6..1e6f64).prop_map(|x| {
+        let mut p = posit(x);
+        p.round_to(8);
+        p
+    })
+}
+
+/// Interval with bounds of 8 bits and a value inside it.
+fn bounded() -> impl Strategy<Value = (Interval, Posit)> {
+    (small(), small()).prop_map(|(a, b)| {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        let inside = (&lo + &hi) * posit(0.5);
+        (Interval::new(lo, hi), inside)
+    })
+}
+
+#[test]
+fn directed_rounding() {
+    let third = (
+        Posit::one().div_rounded_down(posit(3.), 8),
+        Posit::one().div_rounded_up(posit(3.), 8),
+    );
+    assert_eq!(third.0.next_up(8), third.1);
+    assert!(third.0.to_f64() < 1. / 3. && 1. / 3. < third.1.to_f64());
+    assert_eq!(posit(1.).add_rounded_down(posit(2.), 8), posit(3.));
+    assert_eq!(posit(1.).add_rounded_up(posit(2.), 8), posit(3.));
+
+    // 1 - 2^-10 lies between 1 and the posit of 8 bits below it
+    let x = posit(1.).sub_rounded_down(posit(2f64.powi(-10)), 8);
+    assert_eq!(x, Posit::one().next_down(8));
+    assert_eq!(
+        posit(1.).sub_rounded_up(posit(2f64.powi(-10)), 8),
+        posit(1.)
+    );
+    let y = posit(-1.5).mul_rounded_up(posit(1.0625), 8);
+    assert!(y.to_f64() > -1.5 * 1.0625);
+    assert_eq!(
+        y.next_down(8),
+        posit(-1.5).mul_rounded_down(posit(1.0625), 8)
+    );
+
+    // No saturation past maxpos and minpos
+    let maxpos = Posit::maxpos(8);
+    assert!(maxpos.clone().mul_rounded_up(posit(2.), 8).is_nar());
+    assert_eq!(maxpos.clone().mul_rounded_down(posit(2.), 8), maxpos);
+    let minpos = Posit::minpos(8);
+    assert!(minpos.clone().div_rounded_down(posit(2.), 8).is_zero());
+    assert_eq!(minpos.clone().div_rounded_up(posit(2.), 8), minpos);
+    assert!(Posit::one().div_rounded_up(Posit::zero(), 8).is_nar());
+}
+
+#[test]
+fn bounds() {
+    let i = interval(1., 2.5);
+    assert_eq!((i.lo(), i.hi()), (&posit(1.), &posit(2.5)));
+    assert!(i.contains(&posit(1.)) && i.contains(&posit(2.5)));
+    assert!(!i.contains(&posit(3.)) && !i.contains(&Posit::nar()));
+    assert_eq!(i.width(), posit(1.5));
+    assert_eq!(i.to_string(), "[1, 2.5]");
+
+    assert!(Interval::from(Posit::nar()).is_entire());
+    assert!(Interval::new(Posit::nar(), posit(1.)).contains(&posit(100.)));
+    assert_eq!(Interval::<Posit>::entire().to_string(), "entire");
+    assert!(Interval::<Posit>::entire().width().is_nar());
+    assert_eq!(Interval::from(posit(3.)).width(), Posit::zero());
+}
+
+#[test]
+#[should_panic(expected = "above upper bound")]
+fn inverted_bounds() {
+    interval(2., 1.);
+}
+
+#[test]
+fn arithmetic() {
+    let (a, b) = (interval(1., 2.), interval(-3., 0.5));
+    assert_eq!(a.clone() + b.clone(), interval(-2., 2.5));
+    assert_eq!(a.clone() - b.clone(), interval(0.5, 5.));
+    assert_eq!(a.clone() * b.clone(), interval(-6., 1.));
+    assert_eq!(-b.clone(), interval(-0.5, 3.));
+    assert!(a.clone().div_rounded(b.clone(), 8).is_entire());
+    assert_eq!(b.div_rounded(interval(2., 4.), 8), interval(-1.5, 0.25));
+
+    let third = Interval::from(posit(1.)).div_rounded(Interval::from(posit(3.)), 8);
+    assert_eq!(third.lo().next_up(8), *third.hi());
+
+    // Bounds past maxpos make the entire interval
+    let maxpos = Interval::from(Posit::maxpos(8));
+    assert!(maxpos.clone().add_rounded(maxpos.clone(), 8).is_entire());
+    let mut sum = maxpos.clone() + maxpos;
+    assert!(!sum.is_entire());
+    sum.round_to(8);
+    assert!(sum.is_entire());
+    assert!((Interval::entire() * a).is_entire());
+}
+
+proptest! {
+    #[test]
+    fn contains_results((l, x) in bounded(), (r, y) in bounded(), nbits in 3usize..16) {
+        prop_assert!(l.contains(&x) && r.contains(&y));
+        let sum = l.clone().add_rounded(r.clone(), nbits);
+        prop_assert!(sum.contains(&(&x + &y)), "{} + {} = {}", l, r, sum);
+        let difference = l.clone().sub_rounded(r.clone(), nbits);
+        prop_assert!(difference.contains(&(&x - &y)), "{} - {} = {}", l, r, difference);
+        let product = l.clone().mul_rounded(r.clone(), nbits);
+        prop_assert!(product.contains(&(&x * &y)), "{} * {} = {}", l, r, product);
+        prop_assert!((l.clone() * r.clone()).contains(&(&x * &y)));
+    }
+
+    #[test]
+    fn contains_quotients((r, y) in bounded(), z in small(), nbits in 3usize..16) {
+        // The quotient z of x = y * z is exact
+        let x = &y * &z;
+        let quotient = Interval::from(x).div_rounded(r.clone(), nbits);
+        prop_assert!(quotient.contains(&z), "{} / {} = {}", y, r, quotient);
+        prop_assert!(!r.contains(&Posit::zero()) || quotient.is_entire());
+    }
+
+    #[test]
+    fn directed_bounds(x in small(), y in small(), nbits in 2usize..16) {
+        let exact = &x * &y;
+        let (down, up) = (
+            x.clone().mul_rounded_down(y.clone(), nbits),
+            x.clone().mul_rounded_up(y.clone(), nbits),
+        );
+        prop_assert!(down <= exact && (up.is_nar() || exact <= up));
+        let nearest = x.mul_rounded(y, nbits);
+        prop_assert!(nearest == down || nearest == up);
+    }
+}
@@ -0,0 +1,228 @@
+use approx::{assert_relative_eq, assert_ulps_eq, relative_eq, ulps_eq};
+use nalgebra::{ComplexField, DMatrix, DVector, RealField};
+use num_traits::FromPrimitive;
+
+use crate::{Cholesky, Lu, Qr, TestPosit as Posit, P16, P32, P64, P8};
+
+fn matrix<T: FromPrimitive + nalgebra::Scalar>(n: usize, values: &[f64]) -> DMatrix<T> {
+    DMatrix::from_row_slice(n, values.len() / n, values).map(|x| T::from_f64(x).unwrap())
+}
+
+fn vector<T: FromPrimitive + nalgebra::Scalar>(values: &[f64]) -> DVector<T> {
+    DVector::from_column_slice(values).map(|x| T::from_f64(x).unwrap())
+}
+
+fn hilbert<T: nalgebra::Scalar>(
+    n: usize,
+    convert: impl Fn(Posit) -> T,
+) -> (DMatrix<T>, DVector<T>) {
+    let element = |i: usize, j: usize| Posit::one().div_rounded(Posit::from(i + j + 1), 128);
+    let row_sum = |i| (0..n).fold(Posit::zero(), |sum, j| sum + element(i, j));
+    (
+        DMatrix::from_fn(n, n, |i, j| convert(element(i, j))),
+        DVector::from_fn(n, |i, _| convert(row_sum(i))),
+    )
+}
+
+#[test]
+fn matrix_arithmetic() {
+    let a: DMatrix<P32> = matrix(2, &[1.0, 2.0, 3.0, 4.0]);
+    let b: DMatrix<P32> = matrix(2, &[0.5, -1.0, 0.25, 2.0]);
+    assert_eq!(&a * &b, matrix(2, &[1.0, 3.0, 2.5, 5.0]));
+    assert_eq!(&a + &b, matrix(2, &[1.5, 1.0, 3.25, 6.0]));
+    assert_eq!(a.transpose(), matrix(2, &[1.0, 3.0, 2.0, 4.0]));
+    assert_eq!(a.trace(), P32::from_f64(5.0).unwrap());
+    let x: DVector<P16> = vector(&[3.0, 4.0]);
+    assert_eq!(x.norm(), P16::from_f64(5.0).unwrap());
+    assert_eq!(
+        format!("{}", DVector::<P8>::from_element(1, P8::ONE))
+            .trim()
+            .lines()
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn nalgebra_decompositions() {
+    let a: DMatrix<P64> = matrix(3, &[2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0]);
+    let b: DVector<P64> = vector(&[1.0, 0.0, 1.0]);
+    let expected: DVector<P64> = vector(&[1.0, 1.0, 1.0]);
+    assert_ulps_eq!(a.clone().lu().solve(&b).unwrap(), expected);
+    assert_ulps_eq!(a.clone().cholesky().unwrap().solve(&b), expected);
+    assert_ulps_eq!(a.clone().qr().solve(&b).unwrap(), expected);
+    assert_relative_eq!(a.determinant(), P64::from_f64(4.0).unwrap());
+}
+
+#[test]
+fn real_constants() {
+    assert_eq!(P32::pi(), P32::from(Posit::from(-1).acos(32)));
+    assert_eq!(
+        P64::frac_pi_2(),
+        P64::from(Posit::one().atan(64) * Posit::from(2))
+    );
+    assert_eq!(P16::e(), P16::from_f64(std::f64::consts::E).unwrap());
+    assert_eq!(P32::ln_2(), P32::from_f64(std::f64::consts::LN_2).unwrap());
+    assert_eq!(
+        P32::ln_10(),
+        P32::from_f64(std::f64::consts::LN_10).unwrap()
+    );
+    assert_eq!(
+        P32::log2_e(),
+        P32::from_f64(std::f64::consts::LOG2_E).unwrap()
+    );
+    assert_eq!(
+        P32::frac_2_sqrt_pi(),
+        P32::from_f64(std::f64::consts::FRAC_2_SQRT_PI).unwrap()
+    );
+    assert_eq!(
+        P32::frac_pi_3(),
+        P32::from_f64(std::f64::consts::FRAC_PI_3).unwrap()
+    );
+    assert_eq!(P8::two_pi(), P8::from_f64(6.5).unwrap());
+}
+
+#[test]
+fn complex_field() {
+    let x = P32::from_f64(-2.0).unwrap();
+    assert_eq!(x.modulus(), -x);
+    assert_eq!(x.argument(), P32::pi());
+    assert_eq!(x.to_exp(), (-x, -P32::ONE));
+    assert_eq!(x.try_sqrt(), None);
+    assert_eq!((-x).try_sqrt(), Some(P32::from_f64(2f64.sqrt()).unwrap()));
+    assert_eq!(P32::NAR.try_sqrt(), None);
+    assert!(!ComplexField::is_finite(&P32::NAR));
+    assert_eq!(
+        alga::general::MeetSemilattice::meet(&P32::ONE, &P32::NAR),
+        P32::NAR
+    );
+    assert_eq!(
+        nalgebra::convert::<f64, P16>(0.5),
+        P16::from_f64(0.5).unwrap()
+    );
+}
+
+#[test]
+fn approximate_equality() {
+    let one = P32::ONE;
+    let next = P32::from_bits(one.to_bits() + 1);
+    assert!(relative_eq!(one, next));
+    assert!(!relative_eq!(one, P32::from_f64(1.001).unwrap()));
+    assert!(relative_eq!(P32::NAR, P32::NAR));
+    assert!(!relative_eq!(P32::NAR, P32::MAXPOS));
+    assert!(ulps_eq!(
+        P16::MINPOS,
+        -P16::MINPOS,
+        epsilon = P16::ZERO,
+        max_ulps = 2
+    ));
+    assert!(!ulps_eq!(
+        P16::ONE,
+        P16::from_bits(P16::ONE.to_bits() + 5),
+        epsilon = P16::ZERO
+    ));
+    assert_ulps_eq!(
+        P32::from_f64(0.1).unwrap() * P32::from_f64(3.0).unwrap(),
+        P32::from_f64(0.3).unwrap()
+    );
+}
+
+#[test]
+fn lu_solve() {
+    let a: DMatrix<P32> = matrix(3, &[1.0, 2.0, 3.0, 2.0, 5.0, 3.0, 1.0, 0.0, 8.0]);
+    let lu = Lu::new(a.clone());
+    assert!(lu.is_invertible());
+    assert_eq!(lu.permutation(), &[1, 2, 0]);
+    let pa = DMatrix::from_fn(3, 3, |i, j| a[(lu.permutation()[i], j)]);
+    assert_relative_eq!(lu.l() * lu.u(), pa);
+    let x = lu.solve(&vector(&[14.0, 21.0, 25.0])).unwrap();
+    assert_relative_eq!(x, vector(&[1.0, 2.0, 3.0]));
+
+    let singular: DMatrix<P32> = matrix(2, &[1.0, 2.0, 2.0, 4.0]);
+    let lu = Lu::new(singular);
+    assert!(!lu.is_invertible());
+    assert_eq!(lu.solve(&vector(&[1.0, 2.0])), None);
+}
+
+#[test]
+fn lu_single_rounding() {
+    // Rounding 2^-10 - 1024 first would lose the small term in 16 bits
+    let small = 2f64.powi(-10);
+    let a: DMatrix<P16> = matrix(3, &[1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+    let x = Lu::new(a)
+        .solve(&vector(&[small, 1024.0, -1024.0]))
+        .unwrap();
+    assert_eq!(x, vector(&[small, 1024.0, -1024.0]));
+}
+
+#[test]
+fn cholesky_solve() {
+    let a: DMatrix<P64> = matrix(
+        3,
+        &[4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+    );
+    let cholesky = Cholesky::new(a.clone()).unwrap();
+    assert_eq!(
+        cholesky.l(),
+        &matrix(3, &[2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0])
+    );
+    let b = &a * vector::<P64>(&[1.0, -1.0, 0.5]);
+    assert_relative_eq!(cholesky.solve(&b), vector(&[1.0, -1.0, 0.5]));
+
+    assert!(Cholesky::new(matrix::<P32>(2, &[1.0, 2.0, 2.0, 1.0])).is_none());
+    assert!(Cholesky::new(matrix::<P32>(1, &[0.0])).is_none());
+    assert!(Cholesky::new(DMatrix::from_element(1, 1, P32::NAR)).is_none());
+}
+
+#[test]
+fn qr_solve() {
+    let a: DMatrix<P64> = matrix(3, &[12.0, -51.0, 4.0, 6.0, 167.0, -68.0, -4.0, 24.0, -41.0]);
+    let qr = Qr::new(a.clone());
+    let (q, r) = (qr.q(), qr.r());
+    assert_relative_eq!(
+        q.transpose() * &q,
+        DMatrix::identity(3, 3),
+        epsilon = P64::from_f64(1e-15).unwrap()
+    );
+    assert_relative_eq!(&q * &r, a, epsilon = P64::from_f64(1e-12).unwrap());
+    assert_relative_eq!(r[(0, 0)].abs(), P64::from_f64(14.0).unwrap());
+    assert_eq!(r[(1, 0)], P64::ZERO);
+    let b = &a * vector::<P64>(&[1.0, 2.0, 3.0]);
+    assert_relative_eq!(
+        qr.solve(&b).unwrap(),
+        vector(&[1.0, 2.0, 3.0]),
+        epsilon = P64::from_f64(1e-14).unwrap()
+    );
+
+    // Least squares line through (0, 1), (1, 2) and (2, 4)
+    let a: DMatrix<P32> = matrix(3, &[1.0, 0.0, 1.0, 1.0, 1.0, 2.0]);
+    let x = Qr::new(a).solve(&vector(&[1.0, 2.0, 4.0])).unwrap();
+    assert_relative_eq!(
+        x,
+        vector(&[5.0 / 6.0, 1.5]),
+        epsilon = P32::from_f64(1e-6).unwrap()
+    );
+
+    let dependent: DMatrix<P32> = matrix(2, &[1.0, 2.0, 0.0, 0.0]);
+    assert_eq!(Qr::new(dependent).solve(&vector(&[1.0, 2.0])), None);
+}
+
+#[test]
+fn hilbert_conditioning() {
+    let error = |x: DVector<f64>| x.iter().map(|x| (x - 1.0).abs()).fold(0.0, f64::max);
+    let (a, b) = hilbert(10, |x| x.to_f64());
+    let f64_error = error(a.lu().solve(&b).unwrap());
+    let (a, b) = hilbert(10, P64::from);
+    let posit_error = error(
+        Lu::new(a)
+            .solve(&b)
+            .unwrap()
+            .map(|x| Posit::from(x).to_f64()),
+    );
+    assert!(
+        posit_error * 10.0 < f64_error,
+        "{} {}",
+        posit_error,
+        f64_error
+    );
+}
@@ -0,0 +1,77 @@
+use bitvec::prelude::*;
+use proptest::prelude::*;
+
+use crate::{posit, TestPosit as Posit};
+
+#[test]
+fn literal_binary() {
+    let p = posit!(0b0100_01);
+    assert_eq!(p, Posit::from(1.5));
+    assert_eq!(p.bits, bitvec![0, 1, 0, 0, 0, 1]);
+    let p = posit!(0b1);
+    assert!(p.is_nar());
+    let p = posit!(0b0000);
+    assert!(p.is_zero());
+    assert_eq!(p.bits.len(), 4);
+}
+
+#[test]
+fn literal_hex() {
+    let p = posit!(0x4c);
+    assert_eq!(p, Posit::from(3));
+    assert_eq!(p.bits.len(), 8);
+    let p = posit!(0xCC);
+    assert_eq!(p, Posit::from(-3));
+    let p = posit!(0x5, es = 0);
+    assert_eq!(p, super::Posit::from(1.5));
+}
+
+#[test]
+fn literal_errors() {
+    for s in &["", "0b", "0x", "0x_", "0b012", "4c", "0xg", "0B1", "b01", "-0b1", "0b1 "] {
+        assert!(Posit::from_bit_str(s).is_err(), "{:?}", s);
+    }
+}
+
+macro_rules! test_format {
+    ($name: ident: $value: expr, $format: expr => $expected: expr) => {
+        #[test]
+        fn $name() {
+            let value: Posit = $value;
+            assert_eq!(format!($format, value), $expected);
+        }
+    };
+}
+
+test_format! { binary_fields: Posit::from(3), "{:b}" => "0_10_01_1" }
+test_format! { binary_prefix: Posit::from(3), "{:#b}" => "0b0_10_01_1" }
+test_format! { binary_negative: Posit::from(-3), "{:b}" => "1_10_01_1" }
+test_format! { binary_no_fraction: Posit::from(0.0625), "{:b}" => "0_01" }
+test_format! { binary_no_terminator: Posit::maxpos(8), "{:b}" => "0_1111111" }
+test_format! { binary_trailing_zeros: posit!(0b0100_0000), "{:b}" => "0_10_00_000" }
+test_format! { binary_zero: Posit::zero(), "{:#b}" => "0b0" }
+test_format! { binary_nar: Posit::nar(), "{:b}" => "1" }
+test_format! { binary_width: Posit::one(), "{:>6b}" => "   0_1" }
+test_format! { hex_lower: Posit::from(3), "{:x}" => "4c" }
+test_format! { hex_prefix: Posit::from(3), "{:#x}" => "0x4c" }
+test_format! { hex_upper: Posit::from(-3), "{:#X}" => "0xCC" }
+test_format! { hex_zero: Posit::zero(), "{:x}" => "0" }
+test_format! { hex_nar: Posit::nar(), "{:x}" => "8" }
+
+#[test]
+fn binary_es() {
+    assert_eq!(format!("{:b}", super::Posit::<0>::from(1.5)), "0_10_1");
+    assert_eq!(format!("{:b}", super::Posit::<5>::from(-0.75)), "1_01_11111_1");
+}
+
+proptest! {
+    #[test]
+    fn bit_str_round_trip(x in any::<f64>(), nbits in 2usize..80) {
+        let mut p = Posit::from(x);
+        p.round_to(nbits);
+        let binary = Posit::from_bit_str(&format!("{:#b}", p)).unwrap();
+        prop_assert_eq!(binary, p.clone());
+        prop_assert_eq!(Posit::from_bit_str(&format!("{:#x}", p)).unwrap(), p.clone());
+        prop_assert_eq!(Posit::from_bit_str(&format!("{:#X}", p)).unwrap(), p);
+    }
+}
@@ -0,0 +1,247 @@
+use bitvec::prelude::*;
+
+use crate::{Posit, Regime, P16};
+
+/// Posit with the given binary scale and 128 bits of the fraction in hexadecimal.
+fn constant<const ES: usize>(scale: isize, fraction: &str) -> Posit<ES> {
+    let mut frac = BitVec::new();
+    for digit in fraction.chars() {
+        let digit = digit.to_digit(16).unwrap() as u8;
+        frac.extend(digit.as_bitslice::<BigEndian>()[4..].iter());
+    }
+    let (regime, exp) = Regime::from_scale(scale, ES);
+    Posit::from_parts(false, regime, exp, &frac)
+}
+
+fn rounded<const ES: usize>(mut p: Posit<ES>, nbits: usize) -> Posit<ES> {
+    p.round_to(nbits);
+    p
+}
+
+const E: (isize, &str) = (1, "5bf0a8b1457695355fb8ac404e7a79e3");
+const LN_2: (isize, &str) = (-1, "62e42fefa39ef35793c7673007e5ed5e");
+const LN_10: (isize, &str) = (1, "26bb1bbb5551582dd4adac5705a61451");
+const SQRT_2: (isize, &str) = (0, "6a09e667f3bcc908b2fb1366ea957d3e");
+const SQRT_3: (isize, &str) = (0, "bb67ae8584caa73b25742d7078b83b89");
+const LOG10_2: (isize, &str) = (-2, "34413509f79fef311f12b35816f922f0");
+const LOG2_10: (isize, &str) = (1, "a934f0979a3715fc9257edfe9b5fb699");
+const EXP_M10: (isize, &str) = (-15, "7cd79b5647c9a9c5b24755098872f95e");
+const LN_1E10: (isize, &str) = (4, "7069e2aa2aa5ae3949d9176cc70f9966");
+/// `e` to the power of `1e-5` rounded to `f64`
+const EXP_1EM5: (isize, &str) = (0, "0000a7c5e340e6fdae87ca8995cab3dc");
+/// 3 to the power of `pi` rounded to `f64`
+const POW_3_PI: (isize, &str) = (4, "f8b55fadf31420841aa3380883bc5182");
+const PI: (isize, &str) = (1, "921fb54442d18469898cc51701b839a2");
+const PI_2: (isize, &str) = (0, "921fb54442d18469898cc51701b839a2");
+const SQRT_2_2: (isize, &str) = (-1, SQRT_2.1);
+const SIN_1: (isize, &str) = (-1, "aed548f090cee0418dd3d2138a1e7865");
+const COS_1: (isize, &str) = (-1, "14a280fb5068b923848cdb2ed0e37a53");
+const TAN_1: (isize, &str) = (0, "8eb245cbee3a5b8acc7d41323140b3b5");
+/// Negated
+const SIN_1E20: (isize, &str) = (-1, "4a5e605fd645073b8389199c66bbaf88");
+const COS_1E20: (isize, &str) = (-1, "872720fc60d3d644a940ec854df1cf54");
+/// Negated
+const SIN_355: (isize, &str) = (-16, "f9bd0307d1de29dade2cb6b5cb816957");
+const ATAN_1_2: (isize, &str) = (-2, "dac670561bb4f68adfc88bd978751a06");
+const ATAN_10: (isize, &str) = (0, "789bd2c16005382eabf0cd4b6aae57a2");
+const ASIN_1_3: (isize, &str) = (-2, "5bfe34f0511122e353bce91ffee88147");
+const ACOS_1_3: (isize, &str) = (0, "3b2028082e8d3bb0b49d8acf01fe1950");
+/// Negated `atan2(-1, -2)`
+const ATAN2_M1_M2: (isize, &str) = (1, "56c6e7397f5ae5982d93b39bd2a99661");
+const SINH_1: (isize, &str) = (0, "2cd9fc44eb9825a80249487f064ffd5c");
+const COSH_1: (isize, &str) = (0, "8b07551d9f5504c2bd28100196a4f66a");
+const TANH_1_2: (isize, &str) = (-2, "d9353d7568af365128ee21c65b08d3a7");
+/// Hyperbolic sine of `2^-10`
+const SINH_SMALL: (isize, &str) = (-10, "000002aaaaaccccccd9cd9cdcb1594ee");
+const TAN_PI_1_8: (isize, &str) = (-2, "a827999fcef32422cbec4d9baa55f4f8");
+const ATAN_PI_2: (isize, &str) = (-2, "68dfd7131067bccd4bb84928e5735281");
+const CBRT_2: (isize, &str) = (0, "428a2f98d728ae223ddab715be250d0c");
+const SQRT_5: (isize, &str) = (1, "1e3779b97f4a7c15f39cc0605cedc834");
+/// `e^x - 1` for `x = 2^-30`
+const EXP_M1_SMALL: (isize, &str) = (-30, "0000000200000002aaaaaaad55555557");
+/// `ln(1 + x)` for `x = 2^-30`
+const LN_1P_SMALL: (isize, &str) = (-31, "fffffffc0000000aaaaaaa8aaaaaab11");
+const ASINH_1: (isize, &str) = (-1, "c34366179d426cc1b1f33d1ba4a314a7");
+/// Inverse hyperbolic sine of `2^-30`
+const ASINH_SMALL: (isize, &str) = (-31, "fffffffffffffffaaaaaaaaaaaaaaad1");
+const ACOSH_2: (isize, &str) = (0, "51242719804349be684bd0188d52cecc");
+const ATANH_1_2: (isize, &str) = (-1, "193ea7aad030a976a4198d55053b7cb5");
+/// Inverse hyperbolic tangent of `2^-30`
+const ATANH_SMALL: (isize, &str) = (-30, "00000000000000055555555555555588");
+const LOG3_10: (isize, &str) = (1, "0c468ef9537c17c9b03da6108e16f0e3");
+
+macro_rules! test {
+    ($name: ident: ($result: expr) = ($expected: expr) @ ($nbits: expr)) => {
+        #[test]
+        fn $name() {
+            let expected: Posit = constant($expected.0, $expected.1);
+            assert_eq!($result, rounded(expected, $nbits));
+        }
+    };
+    ($name: ident: ($result: expr) = ($expected: expr)) => {
+        #[test]
+        fn $name() {
+            let expected: Posit = $expected;
+            assert_eq!($result, expected);
+        }
+    };
+}
+
+test! { sqrt_2: (Posit::from(2.0).sqrt(100)) = (SQRT_2) @ (100) }
+test! { sqrt_3: (Posit::from(3.0).sqrt(64)) = (SQRT_3) @ (64) }
+test! { sqrt_9: (Posit::from(9.0).sqrt(8)) = (Posit::from(3.0)) }
+test! { sqrt_quarter: (Posit::from(0.25).sqrt(8)) = (Posit::from(0.5)) }
+test! { sqrt_tiny: (Posit::from(2f64.powi(-42)).sqrt(16)) = (Posit::from(2f64.powi(-21))) }
+test! { sqrt_negative: (Posit::from(-4.0).sqrt(32)) = (Posit::nar()) }
+test! { sqrt_zero: (Posit::zero().sqrt(32)) = (Posit::zero()) }
+
+test! { exp_1: (Posit::one().exp(100)) = (E) @ (100) }
+test! { exp_m10: (Posit::from(-10.0).exp(64)) = (EXP_M10) @ (64) }
+test! { exp_small: (Posit::from(1e-5).exp(100)) = (EXP_1EM5) @ (100) }
+test! { exp_zero: (Posit::zero().exp(16)) = (Posit::one()) }
+test! { exp_nar: (Posit::nar().exp(16)) = (Posit::nar()) }
+test! { exp_maxpos: (Posit::from(1e10).exp(32)) = (Posit::maxpos(32)) }
+test! { exp_minpos: (Posit::from(-1e10).exp(32)) = (Posit::minpos(32)) }
+test! { exp_saturated: (Posit::from(30.0).exp(8)) = (Posit::maxpos(8)) }
+
+test! { exp2_half: (Posit::from(0.5).exp2(100)) = (SQRT_2) @ (100) }
+test! { exp2_integer: (Posit::from(-37.0).exp2(16)) = (Posit::from(2f64.powi(-37))) }
+test! { exp2_maxpos: (Posit::from(100.0).exp2(8)) = (Posit::maxpos(8)) }
+
+test! { ln_2: (Posit::from(2.0).ln(100)) = (LN_2) @ (100) }
+test! { ln_10: (Posit::from(10.0).ln(100)) = (LN_10) @ (100) }
+test! { ln_1e10: (Posit::from(1e10).ln(64)) = (LN_1E10) @ (64) }
+test! { ln_1: (Posit::one().ln(32)) = (Posit::zero()) }
+test! { ln_zero: (Posit::zero().ln(32)) = (Posit::nar()) }
+test! { ln_negative: (Posit::from(-1.0).ln(32)) = (Posit::nar()) }
+
+test! { log2_10: (Posit::from(10.0).log2(90)) = (LOG2_10) @ (90) }
+test! { log2_exact: (Posit::from(2f64.powi(-50)).log2(16)) = (Posit::from(-50.0)) }
+test! { log10_2: (Posit::from(2.0).log10(80)) = (LOG10_2) @ (80) }
+test! { log10_exact: (Posit::from(1000.0).log10(32)) = (Posit::from(3.0)) }
+
+test! { powf_pi: (Posit::from(3.0).powf(Posit::from(std::f64::consts::PI), 64)) = (POW_3_PI) @ (64) }
+test! { powf_sqrt: (Posit::from(2.0).powf(Posit::from(0.5), 80)) = (SQRT_2) @ (80) }
+test! { powf_exact: (Posit::from(4.0).powf(Posit::from(1.5), 32)) = (Posit::from(8.0)) }
+test! { powf_zero_base: (Posit::zero().powf(Posit::from(2.0), 32)) = (Posit::zero()) }
+test! { powf_zero_zero: (Posit::zero().powf(Posit::zero(), 32)) = (Posit::nar()) }
+test! { powf_negative: (Posit::from(-2.0).powf(Posit::from(2.0), 32)) = (Posit::nar()) }
+
+test! { sin_1: (Posit::one().sin(100)) = (SIN_1) @ (100) }
+test! { cos_1: (Posit::one().cos(100)) = (COS_1) @ (100) }
+test! { tan_1: (Posit::one().tan(100)) = (TAN_1) @ (100) }
+test! { sin_odd: (Posit::from(-1.0).sin(64)) = (-Posit::one().sin(64)) }
+test! { cos_even: (Posit::from(-1.0).cos(64)) = (Posit::one().cos(64)) }
+test! { sin_large: (-Posit::from(10.0).pow(20).sin(64)) = (SIN_1E20) @ (64) }
+test! { cos_large: (Posit::from(10.0).pow(20).cos(64)) = (COS_1E20) @ (64) }
+test! { sin_near_pi: (-Posit::from(355.0).sin(64)) = (SIN_355) @ (64) }
+test! { sin_zero: (Posit::zero().sin(32)) = (Posit::zero()) }
+test! { cos_zero: (Posit::zero().cos(32)) = (Posit::one()) }
+test! { cos_nar: (Posit::nar().cos(32)) = (Posit::nar()) }
+
+test! { asin_third: (Posit::one().div_rounded(Posit::from(3.0), 200).asin(100)) = (ASIN_1_3) @ (100) }
+test! { asin_one: (Posit::one().asin(64)) = (PI_2) @ (64) }
+test! { asin_out_of_range: (Posit::from(1.5).asin(64)) = (Posit::nar()) }
+test! { acos_third: (Posit::one().div_rounded(Posit::from(3.0), 200).acos(100)) = (ACOS_1_3) @ (100) }
+test! { acos_minus_one: (Posit::from(-1.0).acos(64)) = (PI) @ (64) }
+test! { acos_one: (Posit::one().acos(64)) = (Posit::zero()) }
+test! { atan_half: (Posit::from(0.5).atan(100)) = (ATAN_1_2) @ (100) }
+test! { atan_10: (Posit::from(10.0).atan(100)) = (ATAN_10) @ (100) }
+test! { atan_odd: (Posit::from(-10.0).atan(64)) = (-Posit::from(10.0).atan(64)) }
+test! { atan2_third_quadrant: (-Posit::from(-1.0).atan2(Posit::from(-2.0), 64)) = (ATAN2_M1_M2) @ (64) }
+test! { atan2_negative_axis: (Posit::zero().atan2(Posit::from(-1.0), 64)) = (PI) @ (64) }
+test! { atan2_origin: (Posit::zero().atan2(Posit::zero(), 64)) = (Posit::nar()) }
+
+test! { sinh_1: (Posit::one().sinh(100)) = (SINH_1) @ (100) }
+test! { sinh_small: (Posit::from(2f64.powi(-10)).sinh(100)) = (SINH_SMALL) @ (100) }
+test! { cosh_1: (Posit::one().cosh(100)) = (COSH_1) @ (100) }
+test! { cosh_small: (Posit::from(2f64.powi(-20)).cosh(32)) = (Posit::one()) }
+test! { cosh_maxpos: (Posit::from(1e6).cosh(32)) = (Posit::maxpos(32)) }
+test! { tanh_half: (Posit::from(0.5).tanh(100)) = (TANH_1_2) @ (100) }
+test! { tanh_large: (Posit::from(-100.0).tanh(32)) = (-Posit::one()) }
+
+test! { sin_pi_quarter: (Posit::from(0.25).sin_pi(100)) = (SQRT_2_2) @ (100) }
+test! { sin_pi_integer: (Posit::from(10.0).pow(20).sin_pi(32)) = (Posit::zero()) }
+test! { sin_pi_half: (Posit::from(-1.5).sin_pi(32)) = (Posit::one()) }
+test! { cos_pi_half: (Posit::from(0.5).cos_pi(32)) = (Posit::zero()) }
+test! { cos_pi_odd: (Posit::from(7.0).cos_pi(32)) = (-Posit::one()) }
+test! { tan_pi_eighth: (Posit::from(0.125).tan_pi(100)) = (TAN_PI_1_8) @ (100) }
+test! { tan_pi_quarter: (Posit::from(-0.25).tan_pi(32)) = (-Posit::one()) }
+test! { tan_pi_pole: (Posit::from(0.5).tan_pi(32)) = (Posit::nar()) }
+test! { atan_pi_2: (Posit::from(2.0).atan_pi(100)) = (ATAN_PI_2) @ (100) }
+test! { atan_pi_1: (Posit::one().atan_pi(32)) = (Posit::from(0.25)) }
+
+#[test]
+fn exp_es() {
+    assert_eq!(Posit::<0>::one().exp(64), rounded(constant(E.0, E.1), 64));
+    assert_eq!(Posit::<3>::one().exp(64), rounded(constant(E.0, E.1), 64));
+    assert_eq!(Posit::<1>::from(2.0).ln(64), rounded(constant(LN_2.0, LN_2.1), 64));
+}
+
+#[test]
+fn trig_es() {
+    assert_eq!(Posit::<0>::one().sin(64), rounded(constant(SIN_1.0, SIN_1.1), 64));
+    assert_eq!(Posit::<3>::one().cos(64), rounded(constant(COS_1.0, COS_1.1), 64));
+    assert_eq!(Posit::<1>::from(0.5).atan(64), rounded(constant(ATAN_1_2.0, ATAN_1_2.1), 64));
+    let large = Posit::<0>::from(10.0).pow(20);
+    assert_eq!(-large.sin(64), rounded(constant(SIN_1E20.0, SIN_1E20.1), 64));
+}
+
+#[test]
+fn sqrt_p16_correctly_rounded() {
+    // Every result lies between the midpoints to its neighbours
+    for bits in (1..0x8000).step_by(7) {
+        let x = Posit::from(P16::from_bits(bits));
+        let root = x.clone().sqrt(16);
+        let root_bits = P16::from(root.clone()).to_bits();
+        let lo = (Posit::from(P16::from_bits(root_bits - 1)) + root.clone()).mul_pow2(-1);
+        let hi = (Posit::from(P16::from_bits(root_bits + 1)) + root).mul_pow2(-1);
+        assert!(lo.clone() * lo <= x, "{:?}", x);
+        if root_bits < 0x7fff {
+            assert!(hi.clone() * hi >= x, "{:?}", x);
+        }
+    }
+}
+
+
+test! { floor_positive: (Posit::from(2.75).floor()) = (Posit::from(2.0)) }
+test! { floor_negative: (Posit::from(-2.25).floor()) = (Posit::from(-3.0)) }
+test! { ceil_positive: (Posit::from(2.25).ceil()) = (Posit::from(3.0)) }
+test! { ceil_negative: (Posit::from(-0.75).ceil()) = (Posit::zero()) }
+test! { trunc_negative: (Posit::from(-2.75).trunc()) = (Posit::from(-2.0)) }
+test! { round_tie: (Posit::from(2.5).round()) = (Posit::from(3.0)) }
+test! { round_negative_tie: (Posit::from(-0.5).round()) = (Posit::from(-1.0)) }
+test! { round_below_tie: (Posit::from(0.5 - 2f64.powi(-40)).round()) = (Posit::zero()) }
+test! { fract_negative: (Posit::from(-2.75).fract()) = (Posit::from(-0.75)) }
+test! { fract_large: (Posit::from(2.0).pow(100).fract()) = (Posit::zero()) }
+test! { rem_positive: (Posit::from(7.5) % Posit::from(2.0)) = (Posit::from(1.5)) }
+test! { rem_negative: (Posit::from(-7.5) % Posit::from(-2.0)) = (Posit::from(-1.5)) }
+test! { rem_fraction: (Posit::from(0.75) % Posit::from(0.5)) = (Posit::from(0.25)) }
+test! { rem_small: (Posit::from(0.25) % Posit::from(-3.0)) = (Posit::from(0.25)) }
+test! { rem_large: (Posit::from(2.0).pow(100) % Posit::from(3.0)) = (Posit::one()) }
+test! { rem_zero: (Posit::one() % Posit::zero()) = (Posit::nar()) }
+test! { cbrt_2: (Posit::from(2.0).cbrt(64)) = (CBRT_2) @ (64) }
+test! { cbrt_exact: (Posit::from(-27.0).cbrt(16)) = (Posit::from(-3.0)) }
+test! { hypot_exact: (Posit::from(3.0).hypot(Posit::from(-4.0), 8)) = (Posit::from(5.0)) }
+test! { hypot_1_2: (Posit::one().hypot(Posit::from(2.0), 64)) = (SQRT_5) @ (64) }
+test! { exp_m1_small: (Posit::from(2f64.powi(-30)).exp_m1(64)) = (EXP_M1_SMALL) @ (64) }
+test! { exp_m1_large: (Posit::from(-100.0).exp_m1(32)) = (Posit::from(-1.0)) }
+test! { ln_1p_small: (Posit::from(2f64.powi(-30)).ln_1p(64)) = (LN_1P_SMALL) @ (64) }
+test! { log_exact: (Posit::from(8.0).log(Posit::from(2.0), 16)) = (Posit::from(3.0)) }
+test! { log_3_10: (Posit::from(10.0).log(Posit::from(3.0), 64)) = (LOG3_10) @ (64) }
+test! { asinh_1: (Posit::one().asinh(64)) = (ASINH_1) @ (64) }
+test! { asinh_small: (Posit::from(2f64.powi(-30)).asinh(64)) = (ASINH_SMALL) @ (64) }
+test! { acosh_2: (Posit::from(2.0).acosh(64)) = (ACOSH_2) @ (64) }
+test! { acosh_1: (Posit::one().acosh(64)) = (Posit::zero()) }
+test! { acosh_below_1: (Posit::from(0.5).acosh(64)) = (Posit::nar()) }
+test! { atanh_1_2: (Posit::from(0.5).atanh(64)) = (ATANH_1_2) @ (64) }
+test! { atanh_small: (Posit::from(2f64.powi(-30)).atanh(64)) = (ATANH_SMALL) @ (64) }
+test! { atanh_1: (Posit::one().atanh(64)) = (Posit::nar()) }
+
+#[test]
+fn odd_functions() {
+    let x: Posit = Posit::from(0.5);
+    assert_eq!((-x.clone()).asinh(64), -x.clone().asinh(64));
+    assert_eq!((-x.clone()).atanh(64), -x.clone().atanh(64));
+    assert_eq!((-x.clone()).cbrt(64), -x.cbrt(64));
+}
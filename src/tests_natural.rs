@@ -0,0 +1,106 @@
+use bitvec::prelude::*;
+use proptest::prelude::*;
+
+use crate::natural::Natural;
+use crate::TestPosit as Posit;
+
+fn natural(bytes: &[u8]) -> Natural {
+    Natural::from_bit_range(bytes, 0, bytes.len() * 8)
+}
+
+#[test]
+fn karatsuba_square() {
+    // (2^n - 1)^2 = 2^2n - 2^(n+1) + 1, long enough to split several times
+    for &n in &[64 * 31, 64 * 32, 64 * 100 + 17, 64 * 257] {
+        let x = &Natural::power_of_two(n) - &Natural::power_of_two(0);
+        let expected = &(&Natural::power_of_two(2 * n) - &Natural::power_of_two(n + 1))
+            + &Natural::power_of_two(0);
+        assert_eq!(&x * &x, expected, "{}", n);
+    }
+}
+
+#[test]
+fn bit_ranges() {
+    let bytes = [0b1011_0010, 0xff, 0b0100_0001];
+    assert_eq!(
+        Natural::from_bit_range(&bytes, 2, 7),
+        Natural::from_digits(&[2, 5])
+    );
+    assert_eq!(
+        Natural::from_bit_range(&bytes, 4, 24),
+        natural(&[0b0010, 0xff, 0b0100_0001])
+    );
+    // Bits past the end read as zeros
+    assert_eq!(
+        Natural::from_bit_range(&bytes, 20, 30),
+        natural(&[0b0100_0000])
+    );
+
+    let mut written = [0; 3];
+    natural(&[0xff, 0b0100_0001]).write_bits(13, &mut written, 5);
+    assert_eq!(written, [0b0000_0111, 0b1101_0000, 0b0100_0000]);
+}
+
+#[test]
+fn division_corrections() {
+    // Quotients just below and at powers of two, with remainders of zero and `d - 1`
+    let d = &Natural::power_of_two(130) - &Natural::power_of_two(3);
+    for &k in &[0, 1, 64, 200] {
+        let q = Natural::power_of_two(k);
+        for r in [Natural::zero(), &d - &Natural::power_of_two(0)].iter() {
+            let n = &(&q * &d) + r;
+            assert_eq!(n.div_rem(&d), (q.clone(), r.clone()));
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn multiplication_distributes(
+        a in prop::collection::vec(any::<u8>(), 0..1200),
+        b in prop::collection::vec(any::<u8>(), 0..600),
+        c in prop::collection::vec(any::<u8>(), 0..600),
+    ) {
+        let (a, b, c) = (natural(&a), natural(&b), natural(&c));
+        prop_assert_eq!(&a * &(&b + &c), &(&a * &b) + &(&a * &c));
+    }
+
+    #[test]
+    fn division_remainder(
+        n in prop::collection::vec(any::<u8>(), 0..400),
+        d in prop::collection::vec(any::<u8>(), 1..200),
+    ) {
+        let (n, d) = (natural(&n), natural(&d));
+        prop_assume!(!d.is_zero());
+        let (q, r) = n.div_rem(&d);
+        prop_assert!(r < d);
+        prop_assert_eq!(&(&q * &d) + &r, n);
+    }
+
+    #[test]
+    fn square_root(a in prop::collection::vec(any::<u8>(), 0..300)) {
+        let a = natural(&a);
+        let (root, rem) = a.sqrt_rem();
+        prop_assert_eq!(&(&root * &root) + &rem, a);
+        prop_assert!(rem <= &root + &root);
+    }
+
+    #[test]
+    fn shifts(a in prop::collection::vec(any::<u8>(), 0..100), n in 0usize..300) {
+        let a = natural(&a);
+        prop_assert_eq!(&(&a << n) >> n, a.clone());
+        prop_assert_eq!(&a << n, &a * &Natural::power_of_two(n));
+    }
+
+    #[test]
+    fn wide_posit_arithmetic(
+        a in prop::collection::vec(any::<bool>(), 2..400),
+        b in prop::collection::vec(any::<bool>(), 2..400),
+    ) {
+        let a = Posit { bits: a.into_iter().collect::<BitVec>() };
+        let b = Posit { bits: b.into_iter().collect::<BitVec>() };
+        prop_assume!(!a.is_nar() && !b.is_nar() && !b.is_zero());
+        prop_assert_eq!(&(&a * &b) / &b, a.clone());
+        prop_assert_eq!(&(&a + &b) - &b, a.clone());
+    }
+}
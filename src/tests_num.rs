@@ -0,0 +1,236 @@
+use std::num::FpCategory;
+
+use num_traits::{Bounded, Float, FromPrimitive, Num, One, Signed, ToPrimitive, Zero};
+
+use crate::{with_precision, PositContext, Rounding, TestPosit as Posit, P16, P32, P64, P8};
+
+/// Ternary search for the minimum of a unimodal function, as in `tests_posit::minima`.
+fn minimum<T: Float>(mut left: T, mut right: T, f: impl Fn(T) -> T) -> T {
+    let three = T::from(3).unwrap();
+    let eps = T::from(1e-6).unwrap();
+    while right - left > eps {
+        let left_third = left + (right - left) / three;
+        let right_third = right - (right - left) / three;
+        if f(left_third) < f(right_third) {
+            right = right_third;
+        } else {
+            left = left_third;
+        }
+    }
+    (left + right) / T::from(2).unwrap()
+}
+
+fn parabola_minimum<T: Float>() -> T {
+    let center = T::from(4.0 / 3.0).unwrap();
+    minimum(T::from(-2).unwrap(), T::from(4.65).unwrap(), |x| (x - center).powi(2))
+}
+
+#[test]
+fn generic_minimum() {
+    let expected = 4.0 / 3.0;
+    assert!((parabola_minimum::<f64>() - expected).abs() < 1e-5);
+    assert!((parabola_minimum::<P32>().to_f64().unwrap() - expected).abs() < 1e-5);
+    assert!((parabola_minimum::<P64>().to_f64().unwrap() - expected).abs() < 1e-5);
+}
+
+/// `minimum` for types which are not `Copy`.
+fn real_minimum<T: crate::Real>(mut left: T, mut right: T, f: impl Fn(T) -> T) -> T {
+    let three = T::from(3).unwrap();
+    let eps = T::from(1e-6).unwrap();
+    while right.clone() - left.clone() > eps {
+        let third = (right.clone() - left.clone()) / three.clone();
+        let left_third = left.clone() + third.clone();
+        let right_third = right.clone() - third;
+        if f(left_third.clone()) < f(right_third.clone()) {
+            right = right_third;
+        } else {
+            left = left_third;
+        }
+    }
+    (left + right) / T::from(2).unwrap()
+}
+
+fn real_parabola_minimum<T: crate::Real>() -> T {
+    let center = T::from(4.0 / 3.0).unwrap();
+    real_minimum(T::from(-2).unwrap(), T::from(4.65).unwrap(), |x| {
+        (x - center.clone()).powi(2)
+    })
+}
+
+#[test]
+fn generic_real_minimum() {
+    let expected = 4.0 / 3.0;
+    assert!((real_parabola_minimum::<f64>() - expected).abs() < 1e-5);
+    assert!((real_parabola_minimum::<P32>().to_f64().unwrap() - expected).abs() < 1e-5);
+    let minimum = with_precision(64, real_parabola_minimum::<Posit>);
+    assert!(minimum.bits.len() <= 64);
+    assert!((minimum.to_f64() - expected).abs() < 1e-5);
+}
+
+#[test]
+fn posit_real() {
+    use crate::Real;
+
+    let two = Posit::from(2);
+    let root = Real::sqrt(two.clone());
+    assert_eq!(root, two.clone().sqrt(two.bits.len() + 16));
+    assert_eq!(with_precision(32, || Real::sqrt(two.clone())), two.clone().sqrt(32));
+    assert_eq!(Real::powf(Posit::from(-2), Posit::from(3)), Posit::from(-8));
+    assert!(Real::powf(Posit::from(-2), Posit::from(0.5)).is_nar());
+    assert_eq!(Real::powi(two.clone(), -2), Posit::from(0.25));
+    assert_eq!(Real::recip(Posit::from(4)), Posit::from(0.25));
+    assert!(<Posit as Real>::nan().is_nar());
+    assert_eq!(Real::classify(&Posit::zero()), FpCategory::Zero);
+    assert!(Real::abs_sub(Posit::nar(), two.clone()).is_nar());
+    // Exact without a context, rounded once with one
+    let tiny = Posit::from(2f64.powi(-40));
+    let fma = |x: Posit| Real::mul_add(x, Posit::from(3), tiny.clone());
+    assert_eq!(fma(Posit::one()), Posit::from(3.0 + 2f64.powi(-40)));
+    assert_eq!(with_precision(16, || fma(Posit::one())), Posit::from(3));
+    let up = PositContext { nbits: Some(16), rounding: Rounding::Up, ..PositContext::default() };
+    assert_eq!(up.scope(|| fma(Posit::one())), Posit::from(3).next_up(16));
+    assert_eq!(up.scope(|| fma(-Posit::one())), -Posit::from(3).next_down(16));
+    let down = PositContext { rounding: Rounding::Down, ..up };
+    assert_eq!(down.scope(|| fma(Posit::one())), Posit::from(3));
+    let (sin, cos) = with_precision(32, || Real::sin_cos(Posit::one()));
+    assert_eq!((sin, cos), (Posit::one().sin(32), Posit::one().cos(32)));
+}
+
+#[test]
+fn posit_traits() {
+    assert!(Zero::is_zero(&<Posit as Zero>::zero()));
+    assert_eq!(<Posit as One>::one(), Posit::from(1));
+    assert_eq!(Posit::from_str_radix("-2.5", 10), Ok(Posit::from(-2.5)));
+    assert!(Posit::from_str_radix("101", 2).is_err());
+    assert_eq!(Signed::abs(&Posit::from(-3)), Posit::from(3));
+    assert_eq!(Signed::signum(&Posit::from(-0.25)), Posit::from(-1));
+    assert_eq!(Signed::signum(&Posit::zero()), Posit::zero());
+    assert!(Signed::signum(&Posit::nar()).is_nar());
+    assert_eq!(Posit::from(2).abs_sub(&Posit::from(5)), Posit::zero());
+    assert_eq!(Posit::from(5).abs_sub(&Posit::from(2)), Posit::from(3));
+    assert!(Posit::nar().abs_sub(&Posit::from(2)).is_nar());
+    assert!(Posit::from(2).abs_sub(&Posit::nar()).is_nar());
+    assert!(!Signed::is_negative(&Posit::nar()) && !Signed::is_positive(&Posit::nar()));
+    assert!(!Signed::is_positive(&Posit::zero()));
+    assert_eq!(Posit::from_i128(i128::MIN), Some(-Posit::from(2.0).pow(127)));
+    assert_eq!(Posit::from_f32(0.1), Some(Posit::from(0.1f32 as f64)));
+    assert!(Posit::from_f64(f64::INFINITY).unwrap().is_nar());
+}
+
+#[test]
+fn posit_to_primitive() {
+    let x = Posit::from(-2.75);
+    assert_eq!(x.to_i64(), Some(-2));
+    assert_eq!(x.to_u64(), None);
+    assert_eq!(ToPrimitive::to_f64(&x), Some(-2.75));
+    assert_eq!(Posit::from(u128::MAX).to_u128(), Some(u128::MAX));
+    assert_eq!(Posit::from(2.0).pow(64).to_u64(), None);
+    assert_eq!(Posit::nar().to_i8(), None);
+    assert!(ToPrimitive::to_f32(&Posit::nar()).unwrap().is_nan());
+}
+
+#[test]
+fn num_cast() {
+    let big = <Posit as num_traits::NumCast>::from(u64::MAX).unwrap();
+    assert_eq!(big, Posit::from(u64::MAX));
+    assert_eq!(<Posit as num_traits::NumCast>::from(1.5f32), Some(Posit::from(1.5)));
+    assert_eq!(<Posit as num_traits::NumCast>::from(-7i8), Some(Posit::from(-7)));
+    assert_eq!(<P8 as num_traits::NumCast>::from(1000), Some(P8::from(Posit::from(1000))));
+    assert_eq!(<P16 as num_traits::NumCast>::from(P32::from(Posit::from(0.5))), Some(P16::from(Posit::from(0.5))));
+}
+
+#[test]
+fn fixed_constants() {
+    assert_eq!(P32::zero(), P32::ZERO);
+    assert_eq!(P32::one(), P32::ONE);
+    assert_eq!(<P16 as Bounded>::max_value(), P16::MAXPOS);
+    assert_eq!(<P16 as Bounded>::min_value(), -P16::MAXPOS);
+    assert_eq!(<P8 as Float>::min_positive_value(), P8::MINPOS);
+    assert_eq!(P8::epsilon(), P8::from(Posit::from(0.125)));
+    assert_eq!(P32::epsilon(), P32::from(Posit::from(2f64.powi(-27))));
+    assert!(P32::nan().is_nan() && P32::infinity().is_nan());
+    assert_eq!(P32::neg_zero(), P32::ZERO);
+    assert_eq!(P32::ZERO.classify(), FpCategory::Zero);
+    assert_eq!(P32::NAR.classify(), FpCategory::Nan);
+    assert_eq!(P32::MINPOS.classify(), FpCategory::Normal);
+    assert!(!P32::MAXPOS.is_infinite() && P32::MAXPOS.is_finite());
+}
+
+#[test]
+fn fixed_num() {
+    assert_eq!(P32::from_str_radix("0.1", 10), Ok(P32::from(Posit::from_str_rounded("0.1", 32).unwrap())));
+    assert_eq!(P32::from_f64(2.5).unwrap() % P32::from_f64(1.0).unwrap(), P32::from_f64(0.5).unwrap());
+    assert_eq!(P16::from_i32(-3).unwrap().to_i32(), Some(-3));
+    assert_eq!(Signed::signum(&P16::from_f64(-0.1).unwrap()), -P16::ONE);
+    assert!(Signed::is_positive(&P16::MINPOS));
+    assert!(!Signed::is_negative(&P16::NAR));
+}
+
+macro_rules! test_float_fn {
+    ($name: ident: $method: ident($($arg: expr),*) => $posit: expr) => {
+        #[test]
+        fn $name() {
+            for &x in &[0.3, -0.3, 1.0, 2.5, -7.0, 1e-3, 1e6] {
+                let p64 = P64::from_f64(x).unwrap();
+                let p = Posit::from(p64);
+                assert_eq!(Posit::from(p64.$method($($arg),*)), $posit(p.clone(), 64), "{}", x);
+                let p16 = P16::from_f64(x).unwrap();
+                let p = Posit::from(p16);
+                assert_eq!(Posit::from(p16.$method($(P16::from(Posit::from($arg))),*)), $posit(p, 16), "{}", x);
+            }
+        }
+    };
+}
+
+test_float_fn! { float_sqrt: sqrt() => Posit::sqrt }
+test_float_fn! { float_exp: exp() => Posit::exp }
+test_float_fn! { float_ln: ln() => Posit::ln }
+test_float_fn! { float_sin: sin() => Posit::sin }
+test_float_fn! { float_atan: atan() => Posit::atan }
+test_float_fn! { float_tanh: tanh() => Posit::tanh }
+test_float_fn! { float_cbrt: cbrt() => Posit::cbrt }
+test_float_fn! { float_asinh: asinh() => Posit::asinh }
+test_float_fn! { float_exp_m1: exp_m1() => Posit::exp_m1 }
+test_float_fn! { float_recip: recip() => |p, n| Posit::one().div_rounded(p, n) }
+test_float_fn! { float_floor: floor() => |p: Posit, _| p.floor() }
+test_float_fn! { float_round: round() => |p: Posit, _| p.round() }
+
+#[test]
+fn float_pow() {
+    let x = P32::from_f64(-1.5).unwrap();
+    assert_eq!(x.powi(3), P32::from_f64(-3.375).unwrap());
+    assert_eq!(x.powi(-2), P32::from(Posit::from(4).div_rounded(Posit::from(9), 32)));
+    assert_eq!(x.powi(0), P32::ONE);
+    assert_eq!(x.powf(P32::from_f64(2.0).unwrap()), P32::from_f64(2.25).unwrap());
+    assert!(x.powf(P32::from_f64(0.5).unwrap()).is_nan());
+    assert!(P32::ZERO.powi(-1).is_nan());
+    assert_eq!(P16::from_f64(2.0).unwrap().powi(100), P16::MAXPOS);
+    assert_eq!(P16::from_f64(-2.0).unwrap().powi(-101), -P16::MINPOS);
+}
+
+#[test]
+fn float_misc() {
+    let a = P8::from_f64(1.125).unwrap();
+    let c = P8::from_f64(-1.25).unwrap();
+    // Rounding the product first would give zero
+    assert_eq!(a.mul_add(a, c), P8::from_f64(2f64.powi(-6)).unwrap());
+    let one = P32::ONE;
+    assert_eq!(Float::max(one, P32::NAR), one);
+    assert_eq!(Float::min(P32::NAR, one), one);
+    assert_eq!(Float::min(one, -one), -one);
+    assert_eq!(P16::from_f64(3.0).unwrap().hypot(P16::from_f64(4.0).unwrap()), P16::from_f64(5.0).unwrap());
+    assert_eq!(P32::from_f64(8.0).unwrap().log(P32::from_f64(2.0).unwrap()), P32::from_f64(3.0).unwrap());
+    let atan2 = Posit::from(-1).atan2(Posit::from(-1), 32);
+    assert_eq!((-one).atan2(-one), P32::from(atan2));
+    assert!((P64::from_f64(180.0).unwrap().to_radians().to_f64().unwrap() - std::f64::consts::PI).abs() < 1e-15);
+    assert!(P32::MAXPOS.is_sign_positive() && (-P32::MAXPOS).is_sign_negative());
+}
+
+#[test]
+fn integer_decode() {
+    let x = P32::from_f64(-6.5).unwrap();
+    assert_eq!(x.integer_decode(), (13, -1, -1));
+    assert_eq!(P64::MINPOS.integer_decode(), (1, -248, 1));
+    assert_eq!(P64::ONE.integer_decode(), (1, 0, 1));
+    assert_eq!(P8::ZERO.integer_decode(), (0, 0, 1));
+}
@@ -1,10 +1,9 @@
 use lazy_static::lazy_static;
 
-use crate::Posit;
+use crate::{Posit, Quire};
 
 lazy_static! {
     static ref BS: Vec<usize> = vec![5, 8, 12, 15, 20];
-    static ref EPS: Posit = 10e-8.into();
     static ref RES: Posit = 8779.0.into();
 }
 
@@ -31,29 +30,15 @@ fn init_v2(a: usize) -> Vec<Posit> {
 }
 
 fn dot(left: Vec<Posit>, right: Vec<Posit>) -> Posit {
-    let mut res = Posit::zero();
-    for (a, b) in left.into_iter().zip(right.into_iter()) {
-        dbg!(&a, &b);
-        let prod = a * b;
-        dbg!(&res, &prod);
-        res = res + prod;
-        dbg!(&res);
-    }
-    res
+    Posit::fdp(&left, &right, 64)
 }
 
 #[test]
 fn test_a5() {
     let x = init_v1(5);
     for &i in BS.iter() {
-        dbg!(i);
         let y = init_v2(i);
-        let dot = dot(x.clone(), y);
-        println!("{:?}", dot);
-        dbg!(&dot, RES.clone());
-        let res = (dot - RES.clone()).abs();
-        dbg!(&res, EPS.clone(), i);
-        assert!(res < EPS.clone());
+        assert_eq!(dot(x.clone(), y), *RES, "{}", i);
     }
 }
 
@@ -62,8 +47,18 @@ fn test_a10() {
     let x = init_v1(10);
     for &i in BS.iter() {
         let y = init_v2(i);
-        let dot = dot(x.clone(), y);
-        println!("{:?}", dot);
-        assert!((dot - RES.clone()).abs() < EPS.clone());
+        assert_eq!(dot(x.clone(), y), *RES, "{}", i);
+    }
+}
+
+#[test]
+fn test_quire() {
+    for &a in [5, 10].iter() {
+        let x = init_v1(a);
+        for &i in BS.iter() {
+            let y = init_v2(i);
+            // 8779 is exact in 32 bits, the quire rounds only once
+            assert_eq!(Quire::fdp(&x, &y).to_posit(32), *RES);
+        }
     }
 }
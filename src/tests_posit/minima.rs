@@ -1,6 +1,6 @@
 use lazy_static::lazy_static;
 
-use crate::Posit;
+use crate::{Interval, Posit, Real, P32};
 
 const LIMIT: u32 = 10000000;
 lazy_static! {
@@ -8,21 +8,24 @@ lazy_static! {
     static ref EPS: Posit = Posit::from(10e-8);
 }
 
-fn half_divide_method(
-    mut left: Posit,
-    mut right: Posit,
-    stop: Posit,
-    f: impl Fn(Posit) -> Posit,
+fn half_divide_method<T: Real + PartialOrd>(
+    mut left: T,
+    mut right: T,
+    stop: T,
+    f: impl Fn(T) -> T,
     iterations: u32,
-) -> Posit {
-    let mut x: Posit = (left.clone() + right.clone()) / 2.0.into();
+) -> T {
+    let two = T::from(2.0).unwrap();
+    let three = T::from(3.0).unwrap();
+    let eps = T::from(10e-8).unwrap();
+    let mut x = (left.clone() + right.clone()) / two.clone();
     let mut i = 0;
     while f(x.clone()) >= stop && i < iterations {
-        if (right.clone() - left.clone()) < EPS.clone() {
+        if right.clone() - left.clone() < eps {
             break;
         }
-        let left_third = left.clone() + (right.clone() - left.clone()) / 3.0.into();
-        let right_third = right.clone() - (right.clone() - left.clone()) / 3.0.into();
+        let left_third = left.clone() + (right.clone() - left.clone()) / three.clone();
+        let right_third = right.clone() - (right.clone() - left.clone()) / three.clone();
         let f1 = f(left_third.clone());
         let f2 = f(right_third.clone());
         if f1 < f2 {
@@ -30,25 +33,97 @@ fn half_divide_method(
         } else {
             left = left_third;
         }
-        x = (left.clone() + right.clone()) / 2.0.into();
+        x = (left.clone() + right.clone()) / two.clone();
         i += 1;
     }
     x
 }
 
-fn parabola(x: Posit) -> Posit {
-    (x - RES.clone()).pow(2)
+/// Ternary search for the minimum of a unimodal `f` given by interval bounds of `nbits`
+/// bits. The bracket only shrinks when the bounds prove on which side of a third the minimum
+/// lies, so the minimum is still inside the final bracket.
+fn bracket_minimum(
+    mut left: Posit,
+    mut right: Posit,
+    f: impl Fn(Interval) -> Interval,
+    nbits: usize,
+) -> Interval {
+    let round = |mut p: Posit| {
+        p.round_to(nbits);
+        p
+    };
+    loop {
+        let third = (&right - &left).div_rounded(Posit::from(3.0), nbits);
+        let left_third = round(&left + &third);
+        let right_third = round(&right - &third);
+        if !(left < left_third && left_third < right_third && right_third < right) {
+            break;
+        }
+        let f1 = f(Interval::from(left_third.clone()));
+        let f2 = f(Interval::from(right_third.clone()));
+        if f1.is_entire() || f2.is_entire() {
+            break;
+        }
+        if f1.hi() < f2.lo() {
+            right = right_third;
+        } else if f2.hi() < f1.lo() {
+            left = left_third;
+        } else {
+            break;
+        }
+    }
+    Interval::new(left, right)
+}
+
+fn parabola<T: Real>(x: T) -> T {
+    (x - T::from(4.0 / 3.0).unwrap()).powi(2)
+}
+
+/// Minimum of the parabola found with `T` arithmetic.
+fn minimum<T: Real>() -> Posit
+where
+    Posit: From<T>,
+{
+    // The parabola is the squared distance to the minimum, so stopping below EPS squared keeps
+    // the result within EPS
+    let minima = half_divide_method(
+        T::from(-2.0).unwrap(),
+        T::from(4.65).unwrap(),
+        T::from(10e-16).unwrap(),
+        parabola,
+        LIMIT,
+    );
+    Posit::from(minima)
+}
+
+fn parabola_bounds(x: Interval, nbits: usize) -> Interval {
+    let d = x.sub_rounded(Interval::from(RES.clone()), nbits);
+    d.clone().mul_rounded(d, nbits)
 }
 
 #[test]
 fn test_minima() {
-    let minima = half_divide_method(Posit::from(-2.0), Posit::from(4.65), Posit::from(0.000001), parabola, LIMIT);
-    let err = (RES.clone() - minima.clone()).abs();
-    println!(
-        "Minima: {:?}\nRes: {:?}\nError: {:?}",
-        minima.clone(),
-        &*RES,
-        &err,
+    for minima in [minimum::<Posit>(), minimum::<f64>(), minimum::<P32>()] {
+        let err = (&*RES - &minima).abs();
+        println!(
+            "Minima: {:?}\nRes: {:?}\nError: {:?}",
+            minima.clone(),
+            &*RES,
+            &err,
+        );
+        assert!(err < *EPS);
+    }
+}
+
+#[test]
+fn test_minima_bracket() {
+    let bracket = bracket_minimum(
+        Posit::from(-2.0),
+        Posit::from(4.65),
+        |x| parabola_bounds(x, 32),
+        32,
     );
-    assert!(err < EPS.clone());
+    println!("Bracket: {}\nRes: {:?}", bracket, &*RES);
+    assert!(bracket.contains(&RES));
+    assert!(bracket.width() < *EPS);
 }
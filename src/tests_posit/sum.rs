@@ -1,6 +1,8 @@
+#![allow(clippy::excessive_precision)]
+
 use lazy_static::lazy_static;
 
-use crate::Posit;
+use crate::{Posit, Quire};
 
 const LIMIT: u32 = 4801;
 lazy_static! {
@@ -10,12 +12,22 @@ lazy_static! {
 
 #[test]
 fn test_sum() {
-    let mut sum = Posit::zero();
+    let terms: Vec<Posit> = (1..LIMIT)
+        .map(|i| Posit::from(1.0 / (i as f64)).pow(2))
+        .collect();
+    let sum = Posit::fsum(&terms, 64);
+    let err = (&sum - &*RES).abs();
+    println!("Sum: {:?}\nRes: {:?}\nErr: {:?}\nEps: {:?}", &sum, &*RES, &err, &*EPS);
+    assert!(err < *EPS);
+}
+
+#[test]
+fn test_quire_sum() {
+    let mut quire = Quire::new();
     for i in 1..LIMIT {
-        sum = sum + Posit::from(1.0 / (i as f64)).pow(2);
-        dbg!(i);
+        quire.add_posit(&Posit::from(1.0 / (i as f64)).pow(2));
     }
-    let err = (sum.clone() - RES.clone()).abs();
-    println!("Sum: {:?}\nRes: {:?}\nErr: {:?}\nEps: {:?}", &sum, &*RES, &err, &*EPS);
-    assert!(err < EPS.clone());
+    let sum = quire.to_posit(64);
+    let err = (&sum - &*RES).abs();
+    assert!(err < *EPS);
 }
@@ -0,0 +1,154 @@
+use proptest::prelude::*;
+
+use crate::softposit::*;
+use crate::P32;
+
+/// Posit with the two's complement pattern `bits` of `nbits` bits.
+fn posit<const ES: usize>(bits: u32, nbits: usize) -> super::Posit<ES> {
+    let sign = 1 << (nbits - 1);
+    let bits = if bits & sign != 0 && bits != sign {
+        sign | (bits.wrapping_neg() & (sign - 1))
+    } else {
+        bits
+    };
+    super::Posit::from_bit_str(&format!("0x{:0width$x}", bits, width = nbits / 4)).unwrap()
+}
+
+fn rounded<const ES: usize>(mut p: super::Posit<ES>, nbits: usize) -> super::Posit<ES> {
+    p.round_to(nbits);
+    p
+}
+
+fn p8(bits: u8) -> super::Posit<0> {
+    posit(bits.into(), 8)
+}
+
+fn p16(bits: u16) -> super::Posit<1> {
+    posit(bits.into(), 16)
+}
+
+#[test]
+fn p8_exhaustive() {
+    for a in 0..=u8::MAX {
+        let (x, l) = (castP8(a), p8(a));
+        for b in 0..=u8::MAX {
+            let (y, r) = (castP8(b), p8(b));
+            let expected = |p| rounded(p, 8);
+            assert_eq!(p8(p8_add(x, y).v), expected(&l + &r), "{:x} + {:x}", a, b);
+            assert_eq!(p8(p8_sub(x, y).v), expected(&l - &r), "{:x} - {:x}", a, b);
+            assert_eq!(p8(p8_mul(x, y).v), expected(&l * &r), "{:x} * {:x}", a, b);
+            let quotient = l.clone().div_rounded(r.clone(), 8);
+            assert_eq!(p8(p8_div(x, y).v), quotient, "{:x} / {:x}", a, b);
+            assert_eq!(p8_lt(x, y), l < r);
+            assert_eq!(p8_le(x, y), l <= r);
+            assert_eq!(p8_eq(x, y), l == r);
+            assert_eq!(p8_cmp(x, y), l.cmp(&r) as i32);
+        }
+        assert_eq!(p8(p8_sqrt(x).v), l.clone().sqrt(8), "sqrt {:x}", a);
+        let nearest = super::Posit::from(l.to_f64().round_ties_even());
+        assert_eq!(p8(p8_roundToInt(x).v), rounded(nearest, 8), "round {:x}", a);
+        assert_eq!(p8_to_f64(x).to_bits(), l.to_f64().to_bits());
+        assert_eq!(p8_from_f64(l.to_f64()), x);
+        assert_eq!(p16_to_p8(p8_to_p16(x)), x);
+        assert_eq!(p32_to_p8(p8_to_p32(x)), x);
+    }
+}
+
+#[test]
+fn rounding_to_integers() {
+    let half = p32_from_f64(0.5);
+    assert_eq!(p32_roundToInt(half).v, 0);
+    assert_eq!(p32_to_i32(half), 0);
+    assert_eq!(p32_to_i32(p32_from_f64(1.5)), 2);
+    assert_eq!(p32_to_i32(p32_from_f64(2.5)), 2);
+    assert_eq!(p32_to_i64(p32_from_f64(-2.75)), -3);
+    assert_eq!(p32_to_f64(p32_roundToInt(p32_from_f64(-2.5))), -2.0);
+    let maxpos = castP32(i32::MAX as u32);
+    assert_eq!(p32_roundToInt(maxpos), maxpos);
+    assert_eq!(p32_to_i32(maxpos), i32::MAX);
+    assert_eq!(p32_to_i64(castP32(maxpos.v.wrapping_neg())), i64::MIN);
+    assert_eq!(p32_to_ui64(maxpos), u64::MAX);
+    assert_eq!(p32_to_ui32(p32_from_f64(-7.0)), 0);
+
+    let nar = castP32(1 << 31);
+    assert_eq!(p32_to_i32(nar), i32::MIN);
+    assert_eq!(p32_to_i64(nar), i64::MIN);
+    assert_eq!(p32_to_ui32(nar), 1 << 31);
+    assert_eq!(p32_to_ui64(nar), 1 << 63);
+    assert!(p32_to_f64(nar).is_nan());
+    assert_eq!(p32_from_f64(f64::INFINITY), nar);
+    assert_eq!(i64_to_p32(i64::MIN), p32_from_f64(i64::MIN as f64));
+    assert_eq!(ui64_to_p32(u64::MAX), p32_from_f64(u64::MAX as f64));
+    assert_eq!(p32_to_i32(i32_to_p32(-1_000_000)), -1_000_000);
+}
+
+#[test]
+fn fused_multiply_add() {
+    // 1 - 2^-30 rounds to 1 in 32 bits, so the product must not be rounded first
+    let x = p32_from_f64(1.0 + 2f64.powi(-15));
+    let y = p32_from_f64(1.0 - 2f64.powi(-15));
+    let minus_one = i32_to_p32(-1);
+    assert_eq!(p32_add(p32_mul(x, y), minus_one).v, 0);
+    assert_eq!(p32_to_f64(p32_mulAdd(x, y, minus_one)), -(2f64.powi(-30)));
+    assert_eq!(
+        p16_mulAdd(castP16(0), castP16(1 << 15), i32_to_p16(3)).v,
+        1 << 15
+    );
+}
+
+proptest! {
+    #[test]
+    fn p16_arithmetic(a in any::<u16>(), b in any::<u16>(), c in any::<u16>()) {
+        let (x, y, z) = (castP16(a), castP16(b), castP16(c));
+        let (l, r) = (p16(a), p16(b));
+        prop_assert_eq!(p16(p16_add(x, y).v), rounded(&l + &r, 16));
+        prop_assert_eq!(p16(p16_sub(x, y).v), rounded(&l - &r, 16));
+        prop_assert_eq!(p16(p16_mul(x, y).v), rounded(&l * &r, 16));
+        prop_assert_eq!(p16(p16_div(x, y).v), l.clone().div_rounded(r.clone(), 16));
+        prop_assert_eq!(p16(p16_sqrt(x).v), l.clone().sqrt(16));
+        let fused = l.clone().mul_add_rounded(r.clone(), p16(c), 16);
+        prop_assert_eq!(p16(p16_mulAdd(x, y, z).v), fused);
+        prop_assert_eq!(p16_lt(x, y), l < r);
+        prop_assert_eq!(p16_to_f64(x).to_bits(), l.to_f64().to_bits());
+        prop_assert_eq!(p32_to_p16(p16_to_p32(x)), x);
+    }
+
+    #[test]
+    fn p32_arithmetic(a in any::<u32>(), b in any::<u32>()) {
+        let (x, y) = (castP32(a), castP32(b));
+        let (l, r) = (P32::from_bits(a), P32::from_bits(b));
+        prop_assert_eq!(p32_add(x, y).v, (l + r).to_bits());
+        prop_assert_eq!(p32_sub(x, y).v, (l - r).to_bits());
+        prop_assert_eq!(p32_mul(x, y).v, (l * r).to_bits());
+        prop_assert_eq!(p32_div(x, y).v, (l / r).to_bits());
+        prop_assert_eq!(p32_sqrt(x).v, num_traits::Float::sqrt(l).to_bits());
+        prop_assert_eq!(p32_cmp(x, y), l.cmp(&r) as i32);
+        prop_assert_eq!(p32_from_f64(p32_to_f64(x)), x);
+        let wide = super::Posit::from(l);
+        prop_assert_eq!(p16(p32_to_p16(x).v), rounded(super::Posit::<1>::from(wide.to_f64()), 16));
+    }
+
+    #[test]
+    fn from_f64(x in any::<f64>()) {
+        prop_assert_eq!(p32_from_f64(x).v, P32::from(super::Posit::from(x)).to_bits());
+        prop_assert_eq!(p16(p16_from_f64(x).v), rounded(super::Posit::<1>::from(x), 16));
+    }
+}
+
+#[test]
+fn header_declarations() {
+    // The functions come from a macro, which the header generated by cbindgen cannot see
+    let target = std::env::var("CARGO_TARGET_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target"));
+    let header = std::fs::read_to_string(target.join("unum.h")).unwrap();
+    let functions = [SP8_FUNCTIONS, SP16_FUNCTIONS, SP32_FUNCTIONS].concat();
+    assert_eq!(functions.len(), 3 * 26);
+    for name in functions {
+        assert!(
+            header.contains(&format!(" {}(", name)),
+            "{} is not declared",
+            name
+        );
+    }
+}
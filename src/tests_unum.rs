@@ -0,0 +1,187 @@
+use std::convert::TryFrom;
+
+use bitvec::prelude::*;
+use proptest::prelude::*;
+
+use crate::{TestPosit as Posit, Ubound, Unum};
+
+/// Environment {1, 1} of 2 bits of exponent and 2 of fraction at most.
+type Tiny = Unum<1, 1>;
+type Small = Unum<2, 3>;
+
+fn tiny(x: f64) -> Ubound<1, 1> {
+    Tiny::from(x).into()
+}
+
+fn small(x: f64) -> Ubound<2, 3> {
+    Small::from(x).into()
+}
+
+#[test]
+fn environment() {
+    assert_eq!(Tiny::ESIZEMAX, 2);
+    assert_eq!(Tiny::FSIZEMAX, 2);
+    assert_eq!(Tiny::UTAGSIZE, 3);
+    assert_eq!(Tiny::MAXUBITS, 8);
+    assert_eq!(Unum::<3, 4>::MAXUBITS, 33);
+    assert_eq!(Tiny::maxreal().to_f64(), 6.);
+    assert_eq!(Tiny::smallsubnormal().to_f64(), 0.25);
+    assert_eq!(Small::maxreal().to_f64(), 510.);
+    assert_eq!(Small::smallsubnormal().to_f64(), 2f64.powi(-14));
+}
+
+#[test]
+fn bits() {
+    // 1/3 lies between the subnormals 0.25 and 0.5
+    let third = Tiny::from(1. / 3.);
+    assert_eq!(third.bits(), bitvec![0, 0, 0, 0, 1, 1, 1, 1]);
+    assert_eq!(third.to_string(), "(0.25, 0.5)");
+    assert!(third.to_f64().is_nan());
+    assert_eq!(Tiny::from_bits(&third.bits()), Some(third));
+    assert_eq!(Tiny::from_bits(&bitvec![0, 0, 0, 0, 1, 1, 1]), None);
+
+    // One is the subnormal 1 of the shortest unums
+    let one = Tiny::from(1.);
+    assert_eq!(one, Tiny::new(false, 0, 1, false, 1, 1));
+    assert_eq!(one.bits(), bitvec![0, 0, 1, 0, 0, 0]);
+    assert_eq!(one.size(), 6);
+    assert_eq!(one.utag(), 0);
+    assert_eq!(Tiny::nan().utag(), 0b111);
+
+    let infinity = Tiny::infinity(true);
+    assert_eq!(infinity.bits(), bitvec![1, 1, 1, 1, 1, 0, 1, 1]);
+    assert_eq!(infinity.to_f64(), f64::NEG_INFINITY);
+    assert_eq!(infinity.to_string(), "-inf");
+    assert_eq!(Tiny::nan().to_string(), "NaN");
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(Tiny::from(2.5).to_f64(), 2.5);
+    assert_eq!(Tiny::from(100.), Tiny::new(false, 3, 2, true, 2, 2));
+    assert_eq!(Tiny::from(100.).to_string(), "(6, inf)");
+    assert_eq!(Tiny::from(-100.).to_string(), "(-inf, -6)");
+    assert_eq!(Tiny::from(-0.1).to_string(), "(-0.25, 0)");
+    assert!(Tiny::from(f64::NAN).is_nan());
+    assert!(Tiny::from(f64::INFINITY).is_infinite());
+    assert_eq!(Tiny::from(-0.), Tiny::zero());
+
+    assert_eq!(Posit::try_from(Tiny::from(-2.5)), Ok(Posit::from(-2.5)));
+    assert_eq!(Posit::try_from(Tiny::infinity(false)), Ok(Posit::nar()));
+    assert!(Posit::try_from(Tiny::from(0.3)).is_err());
+    assert!(Tiny::from(Posit::nar()).is_nan());
+    assert_eq!(Small::from(Posit::from(0.375)).to_f64(), 0.375);
+}
+
+#[test]
+fn optimize() {
+    // 2 with two bits of exponent and fraction is 2 with one of each
+    let two = Tiny::new(false, 2, 0, false, 2, 2);
+    assert_eq!(two.to_f64(), 2.);
+    assert_eq!(two.optimize(), Tiny::new(false, 1, 0, false, 1, 1));
+    let gap = Tiny::new(true, 1, 2, true, 2, 2);
+    assert_eq!(gap.to_string(), "(-1.75, -1.5)");
+    assert_eq!(gap.optimize(), gap);
+    assert_eq!(
+        Tiny::maxreal().optimize(),
+        Tiny::new(false, 3, 1, false, 2, 1)
+    );
+
+    let ubound = Ubound::new(Tiny::new(false, 0, 2, false, 2, 2), Tiny::infinity(false));
+    assert_eq!(ubound.to_string(), "[0.5, inf]");
+    assert_eq!(ubound.optimize().lo(), Tiny::new(false, 0, 1, false, 1, 2));
+}
+
+#[test]
+fn unify() {
+    // (1, 1.25) and (1.25, 1.5) are both in the interval after 1 with one fraction bit less
+    let ubound = Ubound::new(Tiny::from(1.1), Tiny::from(1.4));
+    assert_eq!(ubound.to_string(), "(1, 1.5)");
+    let unified = ubound.unify().unwrap();
+    assert_eq!(unified.to_string(), "(1, 1.5)");
+    assert_eq!(unified.size(), 7);
+
+    assert_eq!(Ubound::new(Tiny::from(1.), Tiny::from(1.4)).unify(), None);
+    assert_eq!(Ubound::new(Tiny::from(-0.1), Tiny::from(0.1)).unify(), None);
+    assert_eq!(tiny(1.).unify(), Some(Tiny::from(1.)));
+    assert_eq!(
+        Ubound::new(Tiny::from(7.), Tiny::from(8.)).unify(),
+        Some(Tiny::from(100.))
+    );
+    assert_eq!(
+        Ubound::new(Tiny::from(-0.4), Tiny::from(-0.3)).unify(),
+        Some(Tiny::from(-1. / 3.))
+    );
+    assert!(Ubound::from(Tiny::nan()).unify().unwrap().is_nan());
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!((tiny(1.) + tiny(2.)).to_string(), "3");
+    assert_eq!((tiny(1.5) * tiny(1.5)).to_string(), "(2, 2.5)");
+    assert_eq!((tiny(1.) / tiny(3.)).to_string(), "(0.25, 0.5)");
+    assert_eq!((tiny(4.) + tiny(4.)).to_string(), "(6, inf)");
+    assert_eq!((tiny(-1.) - tiny(0.1)).to_string(), "(-1.25, -1)");
+
+    let infinity = Ubound::from(Tiny::infinity(false));
+    assert_eq!((infinity + tiny(1.)).to_string(), "inf");
+    assert!((infinity - infinity).is_nan());
+    assert!((infinity * tiny(0.)).is_nan());
+    assert_eq!((infinity * tiny(-0.1)).to_string(), "-inf");
+    assert_eq!((tiny(1.) / infinity).to_string(), "0");
+    assert!((infinity / infinity).is_nan());
+    assert!((tiny(1.) / tiny(0.)).is_nan());
+    assert_eq!((tiny(1.) / tiny(0.1)).to_string(), "(4, inf)");
+    assert!((tiny(1.) + Ubound::from(Tiny::nan())).is_nan());
+
+    let reals = Ubound::new(Tiny::from(-100.), Tiny::from(100.));
+    assert_eq!(reals.to_string(), "(-inf, inf)");
+    assert_eq!((reals * reals).to_string(), "(-inf, inf)");
+    assert!((reals / reals).is_nan());
+    assert_eq!(
+        (-Ubound::new(Tiny::from(1.), infinity.hi())).to_string(),
+        "[-inf, -1]"
+    );
+}
+
+/// Multiples of 1/16 in `-n..n`, whose sums and products are exact in `f64`.
+fn sixteenths(n: i32) -> impl Strategy<Value = f64> {
+    (-16 * n..16 * n).prop_map(|i| f64::from(i) / 16.)
+}
+
+proptest! {
+    #[test]
+    fn contains_results(a in sixteenths(40), b in sixteenths(40), c in sixteenths(40)) {
+        let (x, y) = (small(a), small(b));
+        prop_assert!(x.contains(a));
+        let z = Ubound::new(Small::from(a.min(c)), Small::from(a.max(c)));
+        prop_assert!((x + y).contains(a + b), "{} + {} = {}", x, y, x + y);
+        prop_assert!((x - y).contains(a - b), "{} - {} = {}", x, y, x - y);
+        prop_assert!((x * y).contains(a * b), "{} * {} = {}", x, y, x * y);
+        prop_assert!((z * y).contains(a * b), "{} * {} = {}", z, y, z * y);
+        prop_assert!((z + y).contains(c + b), "{} + {} = {}", z, y, z + y);
+    }
+
+    #[test]
+    fn contains_quotients(a in sixteenths(40), b in sixteenths(40)) {
+        // The quotient a of a * b by b is exact
+        let quotient = small(a * b) / small(b);
+        prop_assert!(quotient.contains(a), "{} / {} = {}", a * b, b, quotient);
+        prop_assert_eq!(quotient.is_nan(), b == 0.);
+    }
+
+    #[test]
+    fn round_trips(a in sixteenths(1000)) {
+        let u = Small::from(a);
+        prop_assert_eq!(Small::from_bits(&u.bits()), Some(u));
+        prop_assert_eq!(u.optimize(), u);
+        prop_assert!(Ubound::from(u).contains(a));
+        if u.is_exact() {
+            prop_assert_eq!(u.to_f64(), a);
+            prop_assert_eq!(Posit::try_from(u), Ok(Posit::from(a)));
+        }
+        let v = Small::from(a + 1. / 1024.);
+        let unified = Ubound::new(u, v).unify();
+        prop_assert!(unified.is_none_or(|w| Ubound::from(w).contains(a)));
+    }
+}
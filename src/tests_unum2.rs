@@ -0,0 +1,137 @@
+use lazy_static::lazy_static;
+use proptest::prelude::*;
+
+use crate::{Sorn, TestPosit as Posit, Unum2};
+
+fn posit(x: f64) -> Posit {
+    Posit::from(x)
+}
+
+/// Unums of 3 bits, with the exact values 0, 1, inf and -1 only.
+fn three() -> Unum2<3> {
+    Unum2::new(&[]).unwrap()
+}
+
+lazy_static! {
+    /// Unums of 5 bits on the lattice 1, 2, 3, 4.
+    static ref FIVE: Unum2<5> = Unum2::new(&[posit(2.), posit(3.), posit(4.)]).unwrap();
+}
+
+fn sorn<const N: usize>(unum2: &Unum2<N>, x: f64) -> Sorn<N> {
+    Sorn::from_unum(unum2.from_posit(&posit(x)))
+}
+
+#[test]
+fn lattice() {
+    assert!(Unum2::<4>::new(&[]).is_err());
+    assert!(Unum2::<4>::new(&[posit(1.)]).is_err());
+    assert!(Unum2::<5>::new(&[posit(2.), posit(2.), posit(4.)]).is_err());
+    assert!(Unum2::<5>::new(&[posit(2.), Posit::nar(), posit(4.)]).is_err());
+    assert_eq!(
+        Unum2::<4>::new(&[]).unwrap_err().to_string(),
+        "lattice must have length 1 and increase from above one"
+    );
+    assert!(Unum2::<4>::new(&[posit(10.)]).is_ok());
+}
+
+#[test]
+fn conversions() {
+    let unum2 = &three();
+    let unums: Vec<_> = [0., 0.5, 1., 3., -3., -1., -0.5]
+        .iter()
+        .map(|&x| unum2.from_posit(&posit(x)))
+        .collect();
+    assert_eq!(unums, [0, 1, 2, 3, 5, 6, 7]);
+    assert_eq!(unum2.from_posit(&Posit::nar()), Unum2::<3>::INFINITY);
+    assert_eq!(unum2.from_posit(&crate::Posit::<0>::from(0.75)), 1);
+
+    let unum2 = &*FIVE;
+    assert_eq!(unum2.from_posit(&posit(0.5)), 6);
+    assert_eq!(unum2.from_posit(&posit(2.5)), 11);
+    assert_eq!(unum2.from_posit(&posit(-0.25)), 30);
+    // The posit nearest to 1/3 lies below it
+    assert_eq!(unum2.from_posit(&posit(1. / 3.)), 3);
+    assert_eq!(unum2.display(&sorn(unum2, 0.3)).to_string(), "{(1/4, 1/3)}");
+    assert_eq!(unum2.display(&sorn(unum2, 100.)).to_string(), "{(4, inf)}");
+}
+
+#[test]
+fn sets() {
+    let unum2 = &*FIVE;
+    let a = sorn(unum2, 1.).union(&sorn(unum2, 1.5));
+    let b = sorn(unum2, 2.).union(&sorn(unum2, 1.5));
+    assert_eq!(unum2.display(&a).to_string(), "{[1, 2)}");
+    assert_eq!(unum2.display(&a.union(&b)).to_string(), "{[1, 2]}");
+    assert_eq!(a.intersection(&b), sorn(unum2, 1.5));
+    assert_eq!(unum2.display(&-a.clone()).to_string(), "{(-2, -1]}");
+    assert_eq!(a.unums().collect::<Vec<_>>(), [8, 9]);
+    assert!(a.contains(8) && !a.contains(10) && !a.contains(100));
+    assert!(Sorn::<5>::empty().is_empty());
+    assert_eq!(unum2.display(&Sorn::empty()).to_string(), "{}");
+    assert_eq!(
+        unum2.display(&Sorn::everything()).to_string(),
+        "{(-inf, inf), inf}"
+    );
+    assert_eq!(-Sorn::<5>::from_unum(16), Sorn::from_unum(16));
+}
+
+#[test]
+fn tables() {
+    let unum2 = &three();
+    let display = |s: Sorn<3>| unum2.display(&s).to_string();
+    let (zero, one) = (sorn(unum2, 0.), sorn(unum2, 1.));
+    let (small, infinity) = (sorn(unum2, 0.5), Sorn::from_unum(Unum2::<3>::INFINITY));
+    assert_eq!(display(unum2.add(&one, &one)), "{(1, inf)}");
+    assert_eq!(display(unum2.add(&small, &small)), "{(0, inf)}");
+    assert_eq!(display(unum2.sub(&one, &one)), "{0}");
+    assert_eq!(display(unum2.sub(&one, &small)), "{(0, 1)}");
+    assert_eq!(display(unum2.add(&infinity, &one)), "{inf}");
+    assert!(unum2.add(&infinity, &infinity) == Sorn::everything());
+    assert!(unum2.mul(&zero, &infinity) == Sorn::everything());
+    assert_eq!(display(unum2.mul(&small, &-one.clone())), "{(-1, 0)}");
+    assert_eq!(display(unum2.recip(&small)), "{(1, inf)}");
+    assert_eq!(display(unum2.div(&one, &zero)), "{inf}");
+    assert_eq!(display(unum2.div(&zero, &zero)), "{(-inf, inf), inf}");
+    assert!(unum2.add(&Sorn::empty(), &one).is_empty());
+
+    let unum2 = &*FIVE;
+    let display = |s: Sorn<5>| unum2.display(&s).to_string();
+    let third = Sorn::from_unum(4);
+    assert_eq!(display(third.clone()), "{1/3}");
+    assert_eq!(display(unum2.add(&third, &third)), "{(1/2, 1)}");
+    assert_eq!(display(unum2.mul(&third, &sorn(unum2, 3.))), "{1}");
+    assert_eq!(display(unum2.recip(&third)), "{3}");
+    assert_eq!(
+        display(unum2.div(&sorn(unum2, 1.), &sorn(unum2, 2.5))),
+        "{(1/3, 1/2)}"
+    );
+    assert_eq!(
+        display(unum2.mul(&sorn(unum2, 1.5), &sorn(unum2, 1.5))),
+        "{(1, 4)}"
+    );
+}
+
+/// Posit of 6 bits up to 64 in magnitude, not NaR.
+fn small() -> impl Strategy<Value = Posit> {
+    (-64.0..64f64).prop_map(|x| {
+        let mut p = posit(x);
+        p.round_to(6);
+        p
+    })
+}
+
+proptest! {
+    #[test]
+    fn contains_results(x in small(), y in small()) {
+        let unum2 = &*FIVE;
+        let u = |p: &Posit| Sorn::from_unum(unum2.from_posit(p));
+        let (a, b) = (u(&x), u(&y));
+        prop_assert!(unum2.add(&a, &b).contains(unum2.from_posit(&(&x + &y))));
+        prop_assert!(unum2.sub(&a, &b).contains(unum2.from_posit(&(&x - &y))));
+        prop_assert!(unum2.mul(&a, &b).contains(unum2.from_posit(&(&x * &y))));
+        // The quotient y of x * y by x is exact
+        let quotient = unum2.div(&u(&(&x * &y)), &a);
+        prop_assert!(quotient.contains(unum2.from_posit(&y)));
+        prop_assert_eq!(-unum2.recip(&a), unum2.recip(&-a.clone()));
+    }
+}
@@ -0,0 +1,203 @@
+use proptest::prelude::*;
+
+use crate::{with_precision, TestPosit as Posit};
+
+type Valid = super::Valid;
+
+fn posit(x: f64) -> Posit {
+    Posit::from(x)
+}
+
+fn rounded(x: f64, nbits: usize) -> Posit {
+    let mut p = posit(x);
+    p.round_to(nbits);
+    p
+}
+
+/// Posit of 8 bits, not NaR.
+fn small() -> impl Strategy<Value = Posit> {
+    (-1e6..1e6f64).prop_map(|x| rounded(x, 8))
+}
+
+/// Valid with bounds of 8 bits and a value inside it.
+fn valid() -> impl Strategy<Value = (Valid, Posit)> {
+    (small(), small(), any::<bool>(), any::<bool>()).prop_map(|(a, b, lo_open, hi_open)| {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        let inside = if lo == hi {
+            lo.clone()
+        } else {
+            (&lo + &hi) * posit(0.5)
+        };
+        let (lo_open, hi_open) = if lo == hi {
+            (false, false)
+        } else {
+            (lo_open, hi_open)
+        };
+        (Valid::new(lo, lo_open, hi, hi_open), inside)
+    })
+}
+
+#[test]
+fn bounds() {
+    let v = Valid::new(posit(1.), false, posit(2.), true);
+    assert_eq!(v.lo(), Some((posit(1.), false)));
+    assert_eq!(v.hi(), Some((posit(2.), true)));
+    assert!(v.contains(&posit(1.)));
+    assert!(v.contains(&posit(1.5)));
+    assert!(!v.contains(&posit(2.)));
+    assert!(!v.contains(&Posit::nar()));
+    assert_eq!(v.to_string(), "[1, 2)");
+
+    assert!(Valid::open(posit(1.), posit(1.)).is_empty());
+    assert!(Valid::closed(posit(2.), posit(1.)).is_empty());
+    assert_eq!(Valid::from(posit(3.)).to_string(), "[3, 3]");
+    assert!(Valid::from(Posit::nar()).is_nar());
+    assert!(Valid::nar().contains(&Posit::nar()));
+
+    let unbounded = Valid::new(Posit::nar(), false, posit(3.), false);
+    assert_eq!(unbounded.to_string(), "(-inf, 3]");
+    assert_eq!(unbounded.lo(), Some((Posit::nar(), true)));
+    assert_eq!(Valid::reals().to_string(), "(-inf, inf)");
+    assert_eq!(Valid::empty().to_string(), "empty");
+    assert_eq!(Valid::nar().to_string(), "NaR");
+}
+
+#[test]
+fn set_operations() {
+    let a = Valid::new(posit(1.), false, posit(3.), true);
+    let b = Valid::new(posit(2.), true, posit(3.), false);
+    assert_eq!(a.intersection(&b), Valid::open(posit(2.), posit(3.)));
+    assert_eq!(a.union(&b), Valid::closed(posit(1.), posit(3.)));
+
+    let c = Valid::closed(posit(5.), posit(6.));
+    assert!(a.intersection(&c).is_empty());
+    assert_eq!(a.union(&c), Valid::closed(posit(1.), posit(6.)));
+    assert_eq!(a.union(&Valid::empty()), a);
+    assert_eq!(a.intersection(&Valid::nar()), a);
+    assert!(a.union(&Valid::nar()).is_nar());
+
+    // Touching at an excluded bound
+    let d = Valid::closed(posit(3.), posit(4.));
+    assert!(a.intersection(&d).is_empty());
+    assert_eq!(b.intersection(&d), Valid::from(posit(3.)));
+}
+
+#[test]
+fn width() {
+    assert_eq!(Valid::open(posit(1.), posit(3.5)).width(), posit(2.5));
+    assert_eq!(Valid::from(posit(3.)).width(), Posit::zero());
+    assert_eq!(Valid::empty().width(), Posit::zero());
+    assert!(Valid::reals().width().is_nar());
+    assert!(Valid::nar().width().is_nar());
+}
+
+#[test]
+fn outward_rounding() {
+    // 1/3 lies between two adjacent posits, which are excluded
+    let third = Valid::from(posit(1.)).div_rounded(Valid::from(posit(3.)), 8);
+    let (lo, lo_open) = third.lo().unwrap();
+    let (hi, hi_open) = third.hi().unwrap();
+    assert!(lo_open && hi_open);
+    assert_eq!(lo.next_up(8), hi);
+    assert!(lo.to_f64() < 1. / 3. && 1. / 3. < hi.to_f64());
+
+    // The operator rounds to 16 bits more than the bounds, or to the context width
+    let quotient = || Valid::from(posit(1.)) / Valid::from(posit(3.));
+    let nbits = posit(3.).bits.len() + 16;
+    assert_eq!(quotient(), Valid::from(posit(1.)).div_rounded(Valid::from(posit(3.)), nbits));
+    assert_eq!(with_precision(8, quotient), third);
+    assert!((Valid::from(posit(1.)) / Valid::from(Posit::zero())).is_nar());
+
+    // Exact results stay closed
+    let sum = Valid::from(posit(1.)).add_rounded(Valid::from(posit(2.)), 8);
+    assert_eq!(sum, Valid::from(posit(3.)));
+
+    // Beyond maxpos the bound goes to infinity
+    let maxpos = Posit::nar().next_down(8);
+    let mut v = Valid::from(&maxpos + &maxpos);
+    v.round_to(8);
+    assert_eq!(v.lo(), Some((maxpos, true)));
+    assert_eq!(v.hi(), Some((Posit::nar(), true)));
+}
+
+#[test]
+fn division_by_zero() {
+    let one = Valid::from(posit(1.));
+    assert!(one
+        .clone()
+        .div_rounded(Valid::closed(posit(-1.), posit(1.)), 8)
+        .is_nar());
+    assert!(one
+        .clone()
+        .div_rounded(Valid::from(Posit::zero()), 8)
+        .is_nar());
+
+    // Next to zero the quotient is unbounded
+    let q = one.div_rounded(Valid::new(Posit::zero(), true, posit(1.), false), 8);
+    assert_eq!(q.to_string(), "[1, inf)");
+}
+
+#[test]
+fn infinities() {
+    let positive = Valid::new(Posit::zero(), true, Posit::nar(), true);
+    assert_eq!(
+        (positive.clone() * positive.clone()).to_string(),
+        "(0, inf)"
+    );
+    assert_eq!(
+        (-positive.clone() * positive.clone()).to_string(),
+        "(-inf, 0)"
+    );
+    assert_eq!(
+        (positive.clone() * Valid::from(Posit::zero())).to_string(),
+        "[0, 0]"
+    );
+    assert_eq!(
+        (positive.clone() + Valid::from(posit(1.))).to_string(),
+        "(1, inf)"
+    );
+    assert_eq!(
+        (positive.clone() - positive.clone()).to_string(),
+        "(-inf, inf)"
+    );
+    let q = Valid::from(posit(1.)).div_rounded(positive.clone(), 8);
+    assert_eq!(q.to_string(), "(0, inf)");
+    let q = positive.div_rounded(
+        Valid::reals().intersection(&Valid::closed(posit(2.), posit(4.))),
+        8,
+    );
+    assert_eq!(q.to_string(), "(0, inf)");
+}
+
+proptest! {
+    #[test]
+    fn contains_results((l, x) in valid(), (r, y) in valid(), nbits in 3usize..16) {
+        prop_assert!(l.contains(&x) && r.contains(&y));
+        let sum = l.clone().add_rounded(r.clone(), nbits);
+        prop_assert!(sum.contains(&(&x + &y)), "{} + {} = {}", l, r, sum);
+        let difference = l.clone().sub_rounded(r.clone(), nbits);
+        prop_assert!(difference.contains(&(&x - &y)), "{} - {} = {}", l, r, difference);
+        let product = l.clone().mul_rounded(r.clone(), nbits);
+        prop_assert!(product.contains(&(&x * &y)), "{} * {} = {}", l, r, product);
+        prop_assert!((l.clone() * r.clone()).contains(&(&x * &y)));
+    }
+
+    #[test]
+    fn contains_quotients((r, y) in valid(), z in small(), nbits in 3usize..16) {
+        // The quotient z of x = y * z is exact
+        let x = &y * &z;
+        let l = Valid::closed(&x - &posit(1.), &x + &posit(1.));
+        let quotient = l.clone().div_rounded(r.clone(), nbits);
+        prop_assert!(quotient.contains(&z), "{} / {} = {}", l, r, quotient);
+        prop_assert!(!r.contains(&Posit::zero()) || quotient.is_nar());
+    }
+
+    #[test]
+    fn set_bounds((l, x) in valid(), (r, y) in valid()) {
+        let union = l.union(&r);
+        prop_assert!(union.contains(&x) && union.contains(&y));
+        let intersection = l.intersection(&r);
+        prop_assert_eq!(intersection.contains(&x), r.contains(&x));
+        prop_assert_eq!(intersection.contains(&y), l.contains(&y));
+    }
+}
@@ -0,0 +1,678 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::*;
+
+use bitvec::prelude::*;
+
+use crate::valid::{self, cmp_lower, cmp_upper, Bounds, End, Value};
+use crate::Posit;
+
+/// Error returned when converting an inexact unum to a posit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryFromUnumError(());
+
+impl fmt::Display for TryFromUnumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unum is inexact")
+    }
+}
+
+impl Error for TryFromUnumError {}
+
+/// Type I unum of the environment with `ESS` bits of exponent size and `FSS` bits of fraction
+/// size.
+///
+/// A unum holds a sign, an exponent of `es` bits, a fraction of `fs` bits and the utag: the
+/// ubit followed by `es - 1` in `ESS` bits and `fs - 1` in `FSS` bits. Exact unums read like
+/// IEEE floats of these sizes, subnormals included. The ubit marks an inexact unum, the open
+/// interval from its value to the next one away from zero. Only the longest unums with all
+/// their exponent and fraction bits set are special: the infinities, and NaN with the ubit,
+/// quiet if positive and signaling otherwise.
+///
+/// Equality compares the bits, a value has unums of several sizes. Environments go up to
+/// `ESS = 4` and `FSS = 6`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Unum<const ESS: usize = 3, const FSS: usize = 4> {
+    sign: bool,
+    exponent: u64,
+    fraction: u64,
+    ubit: bool,
+    es: usize,
+    fs: usize,
+}
+
+impl<const ESS: usize, const FSS: usize> Unum<ESS, FSS> {
+    /// Bits of the exponent size field.
+    pub const ESIZESIZE: usize = ESS;
+    /// Bits of the fraction size field.
+    pub const FSIZESIZE: usize = FSS;
+    /// Longest exponent, `2^ESS` bits.
+    pub const ESIZEMAX: usize = 1 << ESS;
+    /// Longest fraction, `2^FSS` bits.
+    pub const FSIZEMAX: usize = 1 << FSS;
+    /// Bits of the utag.
+    pub const UTAGSIZE: usize = 1 + ESS + FSS;
+    /// Bits of the longest unums.
+    pub const MAXUBITS: usize = 1 + Self::ESIZEMAX + Self::FSIZEMAX + Self::UTAGSIZE;
+
+    const ENVIRONMENT: () = assert!(
+        ESS <= 4 && FSS <= 6,
+        "unum environments go up to esizesize 4 and fsizesize 6"
+    );
+
+    /// Unum with the given fields and sizes.
+    ///
+    /// Panics if a size is out of the environment or a field longer than its size.
+    pub fn new(sign: bool, exponent: u64, fraction: u64, ubit: bool, es: usize, fs: usize) -> Self {
+        let () = Self::ENVIRONMENT;
+        assert!(
+            (1..=Self::ESIZEMAX).contains(&es),
+            "exponent size {} out of range",
+            es
+        );
+        assert!(
+            (1..=Self::FSIZEMAX).contains(&fs),
+            "fraction size {} out of range",
+            fs
+        );
+        assert!(
+            u128::from(exponent) >> es == 0,
+            "exponent longer than {} bits",
+            es
+        );
+        assert!(
+            u128::from(fraction) >> fs == 0,
+            "fraction longer than {} bits",
+            fs
+        );
+        Unum {
+            sign,
+            exponent,
+            fraction,
+            ubit,
+            es,
+            fs,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(false, 0, 0, false, 1, 1)
+    }
+
+    pub fn infinity(negative: bool) -> Self {
+        Self::longest(negative, Self::infinity_index(), false)
+    }
+
+    /// Quiet NaN.
+    pub fn nan() -> Self {
+        Self::longest(false, Self::infinity_index(), true)
+    }
+
+    /// Largest finite unum.
+    pub fn maxreal() -> Self {
+        Self::longest(false, Self::infinity_index() - 1, false)
+    }
+
+    /// Smallest positive unum.
+    pub fn smallsubnormal() -> Self {
+        Self::longest(false, 1, false)
+    }
+
+    pub fn sign(&self) -> bool {
+        self.sign
+    }
+
+    pub fn exponent(&self) -> u64 {
+        self.exponent
+    }
+
+    pub fn fraction(&self) -> u64 {
+        self.fraction
+    }
+
+    pub fn ubit(&self) -> bool {
+        self.ubit
+    }
+
+    /// Bits of the exponent.
+    pub fn es(&self) -> usize {
+        self.es
+    }
+
+    /// Bits of the fraction.
+    pub fn fs(&self) -> usize {
+        self.fs
+    }
+
+    /// Ubit, `es - 1` and `fs - 1`.
+    pub fn utag(&self) -> u64 {
+        (self.ubit as u64) << (ESS + FSS) | ((self.es - 1) << FSS | (self.fs - 1)) as u64
+    }
+
+    /// Bits of the unum with its utag.
+    pub fn size(&self) -> usize {
+        1 + self.es + self.fs + Self::UTAGSIZE
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.is_longest() && self.index() == Self::infinity_index() && self.ubit
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.is_longest() && self.index() == Self::infinity_index() && !self.ubit
+    }
+
+    /// Whether the unum is a single value, without its ubit.
+    pub fn is_exact(&self) -> bool {
+        !self.ubit
+    }
+
+    /// Bits of the unum from its sign to its fraction size.
+    pub fn bits(&self) -> BitVec {
+        let mut bits = BitVec::new();
+        let mut push = |word: u64, len: usize| {
+            for i in (0..len).rev() {
+                bits.push(word >> i & 1 != 0);
+            }
+        };
+        push(self.sign as u64, 1);
+        push(self.exponent, self.es);
+        push(self.fraction, self.fs);
+        push(self.utag(), Self::UTAGSIZE);
+        bits
+    }
+
+    /// Unum with the bits given by [`Unum::bits`], `None` if their number disagrees with the
+    /// sizes in their utag.
+    pub fn from_bits(bits: &BitSlice) -> Option<Self> {
+        let len = bits.len();
+        if len < 3 + Self::UTAGSIZE {
+            return None;
+        }
+        let read = |start: usize, len: usize| {
+            (start..start + len).fold(0u64, |word, i| word << 1 | bits[i] as u64)
+        };
+        let utag = read(len - Self::UTAGSIZE, Self::UTAGSIZE);
+        let es = (utag >> FSS) as usize % Self::ESIZEMAX + 1;
+        let fs = utag as usize % Self::FSIZEMAX + 1;
+        if len != 1 + es + fs + Self::UTAGSIZE {
+            return None;
+        }
+        let ubit = utag >> (ESS + FSS) != 0;
+        Some(Self::new(
+            bits[0],
+            read(1, es),
+            read(1 + es, fs),
+            ubit,
+            es,
+            fs,
+        ))
+    }
+
+    /// Shortest unum with the same value or interval, with the shortest exponent among unums
+    /// of the same size.
+    pub fn optimize(&self) -> Self {
+        let index = self.index();
+        // Only the longest unums hold the infinities, NaN and the interval above maxreal
+        if self.is_longest() && index + self.ubit as u128 >= Self::infinity_index() {
+            return *self;
+        }
+        let lo = Self::value::<2>(index, self.es, self.fs);
+        let hi = Self::value::<2>(index + 1, self.es, self.fs);
+        for (es, fs) in Self::sizes() {
+            let longest = es == Self::ESIZEMAX && fs == Self::FSIZEMAX;
+            let last = Self::last_index(es, fs) - (self.ubit && longest) as u128;
+            if let Some((i, true)) = Self::floor_index(&lo, es, fs) {
+                if i <= last && (!self.ubit || Self::value::<2>(i + 1, es, fs) == hi) {
+                    let sign = self.sign && (self.ubit || i != 0);
+                    return Self::from_index(sign, i, self.ubit, es, fs);
+                }
+            }
+        }
+        *self
+    }
+
+    /// Value of an exact unum, NaN for inexact unums. Fractions longer than those of `f64`
+    /// are rounded to nearest.
+    pub fn to_f64(&self) -> f64 {
+        if self.ubit {
+            return f64::NAN;
+        }
+        let magnitude = if self.is_infinite() {
+            f64::INFINITY
+        } else {
+            Self::value::<2>(self.index(), self.es, self.fs).to_f64()
+        };
+        if self.sign {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Exponent and fraction bits as one number, which grows with the magnitude.
+    fn index(&self) -> u128 {
+        u128::from(self.exponent) << self.fs | u128::from(self.fraction)
+    }
+
+    /// Unum of the given sizes whose exponent and fraction bits read `index`.
+    fn from_index(sign: bool, index: u128, ubit: bool, es: usize, fs: usize) -> Self {
+        let fraction = (index & ((1 << fs) - 1)) as u64;
+        Self::new(sign, (index >> fs) as u64, fraction, ubit, es, fs)
+    }
+
+    /// Unum of the longest exponent and fraction.
+    fn longest(sign: bool, index: u128, ubit: bool) -> Self {
+        Self::from_index(sign, index, ubit, Self::ESIZEMAX, Self::FSIZEMAX)
+    }
+
+    fn is_longest(&self) -> bool {
+        self.es == Self::ESIZEMAX && self.fs == Self::FSIZEMAX
+    }
+
+    /// Index of the infinities, the last one of the longest unums.
+    fn infinity_index() -> u128 {
+        (1 << (Self::ESIZEMAX + Self::FSIZEMAX)) - 1
+    }
+
+    /// Last index of the unums of the given sizes with a finite value.
+    fn last_index(es: usize, fs: usize) -> u128 {
+        if es == Self::ESIZEMAX && fs == Self::FSIZEMAX {
+            Self::infinity_index() - 1
+        } else {
+            (1 << (es + fs)) - 1
+        }
+    }
+
+    /// Exponent and fraction sizes, shortest first and then by exponent size.
+    fn sizes() -> impl Iterator<Item = (usize, usize)> {
+        (2..=Self::ESIZEMAX + Self::FSIZEMAX).flat_map(|size| {
+            (1..=Self::ESIZEMAX)
+                .filter(move |&es| es < size && size - es <= Self::FSIZEMAX)
+                .map(move |es| (es, size - es))
+        })
+    }
+
+    /// Magnitude of the unums of the given sizes with the exponent and fraction bits `index`.
+    fn value<const ES: usize>(index: u128, es: usize, fs: usize) -> Posit<ES> {
+        let bias = (1 << (es - 1)) - 1;
+        let exponent = (index >> fs) as i64;
+        let fraction = index & ((1 << fs) - 1);
+        let significand = if exponent == 0 {
+            fraction
+        } else {
+            fraction | 1 << fs
+        };
+        Posit::from(significand).mul_pow2((exponent.max(1) - bias - fs as i64) as isize)
+    }
+
+    /// Index of the largest magnitude of the unums of the given sizes at most `v`, which is
+    /// not negative, and whether it is `v`. `None` if `v` is beyond them.
+    fn floor_index<const ES: usize>(v: &Posit<ES>, es: usize, fs: usize) -> Option<(u128, bool)> {
+        let decoded = match v.decode() {
+            Some(decoded) => decoded,
+            None => return Some((0, true)),
+        };
+        let exponent = decoded.scale() + (1 << (es - 1)) - 1;
+        if exponent >= 1 << es {
+            return None;
+        }
+        // Significand bits `1.fraction` which fit, subnormals keep fewer of them
+        let kept = fs as i64 + exponent.min(1);
+        let mut bits = std::iter::once(true).chain(decoded.fraction.iter());
+        let mut index = 0u128;
+        for _ in 0..kept.max(0) {
+            index = index << 1 | bits.next().unwrap_or(false) as u128;
+        }
+        let exact = !bits.any(|bit| bit);
+        if exponent > 1 {
+            index += ((exponent - 1) as u128) << fs;
+        }
+        Some((index, exact))
+    }
+
+    /// Index of the largest finite magnitude of the longest unums at most `v`, which is not
+    /// negative, and whether it is `v`.
+    fn floor_longest<const ES: usize>(v: &Posit<ES>) -> (u128, bool) {
+        match Self::floor_index(v, Self::ESIZEMAX, Self::FSIZEMAX) {
+            Some((index, exact)) if index < Self::infinity_index() => (index, exact),
+            _ => (Self::infinity_index() - 1, false),
+        }
+    }
+
+    /// Longest unum which holds the values at and just above the lower bound `end`.
+    fn lower<const ES: usize>(end: &End<ES>) -> Self {
+        match &end.value {
+            Value::NegInf if end.open => Self::longest(true, Self::infinity_index() - 1, true),
+            Value::NegInf => Self::infinity(true),
+            Value::PosInf => Self::infinity(false),
+            Value::Finite(v) if !v.is_negative() => {
+                let (index, exact) = Self::floor_longest(v);
+                Self::longest(false, index, end.open || !exact)
+            }
+            Value::Finite(v) => match Self::floor_longest(&-v) {
+                (index, true) if !end.open => Self::longest(true, index, false),
+                (index, true) => Self::longest(true, index - 1, true),
+                (index, false) => Self::longest(true, index, true),
+            },
+        }
+    }
+
+    /// Longest unum which holds the values at and just below the upper bound `end`.
+    fn upper<const ES: usize>(end: &End<ES>) -> Self {
+        -Self::lower(&End::new(end.value.neg(), end.open))
+    }
+
+    /// Bounds of the interval of the unum, `None` for NaN.
+    fn bounds(&self) -> Option<Bounds<2>> {
+        if self.is_nan() {
+            return None;
+        }
+        if self.is_infinite() {
+            let infinity = End::new(Value::infinity(self.sign), false);
+            return Some((infinity.clone(), infinity));
+        }
+        let index = self.index();
+        let lo = End::new(
+            Value::Finite(Self::value(index, self.es, self.fs)),
+            self.ubit,
+        );
+        let hi = if !self.ubit {
+            lo.clone()
+        } else if self.is_longest() && index + 1 == Self::infinity_index() {
+            End::new(Value::PosInf, true)
+        } else {
+            End::new(
+                Value::Finite(Self::value(index + 1, self.es, self.fs)),
+                true,
+            )
+        };
+        Some(if self.sign {
+            valid::negation(&(lo, hi))
+        } else {
+            (lo, hi)
+        })
+    }
+
+    /// Unum of the given sizes which holds the interval from `lo` to `hi`, if any.
+    fn enclosing(lo: &End<2>, hi: &End<2>, es: usize, fs: usize) -> Option<Self> {
+        let zero = Value::Finite(Posit::zero());
+        if lo.value < zero {
+            if hi.value > zero {
+                return None;
+            }
+            let (lo, hi) = valid::negation(&(lo.clone(), hi.clone()));
+            return Self::enclosing(&lo, &hi, es, fs).map(Neg::neg);
+        }
+        let longest = es == Self::ESIZEMAX && fs == Self::FSIZEMAX;
+        let v = match &lo.value {
+            Value::Finite(v) => v,
+            _ => return Some(Self::infinity(false)).filter(|_| longest),
+        };
+        let (index, exact) = if longest {
+            Self::floor_longest(v)
+        } else {
+            Self::floor_index(v, es, fs)?
+        };
+        if exact && !lo.open {
+            let point = Self::from_index(false, index, false, es, fs);
+            return Some(point).filter(|_| hi == lo);
+        }
+        let top = if longest && index + 1 == Self::infinity_index() {
+            End::new(Value::PosInf, true)
+        } else {
+            End::new(Value::Finite(Self::value(index + 1, es, fs)), true)
+        };
+        match cmp_upper(hi, &top) {
+            Ordering::Greater => None,
+            _ => Some(Self::from_index(false, index, true, es, fs)),
+        }
+    }
+
+    /// Bits of the posits which hold quotients, enough for the values of the environment and
+    /// fractions longer than theirs.
+    fn quotient_bits() -> usize {
+        let scale = (1 << (Self::ESIZEMAX - 1)) + Self::FSIZEMAX + 2;
+        scale / 4 + Self::FSIZEMAX + 8
+    }
+}
+
+/// Exact if the environment holds the value, otherwise the shortest inexact unum holding it.
+impl<const ESS: usize, const FSS: usize> From<f64> for Unum<ESS, FSS> {
+    fn from(x: f64) -> Self {
+        if x.is_nan() {
+            Self::nan()
+        } else if x.is_infinite() {
+            Self::infinity(x < 0.)
+        } else {
+            Self::from(Posit::<2>::from(x))
+        }
+    }
+}
+
+/// Exact if the environment holds the value, otherwise the shortest inexact unum holding it.
+/// NaR becomes NaN.
+impl<const ES: usize, const ESS: usize, const FSS: usize> From<Posit<ES>> for Unum<ESS, FSS> {
+    fn from(p: Posit<ES>) -> Self {
+        if p.is_nar() {
+            return Self::nan();
+        }
+        Self::lower(&End::new(Value::Finite(p), false)).optimize()
+    }
+}
+
+/// Exact conversion, infinities and NaN become NaR. Fails for inexact unums.
+impl<const ES: usize, const ESS: usize, const FSS: usize> TryFrom<Unum<ESS, FSS>> for Posit<ES> {
+    type Error = TryFromUnumError;
+
+    fn try_from(u: Unum<ESS, FSS>) -> Result<Self, Self::Error> {
+        if u.is_nan() || u.is_infinite() {
+            return Ok(Posit::nar());
+        }
+        if u.ubit {
+            return Err(TryFromUnumError(()));
+        }
+        let magnitude = Unum::<ESS, FSS>::value(u.index(), u.es, u.fs);
+        Ok(if u.sign { -magnitude } else { magnitude })
+    }
+}
+
+impl<const ESS: usize, const FSS: usize> Neg for Unum<ESS, FSS> {
+    type Output = Self;
+
+    /// Zero and NaN keep their sign.
+    fn neg(self) -> Self::Output {
+        if self.is_nan() || (self.index() == 0 && !self.ubit) {
+            return self;
+        }
+        Unum {
+            sign: !self.sign,
+            ..self
+        }
+    }
+}
+
+/// The value, or the interval of an inexact unum.
+impl<const ESS: usize, const FSS: usize> fmt::Display for Unum<ESS, FSS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_bounds(f, self.bounds())
+    }
+}
+
+/// Type I ubound: the interval from the lower end of one unum to the upper end of another.
+///
+/// Arithmetic is exact up to the unums of the result: their intervals hold the ends of the
+/// exact result, and they are the shortest such unums. Operations which hold an undefined
+/// result such as `0 * inf`, `inf - inf` or a division by an interval holding zero give NaN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Ubound<const ESS: usize = 3, const FSS: usize = 4> {
+    lo: Unum<ESS, FSS>,
+    hi: Unum<ESS, FSS>,
+}
+
+impl<const ESS: usize, const FSS: usize> Ubound<ESS, FSS> {
+    /// From the lower end of `lo` to the upper end of `hi`.
+    pub fn new(lo: Unum<ESS, FSS>, hi: Unum<ESS, FSS>) -> Self {
+        Ubound { lo, hi }
+    }
+
+    pub fn lo(&self) -> Unum<ESS, FSS> {
+        self.lo
+    }
+
+    pub fn hi(&self) -> Unum<ESS, FSS> {
+        self.hi
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.lo.is_nan() || self.hi.is_nan()
+    }
+
+    /// Whether `x` is in the ubound, NaN ubounds hold everything.
+    pub fn contains(&self, x: f64) -> bool {
+        let value = if x.is_infinite() {
+            Value::infinity(x < 0.)
+        } else {
+            Value::Finite(Posit::from(x))
+        };
+        match self.bounds() {
+            None => true,
+            Some(_) if x.is_nan() => false,
+            Some(bounds) => valid::includes(&bounds, &value),
+        }
+    }
+
+    /// Ubound with the shortest unums for the same interval.
+    pub fn optimize(&self) -> Self {
+        Self::new(self.lo.optimize(), self.hi.optimize())
+    }
+
+    /// Smallest single unum holding the ubound, the shortest one if several do. `None` if no
+    /// unum holds it.
+    pub fn unify(&self) -> Option<Unum<ESS, FSS>> {
+        let (lo, hi) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Some(Unum::nan()),
+        };
+        let mut best: Option<(Unum<ESS, FSS>, Bounds<2>)> = None;
+        for (es, fs) in Unum::<ESS, FSS>::sizes() {
+            if let Some(u) = Unum::enclosing(&lo, &hi, es, fs) {
+                let bounds = u.bounds().unwrap();
+                // The intervals of unums are nested or disjoint
+                let narrower = best.as_ref().is_none_or(|(_, best)| {
+                    cmp_lower(&bounds.0, &best.0) == Ordering::Greater
+                        || cmp_upper(&bounds.1, &best.1) == Ordering::Less
+                });
+                if narrower {
+                    best = Some((u, bounds));
+                }
+            }
+        }
+        best.map(|(u, _)| u)
+    }
+
+    /// Bounds of the interval, `None` for NaN.
+    fn bounds(&self) -> Option<Bounds<2>> {
+        let (lo, _) = self.lo.bounds()?;
+        let (_, hi) = self.hi.bounds()?;
+        Some((lo, hi))
+    }
+
+    /// Shortest unums holding the bounds, NaN for `None`.
+    fn from_bounds(bounds: Option<Bounds<2>>) -> Self {
+        match bounds {
+            Some((lo, hi)) => Self::new(Unum::lower(&lo).optimize(), Unum::upper(&hi).optimize()),
+            None => Unum::nan().into(),
+        }
+    }
+
+    /// Applies `f` to the bounds of two ubounds, whose result is NaN for `None`.
+    fn combine(
+        self,
+        rhs: Self,
+        f: impl FnOnce(&Bounds<2>, &Bounds<2>) -> Option<Bounds<2>>,
+    ) -> Self {
+        let bounds = match (self.bounds(), rhs.bounds()) {
+            (Some(l), Some(r)) => f(&l, &r),
+            _ => None,
+        };
+        Self::from_bounds(bounds)
+    }
+}
+
+impl<const ESS: usize, const FSS: usize> From<Unum<ESS, FSS>> for Ubound<ESS, FSS> {
+    fn from(u: Unum<ESS, FSS>) -> Self {
+        Self::new(u, u)
+    }
+}
+
+impl<const ESS: usize, const FSS: usize> Neg for Ubound<ESS, FSS> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.hi, -self.lo)
+    }
+}
+
+impl<const ESS: usize, const FSS: usize> Add for Ubound<ESS, FSS> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, valid::sum)
+    }
+}
+
+impl<const ESS: usize, const FSS: usize> Sub for Ubound<ESS, FSS> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const ESS: usize, const FSS: usize> Mul for Ubound<ESS, FSS> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, valid::product)
+    }
+}
+
+impl<const ESS: usize, const FSS: usize> Div for Ubound<ESS, FSS> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let nbits = Unum::<ESS, FSS>::quotient_bits();
+        self.combine(rhs, |l, r| valid::quotient(l, r, nbits))
+    }
+}
+
+/// A single value, or interval notation with brackets for included bounds and parentheses for
+/// excluded ones.
+impl<const ESS: usize, const FSS: usize> fmt::Display for Ubound<ESS, FSS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_bounds(f, self.bounds())
+    }
+}
+
+fn fmt_bounds(f: &mut fmt::Formatter, bounds: Option<Bounds<2>>) -> fmt::Result {
+    let value = |value: &Value<2>| match value {
+        Value::NegInf => "-inf".to_string(),
+        Value::Finite(p) => p.to_string(),
+        Value::PosInf => "inf".to_string(),
+    };
+    match bounds {
+        None => f.write_str("NaN"),
+        Some((lo, hi)) if lo == hi && !lo.open => f.write_str(&value(&lo.value)),
+        Some((lo, hi)) => write!(
+            f,
+            "{}{}, {}{}",
+            if lo.open { '(' } else { '[' },
+            value(&lo.value),
+            value(&hi.value),
+            if hi.open { ')' } else { ']' }
+        ),
+    }
+}
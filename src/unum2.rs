@@ -0,0 +1,609 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::iter;
+use std::ops::*;
+
+use bitvec::prelude::*;
+
+use crate::Posit;
+
+/// Error returned when the values of a lattice are not the right number of increasing values
+/// above one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LatticeError(usize);
+
+impl fmt::Display for LatticeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "lattice must have length {} and increase from above one",
+            self.0
+        )
+    }
+}
+
+impl Error for LatticeError {}
+
+/// Exact quotient of two posits with a positive denominator, which holds the values of a
+/// lattice and their reciprocals.
+#[derive(Clone, Debug)]
+struct Ratio {
+    num: Posit,
+    den: Posit,
+}
+
+impl Ratio {
+    fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    fn neg(&self) -> Self {
+        Ratio {
+            num: -&self.num,
+            den: self.den.clone(),
+        }
+    }
+
+    /// Reciprocal of a ratio other than zero.
+    fn recip(&self) -> Self {
+        if self.num.is_negative() {
+            Ratio {
+                num: -&self.den,
+                den: -&self.num,
+            }
+        } else {
+            Ratio {
+                num: self.den.clone(),
+                den: self.num.clone(),
+            }
+        }
+    }
+
+    fn add(&self, rhs: &Self) -> Self {
+        Ratio {
+            num: self
+                .num
+                .multiply(&rhs.den)
+                .add_signed(&rhs.num.multiply(&self.den), false),
+            den: self.den.multiply(&rhs.den),
+        }
+    }
+
+    fn mul(&self, rhs: &Self) -> Self {
+        Ratio {
+            num: self.num.multiply(&rhs.num),
+            den: self.den.multiply(&rhs.den),
+        }
+    }
+}
+
+impl From<Posit> for Ratio {
+    fn from(p: Posit) -> Self {
+        Ratio {
+            num: p,
+            den: Posit::one(),
+        }
+    }
+}
+
+impl PartialEq for Ratio {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ratio {}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.num
+            .multiply(&other.den)
+            .cmp(&other.num.multiply(&self.den))
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == Posit::one() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Point of the affine real line.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Real {
+    NegInf,
+    Finite(Ratio),
+    PosInf,
+}
+
+impl Real {
+    fn neg(&self) -> Self {
+        match self {
+            Real::NegInf => Real::PosInf,
+            Real::Finite(r) => Real::Finite(r.neg()),
+            Real::PosInf => Real::NegInf,
+        }
+    }
+
+    /// Sum of two bounds on the same side, which are never infinities of opposite signs.
+    fn add(&self, rhs: &Self) -> Self {
+        match (self, rhs) {
+            (Real::Finite(l), Real::Finite(r)) => Real::Finite(l.add(r)),
+            (Real::Finite(_), infinity) | (infinity, _) => infinity.clone(),
+        }
+    }
+
+    /// Product of two magnitudes, of which an infinite one never meets zero.
+    fn mul(&self, rhs: &Self) -> Self {
+        match (self, rhs) {
+            (Real::Finite(l), Real::Finite(r)) => Real::Finite(l.mul(r)),
+            _ => Real::PosInf,
+        }
+    }
+
+    /// Reciprocal of a magnitude.
+    fn recip(&self) -> Self {
+        match self {
+            Real::Finite(r) if r.is_zero() => Real::PosInf,
+            Real::Finite(r) => Real::Finite(r.recip()),
+            _ => Real::Finite(Ratio::from(Posit::zero())),
+        }
+    }
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Real::NegInf => f.write_str("-inf"),
+            Real::Finite(r) => write!(f, "{}", r),
+            Real::PosInf => f.write_str("inf"),
+        }
+    }
+}
+
+/// Nonempty interval of the real line, whose infinite bounds are open.
+#[derive(Clone, Debug)]
+struct Interval {
+    lo: Real,
+    lo_open: bool,
+    hi: Real,
+    hi_open: bool,
+}
+
+impl Interval {
+    fn point(value: Real) -> Self {
+        Interval {
+            lo: value.clone(),
+            lo_open: false,
+            hi: value,
+            hi_open: false,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        matches!(&self.hi, Real::Finite(r) if r.is_zero()) && self.lo == self.hi
+    }
+
+    fn neg(&self) -> Self {
+        Interval {
+            lo: self.hi.neg(),
+            lo_open: self.hi_open,
+            hi: self.lo.neg(),
+            hi_open: self.lo_open,
+        }
+    }
+
+    /// Whether the values are negative, and their magnitudes. The interval holds no values of
+    /// both signs.
+    fn magnitude(&self) -> (bool, Self) {
+        if self.hi <= Real::Finite(Ratio::from(Posit::zero())) {
+            (true, self.neg())
+        } else {
+            (false, self.clone())
+        }
+    }
+
+    /// Interval of the values with the given sign and magnitudes.
+    fn with_sign(self, negative: bool) -> Self {
+        if negative {
+            self.neg()
+        } else {
+            self
+        }
+    }
+}
+
+/// Values of a unum: the point at infinity or an interval of the real line.
+enum Piece {
+    Infinity,
+    Real(Interval),
+}
+
+/// Type II unums of `N` bits on the lattice of a user, with their arithmetic tables.
+///
+/// The exact values of the unums are zero, the point at infinity of the projective reals, one,
+/// the values of the lattice above one and their reciprocals, and the negations of all these.
+/// Unums are the bit patterns from 0 to `2^N - 1` around the circle of the projective reals:
+/// even ones are the exact values in increasing order from zero up to infinity at `2^(N-1)`
+/// and back up from the negatives, odd ones the open intervals between neighbours.
+///
+/// Arithmetic works on [`Sorn`]s, sets of unums, through tables of the sums, products and
+/// reciprocals of all unums which are exact up to the lattice. Operations without a defined
+/// result, like `inf + inf` or `0 * inf`, give every unum.
+#[derive(Clone, Debug)]
+pub struct Unum2<const N: usize> {
+    /// Exact values of the real line in increasing order, without the point at infinity.
+    points: Vec<Ratio>,
+    add: Vec<Sorn<N>>,
+    mul: Vec<Sorn<N>>,
+    recip: Vec<Sorn<N>>,
+}
+
+impl<const N: usize> Unum2<N> {
+    /// Unum of the exact zero.
+    pub const ZERO: u32 = 0;
+    /// Unum of the point at infinity.
+    pub const INFINITY: u32 = 1 << (N - 1);
+
+    const SIZE: () = assert!(N >= 3 && N <= 8, "Type II unums have 3 to 8 bits");
+
+    /// Unums on the lattice of the `2^(N-3) - 1` increasing values above one of `lattice`.
+    pub fn new(lattice: &[Posit]) -> Result<Self, LatticeError> {
+        let () = Self::SIZE;
+        let expected = (1 << (N - 3)) - 1;
+        let increasing = lattice.windows(2).all(|pair| pair[0] < pair[1]);
+        if lattice.len() != expected || !increasing || lattice.iter().any(|x| *x <= Posit::one()) {
+            return Err(LatticeError(expected));
+        }
+        let positive: Vec<_> = lattice
+            .iter()
+            .rev()
+            .map(|x| Ratio::from(x.clone()).recip())
+            .chain(iter::once(Ratio::from(Posit::one())))
+            .chain(lattice.iter().cloned().map(Ratio::from))
+            .collect();
+        let points = positive
+            .iter()
+            .rev()
+            .map(Ratio::neg)
+            .chain(iter::once(Ratio::from(Posit::zero())))
+            .chain(positive.iter().cloned())
+            .collect();
+        let mut unum2 = Unum2 {
+            points,
+            add: Vec::new(),
+            mul: Vec::new(),
+            recip: Vec::new(),
+        };
+        let pieces: Vec<_> = (0..1 << N).map(|u| unum2.piece(u)).collect();
+        for x in &pieces {
+            for y in &pieces {
+                unum2.add.push(unum2.sum(x, y));
+                unum2.mul.push(unum2.product(x, y));
+            }
+            unum2.recip.push(unum2.reciprocal(x));
+        }
+        Ok(unum2)
+    }
+
+    /// Unum holding the posit: its exact value, or the open interval around it. NaR is the
+    /// point at infinity.
+    pub fn from_posit<const ES: usize>(&self, p: &Posit<ES>) -> u32 {
+        let value = match p.decode() {
+            Some(decoded) => Posit::from_scale(decoded.sign, decoded.scale(), &decoded.fraction),
+            None if p.is_nar() => return Self::INFINITY,
+            None => Posit::zero(),
+        };
+        self.enclosing(&Interval::point(Real::Finite(Ratio::from(value))))
+            .unums()
+            .next()
+            .expect("the lattice encloses every point in exactly one unum")
+    }
+
+    pub fn add(&self, x: &Sorn<N>, y: &Sorn<N>) -> Sorn<N> {
+        Self::apply(&self.add, x, y)
+    }
+
+    pub fn sub(&self, x: &Sorn<N>, y: &Sorn<N>) -> Sorn<N> {
+        self.add(x, &-y.clone())
+    }
+
+    pub fn mul(&self, x: &Sorn<N>, y: &Sorn<N>) -> Sorn<N> {
+        Self::apply(&self.mul, x, y)
+    }
+
+    /// Quotient as the product by the reciprocal, so `0 / 0` and `inf / inf` give every unum.
+    pub fn div(&self, x: &Sorn<N>, y: &Sorn<N>) -> Sorn<N> {
+        self.mul(x, &self.recip(y))
+    }
+
+    pub fn recip(&self, x: &Sorn<N>) -> Sorn<N> {
+        let mut result = Sorn::empty();
+        for u in x.unums() {
+            result.union_with(&self.recip[u as usize]);
+        }
+        result
+    }
+
+    /// Formats the values of a SORN as the intervals and points they cover in increasing
+    /// order, with the point at infinity last.
+    pub fn display<'a>(&'a self, sorn: &'a Sorn<N>) -> impl fmt::Display + 'a {
+        SornDisplay { unum2: self, sorn }
+    }
+
+    /// Union of the entries of `table` for the pairs of unums of `x` and `y`.
+    fn apply(table: &[Sorn<N>], x: &Sorn<N>, y: &Sorn<N>) -> Sorn<N> {
+        let mut result = Sorn::empty();
+        for i in x.unums() {
+            for j in y.unums() {
+                result.union_with(&table[(i << N | j) as usize]);
+            }
+        }
+        result
+    }
+
+    /// Unum at the position `r` on the real line, from `(-inf, -maxreal)` at zero up to
+    /// `(maxreal, inf)`.
+    fn unum_at(r: usize) -> u32 {
+        ((r + (1 << (N - 1)) + 1) % (1 << N)) as u32
+    }
+
+    /// Values of the unum at the position `r` on the real line.
+    fn interval_at(&self, r: usize) -> Interval {
+        if r % 2 == 1 {
+            return Interval::point(Real::Finite(self.points[r / 2].clone()));
+        }
+        let lo = match r {
+            0 => Real::NegInf,
+            _ => Real::Finite(self.points[r / 2 - 1].clone()),
+        };
+        let hi = self
+            .points
+            .get(r / 2)
+            .map_or(Real::PosInf, |p| Real::Finite(p.clone()));
+        Interval {
+            lo,
+            lo_open: true,
+            hi,
+            hi_open: true,
+        }
+    }
+
+    fn piece(&self, u: u32) -> Piece {
+        if u == Self::INFINITY {
+            return Piece::Infinity;
+        }
+        let r = (u as usize + (1 << (N - 1)) - 1) % (1 << N);
+        Piece::Real(self.interval_at(r))
+    }
+
+    /// Unums whose values meet the interval.
+    fn enclosing(&self, interval: &Interval) -> Sorn<N> {
+        // Index of the first point at least `value` and whether it is `value`
+        let find = |value: &Ratio| {
+            let i = self.points.partition_point(|p| p < value);
+            (i, self.points.get(i) == Some(value))
+        };
+        let first = match &interval.lo {
+            Real::NegInf => 0,
+            Real::Finite(lo) => match find(lo) {
+                (i, true) => 2 * i + 1 + interval.lo_open as usize,
+                (i, false) => 2 * i,
+            },
+            Real::PosInf => unreachable!(),
+        };
+        let last = match &interval.hi {
+            Real::NegInf => unreachable!(),
+            Real::Finite(hi) => match find(hi) {
+                (i, true) => 2 * i + 1 - interval.hi_open as usize,
+                (i, false) => 2 * i,
+            },
+            Real::PosInf => 2 * self.points.len(),
+        };
+        let mut sorn = Sorn::empty();
+        for r in first..=last {
+            sorn.insert(Self::unum_at(r));
+        }
+        sorn
+    }
+
+    fn sum(&self, x: &Piece, y: &Piece) -> Sorn<N> {
+        match (x, y) {
+            (Piece::Infinity, Piece::Infinity) => Sorn::everything(),
+            (Piece::Infinity, _) | (_, Piece::Infinity) => Sorn::from_unum(Self::INFINITY),
+            (Piece::Real(x), Piece::Real(y)) => self.enclosing(&Interval {
+                lo: x.lo.add(&y.lo),
+                lo_open: x.lo_open || y.lo_open,
+                hi: x.hi.add(&y.hi),
+                hi_open: x.hi_open || y.hi_open,
+            }),
+        }
+    }
+
+    fn product(&self, x: &Piece, y: &Piece) -> Sorn<N> {
+        match (x, y) {
+            (Piece::Infinity, Piece::Real(r)) | (Piece::Real(r), Piece::Infinity)
+                if r.is_zero() =>
+            {
+                Sorn::everything()
+            }
+            (Piece::Infinity, _) | (_, Piece::Infinity) => Sorn::from_unum(Self::INFINITY),
+            (Piece::Real(x), Piece::Real(y)) if x.is_zero() || y.is_zero() => {
+                Sorn::from_unum(Self::ZERO)
+            }
+            (Piece::Real(x), Piece::Real(y)) => {
+                let ((x_negative, x), (y_negative, y)) = (x.magnitude(), y.magnitude());
+                let magnitude = Interval {
+                    lo: x.lo.mul(&y.lo),
+                    lo_open: x.lo_open || y.lo_open,
+                    hi: x.hi.mul(&y.hi),
+                    hi_open: x.hi_open || y.hi_open,
+                };
+                self.enclosing(&magnitude.with_sign(x_negative != y_negative))
+            }
+        }
+    }
+
+    fn reciprocal(&self, x: &Piece) -> Sorn<N> {
+        match x {
+            Piece::Infinity => Sorn::from_unum(Self::ZERO),
+            Piece::Real(x) if x.is_zero() => Sorn::from_unum(Self::INFINITY),
+            Piece::Real(x) => {
+                let (negative, x) = x.magnitude();
+                let magnitude = Interval {
+                    lo: x.hi.recip(),
+                    lo_open: x.hi_open,
+                    hi: x.lo.recip(),
+                    hi_open: x.lo_open,
+                };
+                self.enclosing(&magnitude.with_sign(negative))
+            }
+        }
+    }
+}
+
+struct SornDisplay<'a, const N: usize> {
+    unum2: &'a Unum2<N>,
+    sorn: &'a Sorn<N>,
+}
+
+impl<const N: usize> fmt::Display for SornDisplay<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let contains = |r: usize| self.sorn.contains(Unum2::<N>::unum_at(r));
+        let mut parts = Vec::new();
+        let mut r = 0;
+        while r < (1 << N) - 1 {
+            if !contains(r) {
+                r += 1;
+                continue;
+            }
+            // Runs of neighbouring unums make a single interval
+            let first = r;
+            while r + 1 < (1 << N) - 1 && contains(r + 1) {
+                r += 1;
+            }
+            parts.push((first, r));
+            r += 1;
+        }
+        f.write_str("{")?;
+        for (i, &(first, last)) in parts.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            let lo = self.unum2.interval_at(first);
+            let hi = self.unum2.interval_at(last);
+            if first == last && first % 2 == 1 {
+                lo.lo.fmt(f)?;
+                continue;
+            }
+            f.write_str(if lo.lo_open { "(" } else { "[" })?;
+            lo.lo.fmt(f)?;
+            f.write_str(", ")?;
+            hi.hi.fmt(f)?;
+            f.write_str(if hi.hi_open { ")" } else { "]" })?;
+        }
+        if self.sorn.contains(Unum2::<N>::INFINITY) {
+            f.write_str(if parts.is_empty() { "inf" } else { ", inf" })?;
+        }
+        f.write_str("}")
+    }
+}
+
+/// Set of Type II unums of `N` bits, one bit for each.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Sorn<const N: usize> {
+    bits: BitVec,
+}
+
+impl<const N: usize> Sorn<N> {
+    pub fn empty() -> Self {
+        Sorn {
+            bits: bitvec![0; 1 << N],
+        }
+    }
+
+    /// All unums, the projective reals.
+    pub fn everything() -> Self {
+        Sorn {
+            bits: bitvec![1; 1 << N],
+        }
+    }
+
+    pub fn from_unum(u: u32) -> Self {
+        let mut sorn = Self::empty();
+        sorn.insert(u);
+        sorn
+    }
+
+    pub fn insert(&mut self, u: u32) {
+        self.bits.set(u as usize, true);
+    }
+
+    pub fn contains(&self, u: u32) -> bool {
+        self.bits.get(u as usize).unwrap_or(false)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.not_any()
+    }
+
+    /// Unums of the set in increasing order of their bits.
+    pub fn unums(&self) -> impl Iterator<Item = u32> + '_ {
+        self.bits
+            .iter()
+            .enumerate()
+            .filter(|&(_, bit)| bit)
+            .map(|(u, _)| u as u32)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for (byte, other) in result
+            .bits
+            .as_mut_slice()
+            .iter_mut()
+            .zip(other.bits.as_slice())
+        {
+            *byte &= other;
+        }
+        result
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        for (byte, other) in self
+            .bits
+            .as_mut_slice()
+            .iter_mut()
+            .zip(other.bits.as_slice())
+        {
+            *byte |= other;
+        }
+    }
+}
+
+impl<const N: usize> Neg for Sorn<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut result = Self::empty();
+        for u in self.unums() {
+            result.insert(u.wrapping_neg() % (1 << N));
+        }
+        result
+    }
+}
@@ -0,0 +1,534 @@
+use std::cmp::{self, Ordering};
+use std::fmt;
+use std::ops::*;
+
+use crate::{Posit, PositContext};
+
+/// Bound of a valid or a ubound on the extended real line.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum Value<const ES: usize> {
+    NegInf,
+    Finite(Posit<ES>),
+    PosInf,
+}
+
+/// Bound of an interval and whether it is excluded from it, its ubit.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct End<const ES: usize> {
+    pub(crate) value: Value<ES>,
+    pub(crate) open: bool,
+}
+
+impl<const ES: usize> Value<ES> {
+    pub(crate) fn infinity(negative: bool) -> Self {
+        if negative {
+            Value::NegInf
+        } else {
+            Value::PosInf
+        }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        matches!(self, Value::Finite(p) if p.is_zero())
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        match self {
+            Value::NegInf => Value::PosInf,
+            Value::Finite(p) => Value::Finite(-p.clone()),
+            Value::PosInf => Value::NegInf,
+        }
+    }
+}
+
+impl<const ES: usize> End<ES> {
+    pub(crate) fn new(value: Value<ES>, open: bool) -> Self {
+        End { value, open }
+    }
+
+    /// Sign of the values of the interval near this bound: a zero lower bound is approached from
+    /// above and a zero upper bound from below.
+    pub(crate) fn is_negative(&self, lower: bool) -> bool {
+        match &self.value {
+            Value::NegInf => true,
+            Value::Finite(p) if p.is_zero() => !lower,
+            Value::Finite(p) => p.is_negative(),
+            Value::PosInf => false,
+        }
+    }
+
+    /// Rounds the bound outward to `nbits` bits, excluding it if it moved.
+    fn round(&mut self, nbits: usize, lower: bool) {
+        if let Value::Finite(p) = &mut self.value {
+            self.open |= p.round_directed(nbits, !lower);
+            if p.is_nar() {
+                self.value = Value::infinity(lower);
+            }
+        }
+    }
+}
+
+/// Lower bounds ordered by the sets they bound: a closed bound is below an open one at the
+/// same value.
+pub(crate) fn cmp_lower<const ES: usize>(l: &End<ES>, r: &End<ES>) -> Ordering {
+    l.value.cmp(&r.value).then(l.open.cmp(&r.open))
+}
+
+/// Upper bounds ordered by the sets they bound: an open bound is below a closed one at the
+/// same value.
+pub(crate) fn cmp_upper<const ES: usize>(l: &End<ES>, r: &End<ES>) -> Ordering {
+    l.value.cmp(&r.value).then(r.open.cmp(&l.open))
+}
+
+/// Type III valid: an interval of the real line whose bounds are posits, each of which may be
+/// excluded by its ubit.
+///
+/// A bound rounded to a posit length is excluded when it was inexact, so that the valid lies
+/// in the open interval between two adjacent posits instead of growing to both of them.
+/// Bounds beyond maxpos round to the infinities. Besides the intervals, a valid may be empty
+/// or NaR, the set of everything, which results from NaR and from division by zero.
+///
+/// Sums, differences and products are exact, their bounds grow like the results of posit
+/// arithmetic. Quotients cannot be exact, so `/` rounds their bounds outward to the width of the
+/// current [`PositContext`], or without one to 16 bits more than the longest bound, like posit
+/// quotients. The `*_rounded` methods round outward to a width of their own.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Valid<const ES: usize = 2> {
+    set: Set<ES>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Set<const ES: usize> {
+    Empty,
+    Nar,
+    Interval(End<ES>, End<ES>),
+}
+
+impl<const ES: usize> Valid<ES> {
+    /// Interval from `lo` to `hi`, each excluded if it is open. A NaR bound stands for an
+    /// infinity, which is always excluded. Empty if `lo` is above `hi`.
+    pub fn new(lo: Posit<ES>, lo_open: bool, hi: Posit<ES>, hi_open: bool) -> Self {
+        let end = |p: Posit<ES>, open: bool, lower: bool| {
+            if p.is_nar() {
+                End::new(Value::infinity(lower), true)
+            } else {
+                End::new(Value::Finite(p), open)
+            }
+        };
+        Self::interval(end(lo, lo_open, true), end(hi, hi_open, false))
+    }
+
+    /// Closed interval `[lo, hi]`.
+    pub fn closed(lo: Posit<ES>, hi: Posit<ES>) -> Self {
+        Self::new(lo, false, hi, false)
+    }
+
+    /// Open interval `(lo, hi)`.
+    pub fn open(lo: Posit<ES>, hi: Posit<ES>) -> Self {
+        Self::new(lo, true, hi, true)
+    }
+
+    pub fn empty() -> Self {
+        Valid { set: Set::Empty }
+    }
+
+    /// The set of all reals and NaR.
+    pub fn nar() -> Self {
+        Valid { set: Set::Nar }
+    }
+
+    /// All reals, `(-∞, ∞)`.
+    pub fn reals() -> Self {
+        Self::interval(End::new(Value::NegInf, true), End::new(Value::PosInf, true))
+    }
+
+    fn interval(lo: End<ES>, hi: End<ES>) -> Self {
+        let set = match lo.value.cmp(&hi.value) {
+            Ordering::Less => Set::Interval(lo, hi),
+            Ordering::Equal if !lo.open && !hi.open => Set::Interval(lo, hi),
+            _ => Set::Empty,
+        };
+        Valid { set }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set == Set::Empty
+    }
+
+    pub fn is_nar(&self) -> bool {
+        self.set == Set::Nar
+    }
+
+    /// Lower bound and whether it is excluded, NaR for -∞. `None` if the valid is empty or
+    /// NaR.
+    pub fn lo(&self) -> Option<(Posit<ES>, bool)> {
+        match &self.set {
+            Set::Interval(lo, _) => Some(Self::bound(lo)),
+            _ => None,
+        }
+    }
+
+    /// Upper bound and whether it is excluded, NaR for ∞. `None` if the valid is empty or
+    /// NaR.
+    pub fn hi(&self) -> Option<(Posit<ES>, bool)> {
+        match &self.set {
+            Set::Interval(_, hi) => Some(Self::bound(hi)),
+            _ => None,
+        }
+    }
+
+    fn bound(end: &End<ES>) -> (Posit<ES>, bool) {
+        match &end.value {
+            Value::Finite(p) => (p.clone(), end.open),
+            _ => (Posit::nar(), true),
+        }
+    }
+
+    pub fn contains(&self, p: &Posit<ES>) -> bool {
+        match &self.set {
+            Set::Empty => false,
+            Set::Nar => true,
+            _ if p.is_nar() => false,
+            Set::Interval(lo, hi) => {
+                let point = End::new(Value::Finite(p.clone()), false);
+                cmp_lower(lo, &point) != Ordering::Greater
+                    && cmp_upper(&point, hi) != Ordering::Greater
+            }
+        }
+    }
+
+    /// Distance between the bounds, exact. Zero for the empty valid, NaR for unbounded ones.
+    pub fn width(&self) -> Posit<ES> {
+        match &self.set {
+            Set::Empty => Posit::zero(),
+            Set::Interval(
+                End {
+                    value: Value::Finite(lo),
+                    ..
+                },
+                End {
+                    value: Value::Finite(hi),
+                    ..
+                },
+            ) => hi.add_signed(lo, true),
+            _ => Posit::nar(),
+        }
+    }
+
+    /// Values in both valids.
+    pub fn intersection(&self, other: &Self) -> Self {
+        match (&self.set, &other.set) {
+            (Set::Empty, _) | (_, Set::Empty) => Self::empty(),
+            (Set::Nar, _) => other.clone(),
+            (_, Set::Nar) => self.clone(),
+            (Set::Interval(l_lo, l_hi), Set::Interval(r_lo, r_hi)) => Self::interval(
+                cmp::max_by(l_lo, r_lo, |l, r| cmp_lower(l, r)).clone(),
+                cmp::min_by(l_hi, r_hi, |l, r| cmp_upper(l, r)).clone(),
+            ),
+        }
+    }
+
+    /// Smallest valid holding the values of both, which also holds the gap between them if
+    /// they are disjoint.
+    pub fn union(&self, other: &Self) -> Self {
+        match (&self.set, &other.set) {
+            (Set::Nar, _) | (_, Set::Nar) => Self::nar(),
+            (Set::Empty, _) => other.clone(),
+            (_, Set::Empty) => self.clone(),
+            (Set::Interval(l_lo, l_hi), Set::Interval(r_lo, r_hi)) => Self::interval(
+                cmp::min_by(l_lo, r_lo, |l, r| cmp_lower(l, r)).clone(),
+                cmp::max_by(l_hi, r_hi, |l, r| cmp_upper(l, r)).clone(),
+            ),
+        }
+    }
+
+    /// Rounds the bounds outward to `nbits` bits, the result contains the valid.
+    pub fn round_to(&mut self, nbits: usize) {
+        if let Set::Interval(lo, hi) = &mut self.set {
+            lo.round(nbits, true);
+            hi.round(nbits, false);
+        }
+    }
+
+    pub fn add_rounded(self, rhs: Self, nbits: usize) -> Self {
+        let mut result = self + rhs;
+        result.round_to(nbits);
+        result
+    }
+
+    pub fn sub_rounded(self, rhs: Self, nbits: usize) -> Self {
+        let mut result = self - rhs;
+        result.round_to(nbits);
+        result
+    }
+
+    pub fn mul_rounded(self, rhs: Self, nbits: usize) -> Self {
+        let mut result = self * rhs;
+        result.round_to(nbits);
+        result
+    }
+
+    /// Quotient with its bounds rounded outward to `nbits` bits. NaR if the divisor contains
+    /// zero.
+    pub fn div_rounded(self, rhs: Self, nbits: usize) -> Self {
+        Self::combine(self, rhs, |l, r| quotient(l, r, nbits))
+    }
+
+    /// Length of the longest finite bound, 0 without any.
+    fn bound_len(&self) -> usize {
+        match &self.set {
+            Set::Interval(lo, hi) => [lo, hi]
+                .iter()
+                .filter_map(|end| match &end.value {
+                    Value::Finite(p) => Some(p.bits.len()),
+                    _ => None,
+                })
+                .max()
+                .unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// Applies `f` to the bounds of two valids, whose result is NaR for `None`.
+    fn combine(
+        lhs: Self,
+        rhs: Self,
+        f: impl FnOnce(&Bounds<ES>, &Bounds<ES>) -> Option<Bounds<ES>>,
+    ) -> Self {
+        match (lhs.set, rhs.set) {
+            (Set::Nar, _) | (_, Set::Nar) => Self::nar(),
+            (Set::Empty, _) | (_, Set::Empty) => Self::empty(),
+            (Set::Interval(l_lo, l_hi), Set::Interval(r_lo, r_hi)) => {
+                match f(&(l_lo, l_hi), &(r_lo, r_hi)) {
+                    Some((lo, hi)) => Self::interval(lo, hi),
+                    None => Self::nar(),
+                }
+            }
+        }
+    }
+}
+
+/// Lower and upper bound of a nonempty interval of the extended reals.
+///
+/// Valids always exclude the infinities, the bounds of Type I ubounds may include them. The
+/// operations on bounds return `None` for NaR, which results from operations on included
+/// infinities as well as from division by intervals holding zero.
+pub(crate) type Bounds<const ES: usize> = (End<ES>, End<ES>);
+
+/// Whether the interval holds `value`.
+pub(crate) fn includes<const ES: usize>((lo, hi): &Bounds<ES>, value: &Value<ES>) -> bool {
+    let point = End::new(value.clone(), false);
+    cmp_lower(lo, &point) != Ordering::Greater && cmp_upper(&point, hi) != Ordering::Greater
+}
+
+fn includes_infinity<const ES: usize>(bounds: &Bounds<ES>) -> bool {
+    includes(bounds, &Value::NegInf) || includes(bounds, &Value::PosInf)
+}
+
+pub(crate) fn negation<const ES: usize>((lo, hi): &Bounds<ES>) -> Bounds<ES> {
+    (
+        End::new(hi.value.neg(), hi.open),
+        End::new(lo.value.neg(), lo.open),
+    )
+}
+
+/// Exact sum of two intervals.
+pub(crate) fn sum<const ES: usize>(l: &Bounds<ES>, r: &Bounds<ES>) -> Option<Bounds<ES>> {
+    let (neg, pos) = (Value::NegInf, Value::PosInf);
+    if (includes(l, &neg) && includes(r, &pos)) || (includes(l, &pos) && includes(r, &neg)) {
+        return None;
+    }
+    let add = |x: &End<ES>, y: &End<ES>| match (&x.value, &y.value) {
+        (Value::Finite(a), Value::Finite(b)) => End::new(Value::Finite(a.add_signed(b, false)), x.open || y.open),
+        (Value::Finite(_), _) => y.clone(),
+        (_, Value::Finite(_)) => x.clone(),
+        _ if x.value == y.value => End::new(x.value.clone(), x.open && y.open),
+        // Infinities of opposite signs only meet on the same side if one is excluded
+        _ if x.open => y.clone(),
+        _ => x.clone(),
+    };
+    Some((add(&l.0, &r.0), add(&l.1, &r.1)))
+}
+
+/// Exact product of two intervals.
+pub(crate) fn product<const ES: usize>(l: &Bounds<ES>, r: &Bounds<ES>) -> Option<Bounds<ES>> {
+    let zero = Value::Finite(Posit::zero());
+    if (includes(l, &zero) && includes_infinity(r)) || (includes_infinity(l) && includes(r, &zero))
+    {
+        return None;
+    }
+    Some(hull(l, r, |x, x_lower, y, y_lower| {
+        let exact = |end: End<ES>| vec![(end.clone(), end)];
+        // Zero times anything in the interval is zero
+        if (x.value.is_zero() && !x.open) || (y.value.is_zero() && !y.open) {
+            return exact(End::new(Value::Finite(Posit::zero()), false));
+        }
+        let infinity = Value::infinity(x.is_negative(x_lower) != y.is_negative(y_lower));
+        let (infinite, other) = match (&x.value, &y.value) {
+            (Value::Finite(a), Value::Finite(b)) => {
+                return exact(End::new(Value::Finite(a.multiply(b)), x.open || y.open))
+            }
+            (Value::Finite(_), _) => (y, x),
+            _ => (x, y),
+        };
+        if infinite.open && other.value.is_zero() {
+            // Values near zero times values near infinity may give any product of their sign
+            let zero = End::new(Value::Finite(Posit::zero()), true);
+            let infinity = End::new(infinity, true);
+            vec![(zero.clone(), zero), (infinity.clone(), infinity)]
+        } else {
+            exact(End::new(infinity, infinite.open))
+        }
+    }))
+}
+
+/// Quotient of two intervals with its bounds rounded outward to `nbits` bits.
+pub(crate) fn quotient<const ES: usize>(
+    l: &Bounds<ES>,
+    r: &Bounds<ES>,
+    nbits: usize,
+) -> Option<Bounds<ES>> {
+    let closed_infinity = |(lo, hi): &Bounds<ES>| {
+        (lo.value == Value::NegInf && !lo.open) || (hi.value == Value::PosInf && !hi.open)
+    };
+    if includes(r, &Value::Finite(Posit::zero())) || (closed_infinity(l) && closed_infinity(r)) {
+        return None;
+    }
+    Some(hull(l, r, |x, x_lower, y, y_lower| {
+        let exact = |end: End<ES>| vec![(end.clone(), end)];
+        let zero = |open| End::new(Value::Finite(Posit::zero()), open);
+        let infinity = |open| {
+            let negative = x.is_negative(x_lower) != y.is_negative(y_lower);
+            End::new(Value::infinity(negative), open)
+        };
+        let x_small = x.value.is_zero();
+        let x_large = matches!(x.value, Value::NegInf | Value::PosInf);
+        let y_small = y.value.is_zero();
+        let y_large = matches!(y.value, Value::NegInf | Value::PosInf);
+        match (&x.value, &y.value) {
+            _ if x_small && !x.open => exact(zero(false)),
+            _ if x_large && !x.open => exact(infinity(false)),
+            _ if y_large && !y.open => exact(zero(false)),
+            // Both tend to zero or both to infinity, any quotient of their sign is possible
+            _ if (x_small && y_small) || (x_large && y_large) => {
+                vec![(zero(true), zero(true)), (infinity(true), infinity(true))]
+            }
+            _ if x_large || y_small => exact(infinity(true)),
+            _ if y_large => exact(zero(true)),
+            (Value::Finite(a), Value::Finite(b)) => {
+                let open = x.open || y.open;
+                // Rounding beyond maxpos gives NaR, an infinity
+                let bound = |lower: bool| match a.div_directed(b, nbits, !lower) {
+                    (p, _) if p.is_nar() => End::new(Value::infinity(lower), true),
+                    (p, inexact) => End::new(Value::Finite(p), open || inexact),
+                };
+                vec![(bound(true), bound(false))]
+            }
+            _ => unreachable!(),
+        }
+    }))
+}
+
+/// Hull of the bounds `f` gives for the pairs of bounds of `l` and `r`. `f` returns
+/// candidates for the lower bound and for the upper bound of the result.
+fn hull<const ES: usize>(
+    (l_lo, l_hi): &Bounds<ES>,
+    (r_lo, r_hi): &Bounds<ES>,
+    f: impl Fn(&End<ES>, bool, &End<ES>, bool) -> Vec<Bounds<ES>>,
+) -> Bounds<ES> {
+    let mut candidates = Vec::new();
+    for &(x, x_lower) in &[(l_lo, true), (l_hi, false)] {
+        for &(y, y_lower) in &[(r_lo, true), (r_hi, false)] {
+            candidates.extend(f(x, x_lower, y, y_lower));
+        }
+    }
+    let lo = candidates.iter().map(|(lo, _)| lo);
+    let hi = candidates.iter().map(|(_, hi)| hi);
+    (
+        lo.min_by(|l, r| cmp_lower(l, r)).unwrap().clone(),
+        hi.max_by(|l, r| cmp_upper(l, r)).unwrap().clone(),
+    )
+}
+
+impl<const ES: usize> From<Posit<ES>> for Valid<ES> {
+    /// The single value of the posit, or NaR.
+    fn from(p: Posit<ES>) -> Self {
+        if p.is_nar() {
+            Self::nar()
+        } else {
+            Self::closed(p.clone(), p)
+        }
+    }
+}
+
+impl<const ES: usize> Neg for Valid<ES> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        match self.set {
+            Set::Interval(lo, hi) => {
+                let (lo, hi) = negation(&(lo, hi));
+                Self::interval(lo, hi)
+            }
+            _ => self,
+        }
+    }
+}
+
+impl<const ES: usize> Add for Valid<ES> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::combine(self, rhs, sum)
+    }
+}
+
+impl<const ES: usize> Sub for Valid<ES> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const ES: usize> Mul for Valid<ES> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::combine(self, rhs, product)
+    }
+}
+
+impl<const ES: usize> Div for Valid<ES> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let nbits = PositContext::current()
+            .nbits_for(ES)
+            .unwrap_or_else(|| cmp::max(self.bound_len(), rhs.bound_len()) + 16);
+        self.div_rounded(rhs, nbits)
+    }
+}
+
+/// Interval notation with brackets for included bounds and parentheses for excluded ones.
+impl<const ES: usize> fmt::Display for Valid<ES> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = |value: &Value<ES>| match value {
+            Value::NegInf => "-inf".to_string(),
+            Value::Finite(p) => p.to_string(),
+            Value::PosInf => "inf".to_string(),
+        };
+        match &self.set {
+            Set::Empty => f.write_str("empty"),
+            Set::Nar => f.write_str("NaR"),
+            Set::Interval(lo, hi) => write!(
+                f,
+                "{}{}, {}{}",
+                if lo.open { '(' } else { '[' },
+                value(&lo.value),
+                value(&hi.value),
+                if hi.open { ')' } else { ']' }
+            ),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+/* Calls the C API with null and malformed arguments, the way C code would. */
+
+#include <math.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "unum.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                       \
+  do {                                                                         \
+    if (!(condition)) {                                                        \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,        \
+              #condition);                                                     \
+      failures++;                                                              \
+    }                                                                          \
+  } while (0)
+
+/* Whether the last error of the thread mentions `text`. */
+static int last_error_mentions(const char *text) {
+  const char *message = unum_last_error();
+  return message != NULL && strstr(message, text) != NULL;
+}
+
+static void null_handles(void) {
+  Posit *x = posit_from_int64(3);
+  double value = 0.0;
+  int32_t order = 0;
+
+  CHECK(posit_add(x, NULL) == NULL);
+  CHECK(last_error_mentions("`rhs` is null"));
+  CHECK(posit_neg(NULL) == NULL);
+  CHECK(last_error_mentions("`p` is null"));
+  CHECK(posit_clone(NULL) == NULL);
+  CHECK(posit_to_string(NULL) == NULL);
+  CHECK(posit_to_double(NULL, &value) == UNUM_STATUS_NULL_POINTER);
+  CHECK(posit_to_double(x, NULL) == UNUM_STATUS_NULL_POINTER);
+  CHECK(last_error_mentions("`out` is null"));
+  CHECK(posit_compare(x, NULL, &order) == UNUM_STATUS_NULL_POINTER);
+  CHECK(posit_mul_into(NULL, x, x) == UNUM_STATUS_NULL_POINTER);
+  CHECK(posit_div_into(x, NULL, x) == UNUM_STATUS_NULL_POINTER);
+  CHECK(posit_round(NULL, 8) == UNUM_STATUS_NULL_POINTER);
+  CHECK(posit_set_double(NULL, 1.0) == UNUM_STATUS_NULL_POINTER);
+
+  /* The failed calls left the handle alone */
+  CHECK(posit_to_double(x, &value) == UNUM_STATUS_OK);
+  CHECK(value == 3.0);
+
+  posit_free(NULL);
+  posit_string_free(NULL);
+  posit_free(x);
+}
+
+static void malformed_arguments(void) {
+  Posit *x = posit_from_string("0.5");
+  double value = 0.0;
+
+  CHECK(posit_from_string(NULL) == NULL);
+  CHECK(last_error_mentions("`s` is null"));
+  CHECK(posit_from_string("1.2.3") == NULL);
+  CHECK(last_error_mentions("invalid posit literal"));
+  CHECK(posit_from_string("\xff") == NULL);
+  CHECK(last_error_mentions("UTF-8"));
+  CHECK(posit_set_string(x, "") == UNUM_STATUS_INVALID_ARGUMENT);
+  CHECK(posit_sqrt(x, 1) == NULL);
+  CHECK(last_error_mentions("`nbits` is 1"));
+  CHECK(posit_sqrt_into(x, x, 0) == UNUM_STATUS_INVALID_ARGUMENT);
+  CHECK(posit_round(x, 1) == UNUM_STATUS_INVALID_ARGUMENT);
+
+  CHECK(posit_to_double(x, &value) == UNUM_STATUS_OK);
+  CHECK(value == 0.5);
+  posit_free(x);
+}
+
+static void arithmetic(void) {
+  Posit *x = posit_from_double(1.5);
+  Posit *y = posit_from_string("-0.25");
+  Posit *sum = posit_add(x, y);
+  char *string = posit_to_string(sum);
+  double value = 0.0;
+  int32_t order = 0;
+
+  CHECK(strcmp(string, "1.25") == 0);
+  CHECK(posit_compare(y, x, &order) == UNUM_STATUS_OK && order == -1);
+  CHECK(posit_pow_into(x, x, 2) == UNUM_STATUS_OK);
+  CHECK(posit_to_double(x, &value) == UNUM_STATUS_OK && value == 2.25);
+  CHECK(posit_sqrt_into(x, x, 32) == UNUM_STATUS_OK);
+  CHECK(posit_to_double(x, &value) == UNUM_STATUS_OK && value == 1.5);
+
+  posit_string_free(string);
+  posit_free(sum);
+  posit_free(y);
+  posit_free(x);
+}
+
+static void softposit(void) {
+  posit32_t one = i32_to_p32(1);
+  posit32_t nar = castP32(0x80000000);
+  posit16_t three = convertDoubleToP16(3.0);
+
+  CHECK(one.v == 0x40000000);
+  CHECK(three.v == 0x5800);
+  CHECK(castP8(0x40).v == p16_to_p8(p16_div(three, three)).v);
+  CHECK(p32_to_f64(p32_add(one, p32_sqrt(i32_to_p32(4)))) == 3.0);
+  CHECK(p32_div(one, castP32(0)).v == nar.v);
+  CHECK(isnan(convertP32ToDouble(nar)));
+  CHECK(p32_to_i64(nar) == INT64_MIN);
+  CHECK(p32_lt(nar, one) && p32_cmp(one, nar) == 1);
+}
+
+int main(void) {
+  CHECK(unum_last_error() == NULL);
+  null_handles();
+  malformed_arguments();
+  arithmetic();
+  softposit();
+  return failures != 0;
+}
@@ -0,0 +1,34 @@
+//! Builds `ffi.c` against the static library and the generated header, and runs it.
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn c_api() {
+    // The static library is built next to this test, the header in the target directory
+    let deps = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let target = env::var("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target"));
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+
+    let compiled = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/ffi.c"))
+        .arg("-I")
+        .arg(&target)
+        .arg("-o")
+        .arg(&program)
+        .arg(deps.join("libunum.a"))
+        .args(["-lpthread", "-ldl", "-lm"])
+        .status()
+        .expect("a C compiler is needed to test the C API");
+    assert!(compiled.success(), "compiling ffi.c failed");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}